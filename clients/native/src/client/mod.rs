@@ -10,6 +10,7 @@ use nym_client_core::client::base_client::storage::OnDiskPersistent;
 use nym_client_core::client::base_client::{
     BaseClientBuilder, ClientInput, ClientOutput, ClientState,
 };
+use nym_pemstore::KeyPassphrase;
 use nym_sphinx::params::PacketType;
 use nym_task::ShutdownManager;
 use nym_validator_client::QueryHttpRpcNyxdClient;
@@ -30,6 +31,9 @@ pub struct SocketClient {
     /// Optional path to a .json file containing standalone network details.
     custom_mixnet: Option<PathBuf>,
 
+    /// Optional passphrase used for decrypting the private keys stored on disk.
+    key_passphrase: Option<KeyPassphrase>,
+
     shutdown_manager: ShutdownManager,
 }
 
@@ -42,10 +46,17 @@ impl SocketClient {
         SocketClient {
             config,
             custom_mixnet,
+            key_passphrase: None,
             shutdown_manager: Default::default(),
         }
     }
 
+    #[must_use]
+    pub fn with_key_passphrase(mut self, key_passphrase: Option<KeyPassphrase>) -> Self {
+        self.key_passphrase = key_passphrase;
+        self
+    }
+
     fn start_websocket_listener(
         config: &Config,
         client_input: ClientInput,
@@ -106,7 +117,8 @@ impl SocketClient {
             self.config.storage_paths.common_paths.clone(),
             &self.config.base.debug,
         )
-        .await?)
+        .await?
+        .with_key_passphrase(self.key_passphrase.clone()))
     }
 
     // TODO: see if this could also be shared with socks5 client / nym-sdk maybe
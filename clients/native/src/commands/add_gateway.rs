@@ -5,12 +5,16 @@ use crate::commands::CliNativeClient;
 use crate::error::ClientError;
 use nym_bin_common::output_format::OutputFormat;
 use nym_client_core::cli_helpers::client_add_gateway::{add_gateway, CommonClientAddGatewayArgs};
+use nym_client_core::cli_helpers::client_keys::KeyPassphraseArgs;
 
 #[derive(clap::Args)]
 pub(crate) struct Args {
     #[command(flatten)]
     common_args: CommonClientAddGatewayArgs,
 
+    #[command(flatten)]
+    key_passphrase: KeyPassphraseArgs,
+
     #[arg(short, long, default_value_t = OutputFormat::default())]
     output: OutputFormat,
 }
@@ -21,7 +25,9 @@ impl AsRef<CommonClientAddGatewayArgs> for Args {
     }
 }
 
-pub(crate) async fn execute(args: Args) -> Result<(), ClientError> {
+pub(crate) async fn execute(mut args: Args) -> Result<(), ClientError> {
+    args.common_args.key_passphrase = args.key_passphrase.resolve(false)?;
+
     let user_agent = nym_bin_common::bin_info!().into();
     let output = args.output;
     let res = add_gateway::<CliNativeClient, _>(args, Some(user_agent)).await?;
@@ -15,6 +15,7 @@ use nym_bin_common::output_format::OutputFormat;
 use nym_client_core::cli_helpers::client_init::{
     initialise_client, CommonClientInitArgs, InitResultsWithConfig, InitialisableClient,
 };
+use nym_client_core::cli_helpers::client_keys::KeyPassphraseArgs;
 use serde::Serialize;
 use std::fmt::Display;
 use std::fs;
@@ -47,6 +48,9 @@ pub(crate) struct Init {
     #[command(flatten)]
     common_args: CommonClientInitArgs,
 
+    #[command(flatten)]
+    key_passphrase: KeyPassphraseArgs,
+
     /// Whether to not start the websocket
     #[clap(long)]
     disable_socket: Option<bool>,
@@ -113,9 +117,11 @@ impl Display for InitResults {
     }
 }
 
-pub(crate) async fn execute(args: Init) -> Result<(), ClientError> {
+pub(crate) async fn execute(mut args: Init) -> Result<(), ClientError> {
     eprintln!("Initialising client...");
 
+    args.common_args.key_passphrase = args.key_passphrase.resolve(true)?;
+
     let user_agent = nym_bin_common::bin_info!().into();
     let output = args.output;
     let res = initialise_client::<CliNativeClient>(args, Some(user_agent)).await?;
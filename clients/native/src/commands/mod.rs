@@ -25,7 +25,9 @@ use std::sync::OnceLock;
 
 mod add_gateway;
 pub(crate) mod build_info;
+mod decrypt_keys;
 pub(crate) mod ecash;
+mod encrypt_keys;
 pub(crate) mod init;
 mod list_gateways;
 pub(crate) mod run;
@@ -87,6 +89,12 @@ pub(crate) enum Commands {
     /// Change the currently active gateway. Note that you must have already registered with the new gateway!
     SwitchGateway(switch_gateway::Args),
 
+    /// Encrypt the private keys of this client with a passphrase
+    EncryptKeys(encrypt_keys::Args),
+
+    /// Decrypt the private keys of this client, storing them in plaintext
+    DecryptKeys(decrypt_keys::Args),
+
     /// Show build information of this binary
     BuildInfo(build_info::BuildInfo),
 
@@ -121,6 +129,8 @@ pub(crate) async fn execute(args: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Commands::ListGateways(args) => list_gateways::execute(args).await?,
         Commands::AddGateway(args) => add_gateway::execute(args).await?,
         Commands::SwitchGateway(args) => switch_gateway::execute(args).await?,
+        Commands::EncryptKeys(args) => encrypt_keys::execute(args).await?,
+        Commands::DecryptKeys(args) => decrypt_keys::execute(args).await?,
         Commands::BuildInfo(m) => build_info::execute(m),
         Commands::Completions(s) => s.generate(&mut Cli::command(), bin_name),
         Commands::GenerateFigSpec => fig_generate(&mut Cli::command(), bin_name),
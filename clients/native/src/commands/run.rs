@@ -7,6 +7,7 @@ use crate::{
     commands::{override_config, OverrideConfig},
 };
 use clap::Args;
+use nym_client_core::cli_helpers::client_keys::KeyPassphraseArgs;
 use nym_client_core::cli_helpers::client_run::CommonClientRunArgs;
 use std::error::Error;
 use std::net::IpAddr;
@@ -16,6 +17,9 @@ pub(crate) struct Run {
     #[command(flatten)]
     common_args: CommonClientRunArgs,
 
+    #[command(flatten)]
+    key_passphrase: KeyPassphraseArgs,
+
     /// Whether to not start the websocket
     #[clap(long)]
     disable_socket: Option<bool>,
@@ -49,10 +53,12 @@ impl From<Run> for OverrideConfig {
 pub(crate) async fn execute(args: Run) -> Result<(), Box<dyn Error + Send + Sync>> {
     eprintln!("Starting client {}...", args.common_args.id);
 
+    let key_passphrase = args.key_passphrase.resolve(false)?;
     let mut config = try_load_current_config(&args.common_args.id).await?;
     config = override_config(config, OverrideConfig::from(args.clone()));
 
     SocketClient::new(config, args.common_args.custom_mixnet)
+        .with_key_passphrase(key_passphrase)
         .run_socket_forever()
         .await
 }
@@ -0,0 +1,27 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::commands::CliSocks5Client;
+use crate::error::Socks5ClientError;
+use nym_client_core::cli_helpers::client_keys::{decrypt_keys, CommonClientKeysEncryptionArgs};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    #[command(flatten)]
+    common_args: CommonClientKeysEncryptionArgs,
+}
+
+impl AsRef<CommonClientKeysEncryptionArgs> for Args {
+    fn as_ref(&self) -> &CommonClientKeysEncryptionArgs {
+        &self.common_args
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), Socks5ClientError> {
+    let decrypted = decrypt_keys::<CliSocks5Client, _>(args).await?;
+    for path in &decrypted {
+        println!("decrypted {}", path.display());
+    }
+    eprintln!("decrypted {} key file(s)", decrypted.len());
+    Ok(())
+}
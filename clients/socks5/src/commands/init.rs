@@ -14,6 +14,7 @@ use nym_bin_common::output_format::OutputFormat;
 use nym_client_core::cli_helpers::client_init::{
     initialise_client, CommonClientInitArgs, InitResultsWithConfig, InitialisableClient,
 };
+use nym_client_core::cli_helpers::client_keys::KeyPassphraseArgs;
 use nym_sphinx::addressing::clients::Recipient;
 use serde::Serialize;
 use std::fmt::Display;
@@ -47,6 +48,9 @@ pub(crate) struct Init {
     #[command(flatten)]
     common_args: CommonClientInitArgs,
 
+    #[command(flatten)]
+    key_passphrase: KeyPassphraseArgs,
+
     /// Address of the socks5 provider to send messages to.
     #[clap(long)]
     provider: Recipient,
@@ -127,9 +131,11 @@ impl Display for InitResults {
     }
 }
 
-pub(crate) async fn execute(args: Init) -> Result<(), Socks5ClientError> {
+pub(crate) async fn execute(mut args: Init) -> Result<(), Socks5ClientError> {
     eprintln!("Initialising client...");
 
+    args.common_args.key_passphrase = args.key_passphrase.resolve(true)?;
+
     let user_agent = nym_bin_common::bin_info!().into();
     let output = args.output;
     let res = initialise_client::<CliSocks5Client>(args, Some(user_agent)).await?;
@@ -14,6 +14,7 @@ use crate::{
 };
 use nym_client_core_gateways_storage::GatewayDetails;
 use nym_crypto::asymmetric::ed25519;
+use nym_pemstore::KeyPassphrase;
 use nym_topology::NymTopology;
 use nym_validator_client::UserAgent;
 use std::path::PathBuf;
@@ -59,6 +60,10 @@ pub struct CommonClientAddGatewayArgs {
     /// Path to .json file containing custom network specification.
     #[cfg_attr(feature = "cli", clap(long, group = "network", hide = true))]
     pub custom_mixnet: Option<PathBuf>,
+    /// Passphrase used for encrypting the private keys at rest.
+    /// It's resolved by the binary itself (see [`KeyPassphraseArgs`](crate::cli_helpers::client_keys::KeyPassphraseArgs))
+    #[cfg_attr(feature = "cli", clap(skip))]
+    pub key_passphrase: Option<KeyPassphrase>,
 }
 
 pub async fn add_gateway<C, A>(
@@ -76,7 +81,8 @@ where
     let core = config.core_config();
     let paths = config.common_paths();
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
+    let key_store =
+        OnDiskKeys::new(paths.keys.clone()).with_passphrase(common_args.key_passphrase.clone());
     let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
 
     // Attempt to use a user-provided gateway, if possible
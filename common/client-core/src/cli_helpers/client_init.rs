@@ -14,6 +14,7 @@ use crate::{
 };
 use nym_client_core_gateways_storage::GatewayDetails;
 use nym_crypto::asymmetric::ed25519;
+use nym_pemstore::KeyPassphrase;
 use nym_sphinx::addressing::Recipient;
 use nym_topology::NymTopology;
 use nym_validator_client::UserAgent;
@@ -101,6 +102,10 @@ pub struct CommonClientInitArgs {
     /// Sets the forget me flag
     #[cfg_attr(feature = "cli", clap(long, hide = true, default_value_t = false))]
     pub forget_me: bool,
+    /// Passphrase used for encrypting the private keys at rest.
+    /// It's resolved by the binary itself (see [`KeyPassphraseArgs`](crate::cli_helpers::client_keys::KeyPassphraseArgs))
+    #[cfg_attr(feature = "cli", clap(skip))]
+    pub key_passphrase: Option<KeyPassphrase>,
 }
 
 pub struct InitResultsWithConfig<T> {
@@ -161,7 +166,8 @@ where
             .join(",")
     );
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
+    let key_store =
+        OnDiskKeys::new(paths.keys.clone()).with_passphrase(common_args.key_passphrase.clone());
     let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
 
     let mut rng = OsRng;
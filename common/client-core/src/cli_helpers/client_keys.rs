@@ -0,0 +1,133 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_helpers::{CliClient, CliClientConfig};
+use crate::config::disk_persistence::ClientKeysPaths;
+use crate::error::ClientCoreError;
+use nym_pemstore::KeyPassphrase;
+use std::collections::BTreeSet;
+use std::io;
+use std::path::PathBuf;
+use tracing::info;
+
+#[cfg_attr(feature = "cli", derive(clap::Args))]
+#[derive(Debug, Clone, Default)]
+pub struct KeyPassphraseArgs {
+    /// Passphrase used for encrypting the private keys at rest.
+    /// Prefer providing it via the environment or a file rather than on the command line.
+    #[cfg_attr(
+        feature = "cli",
+        clap(long, env = "NYM_KEY_PASSPHRASE", hide = true, hide_env_values = true)
+    )]
+    pub key_passphrase: Option<String>,
+
+    /// Path to a file containing the passphrase used for encrypting the private keys at rest.
+    #[cfg_attr(feature = "cli", clap(long, env = "NYM_KEY_PASSPHRASE_FILE"))]
+    pub key_passphrase_file: Option<PathBuf>,
+
+    /// Interactively prompt for the passphrase used for encrypting the private keys at rest.
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub prompt_key_passphrase: bool,
+}
+
+impl KeyPassphraseArgs {
+    /// Resolve the passphrase from the prompt, the file or the raw value (in that order).
+    /// `new_passphrase` makes the prompt ask for a confirmation.
+    pub fn resolve(&self, new_passphrase: bool) -> io::Result<Option<KeyPassphrase>> {
+        if self.prompt_key_passphrase {
+            return KeyPassphrase::prompt("key passphrase:", new_passphrase).map(Some);
+        }
+
+        if let Some(file) = &self.key_passphrase_file {
+            return KeyPassphrase::from_file(file).map(Some);
+        }
+        match &self.key_passphrase {
+            Some(passphrase) if passphrase.is_empty() => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the key passphrase must not be empty",
+            )),
+            Some(passphrase) => Ok(Some(KeyPassphrase::new(passphrase.as_bytes()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Like [`Self::resolve`], but fail if no passphrase got provided.
+    pub fn resolve_required(&self, new_passphrase: bool) -> io::Result<KeyPassphrase> {
+        self.resolve(new_passphrase)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no key passphrase provided. use `--key-passphrase-file`, `--prompt-key-passphrase` or set `NYM_KEY_PASSPHRASE`",
+            )
+        })
+    }
+}
+
+#[cfg_attr(feature = "cli", derive(clap::Args))]
+#[derive(Debug, Clone)]
+pub struct CommonClientKeysEncryptionArgs {
+    /// Id of client whose keys we want to encrypt (or decrypt).
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub id: String,
+
+    #[cfg_attr(feature = "cli", clap(flatten))]
+    pub key_passphrase: KeyPassphraseArgs,
+}
+
+fn key_directories(paths: &ClientKeysPaths) -> BTreeSet<PathBuf> {
+    [
+        paths.private_identity_key(),
+        paths.private_encryption_key(),
+        paths.ack_key(),
+    ]
+    .into_iter()
+    .filter_map(|path| path.parent().map(|dir| dir.to_path_buf()))
+    .collect()
+}
+
+/// Encrypt the private keys of an existing client.
+pub async fn encrypt_keys<C, A>(args: A) -> Result<Vec<PathBuf>, C::Error>
+where
+    A: AsRef<CommonClientKeysEncryptionArgs>,
+    C: CliClient,
+{
+    let common_args = args.as_ref();
+    let config = C::try_load_current_config(&common_args.id).await?;
+    let passphrase = common_args
+        .key_passphrase
+        .resolve_required(true)
+        .map_err(ClientCoreError::from)?;
+
+    let mut encrypted = Vec::new();
+    for dir in key_directories(&config.common_paths().keys) {
+        info!("encrypting keys in {}", dir.display());
+        encrypted.extend(
+            nym_pemstore::encrypt_key_directory(&dir, &passphrase)
+                .map_err(ClientCoreError::from)?,
+        );
+    }
+    Ok(encrypted)
+}
+
+/// Decrypt the private keys of an existing client.
+pub async fn decrypt_keys<C, A>(args: A) -> Result<Vec<PathBuf>, C::Error>
+where
+    A: AsRef<CommonClientKeysEncryptionArgs>,
+    C: CliClient,
+{
+    let common_args = args.as_ref();
+    let config = C::try_load_current_config(&common_args.id).await?;
+    let passphrase = common_args
+        .key_passphrase
+        .resolve_required(false)
+        .map_err(ClientCoreError::from)?;
+
+    let mut decrypted = Vec::new();
+    for dir in key_directories(&config.common_paths().keys) {
+        info!("decrypting keys in {}", dir.display());
+        decrypted.extend(
+            nym_pemstore::decrypt_key_directory(&dir, &passphrase)
+                .map_err(ClientCoreError::from)?,
+        );
+    }
+    Ok(decrypted)
+}
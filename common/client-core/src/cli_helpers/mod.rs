@@ -7,6 +7,7 @@ pub mod client_import_credential;
 pub mod client_import_expiration_date_signatures;
 pub mod client_import_master_verification_key;
 pub mod client_init;
+pub mod client_keys;
 pub mod client_list_gateways;
 pub mod client_run;
 pub mod client_show_ticketbooks;
@@ -27,6 +27,12 @@ use crate::{
 };
 #[cfg(all(not(target_arch = "wasm32"), feature = "fs-credentials-storage"))]
 pub use nym_credential_storage::persistent_storage::PersistentStorage as PersistentCredentialStorage;
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "fs-surb-storage",
+    feature = "fs-gateways-storage"
+))]
+use nym_pemstore::KeyPassphrase;
 
 pub use nym_client_core_gateways_storage as gateways_storage;
 pub use nym_client_core_gateways_storage::{GatewaysDetailsStore, InMemGatewaysDetails};
@@ -154,6 +160,13 @@ impl OnDiskPersistent {
         }
     }
 
+    /// Encrypt the private keys at rest with the provided passphrase.
+    #[must_use]
+    pub fn with_key_passphrase(mut self, passphrase: Option<KeyPassphrase>) -> Self {
+        self.key_store = self.key_store.with_passphrase(passphrase);
+        self
+    }
+
     pub async fn from_paths(
         paths: CommonClientPaths,
         debug_config: &config::DebugConfig,
@@ -13,10 +13,10 @@ use crate::config::disk_persistence::ClientKeysPaths;
 #[cfg(not(target_arch = "wasm32"))]
 use nym_crypto::asymmetric::{ed25519, x25519};
 #[cfg(not(target_arch = "wasm32"))]
-use nym_pemstore::KeyPairPath;
-#[cfg(not(target_arch = "wasm32"))]
 use nym_pemstore::traits::{PemStorableKey, PemStorableKeyPair};
 #[cfg(not(target_arch = "wasm32"))]
+use nym_pemstore::{KeyPairPath, KeyPassphrase};
+#[cfg(not(target_arch = "wasm32"))]
 use nym_sphinx::acknowledgements::AckKey;
 
 // we have to define it as an async trait since wasm storage is async
@@ -70,19 +70,31 @@ pub enum OnDiskKeysError {
 #[cfg(not(target_arch = "wasm32"))]
 pub struct OnDiskKeys {
     paths: ClientKeysPaths,
+
+    /// If specified, private keys are encrypted at rest with the provided passphrase.
+    passphrase: Option<KeyPassphrase>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl From<ClientKeysPaths> for OnDiskKeys {
     fn from(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys::new(paths)
     }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl OnDiskKeys {
     pub fn new(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys {
+            paths,
+            passphrase: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_passphrase(mut self, passphrase: Option<KeyPassphrase>) -> Self {
+        self.passphrase = passphrase;
+        self
     }
 
     #[doc(hidden)]
@@ -102,10 +114,12 @@ impl OnDiskKeys {
         path: &std::path::Path,
         name: impl Into<String>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_key(path).map_err(|err| OnDiskKeysError::KeyLoadFailure {
-            key: name.into(),
-            path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
-            err,
+        nym_pemstore::load_key_with_passphrase(path, self.passphrase.as_ref()).map_err(|err| {
+            OnDiskKeysError::KeyLoadFailure {
+                key: name.into(),
+                path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+                err,
+            }
         })
     }
 
@@ -114,11 +128,13 @@ impl OnDiskKeys {
         paths: KeyPairPath,
         name: impl Into<String>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_keypair(&paths).map_err(|err| OnDiskKeysError::KeyPairLoadFailure {
-            keys: name.into(),
-            paths,
-            err,
-        })
+        nym_pemstore::load_keypair_with_passphrase(&paths, self.passphrase.as_ref()).map_err(
+            |err| OnDiskKeysError::KeyPairLoadFailure {
+                keys: name.into(),
+                paths,
+                err,
+            },
+        )
     }
 
     fn store_key<T: PemStorableKey>(
@@ -127,11 +143,13 @@ impl OnDiskKeys {
         path: &std::path::Path,
         name: impl Into<String>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_key(key, path).map_err(|err| OnDiskKeysError::KeyStoreFailure {
-            key: name.into(),
-            path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
-            err,
-        })
+        nym_pemstore::store_key_with_passphrase(key, path, self.passphrase.as_ref()).map_err(
+            |err| OnDiskKeysError::KeyStoreFailure {
+                key: name.into(),
+                path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+                err,
+            },
+        )
     }
 
     fn store_keypair<T: PemStorableKeyPair>(
@@ -140,13 +158,13 @@ impl OnDiskKeys {
         paths: KeyPairPath,
         name: impl Into<String>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_keypair(keys, &paths).map_err(|err| {
-            OnDiskKeysError::KeyPairStoreFailure {
+        nym_pemstore::store_keypair_with_passphrase(keys, &paths, self.passphrase.as_ref()).map_err(
+            |err| OnDiskKeysError::KeyPairStoreFailure {
                 keys: name.into(),
                 paths,
                 err,
-            }
-        })
+            },
+        )
     }
 
     fn load_keys(&self) -> Result<ClientKeys, OnDiskKeysError> {
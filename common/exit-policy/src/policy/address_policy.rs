@@ -173,6 +173,11 @@ impl AddressPolicy {
     pub fn push_rule(&mut self, rule: AddressPolicyRule) {
         self.rules.push(rule)
     }
+
+    /// Return the rules of this policy, in the order they're applied.
+    pub fn rules(&self) -> &[AddressPolicyRule] {
+        &self.rules
+    }
 }
 
 /// A single rule in an address policy.
@@ -208,6 +213,16 @@ impl AddressPolicyRule {
     pub fn new(action: AddressPolicyAction, pattern: AddressPortPattern) -> Self {
         AddressPolicyRule { action, pattern }
     }
+
+    /// What happens to items matching this rule's pattern.
+    pub fn action(&self) -> AddressPolicyAction {
+        self.action
+    }
+
+    /// The pattern this rule is trying to match.
+    pub fn pattern(&self) -> &AddressPortPattern {
+        &self.pattern
+    }
 }
 impl Display for AddressPolicyRule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -276,6 +291,11 @@ impl AddressPortPattern {
     pub fn matches_sockaddr(&self, addr: &SocketAddr) -> bool {
         self.matches(&addr.ip(), addr.port())
     }
+
+    /// The range of ports covered by this pattern.
+    pub fn ports(&self) -> &PortRange {
+        &self.ports
+    }
 }
 
 impl Display for AddressPortPattern {
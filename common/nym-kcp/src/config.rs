@@ -0,0 +1,73 @@
+/// Tuning profile of a [`KcpSession`](crate::session::KcpSession).
+///
+/// It mirrors the `ikcp_nodelay`/`ikcp_wndsize`/`ikcp_setmtu` knobs of the reference implementation.
+/// The [`Default`] profile is conservative and reacts to losses only through RTO expiry,
+/// while [`KcpConfig::fast`] is meant for lossy links where latency matters more than bandwidth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KcpConfig {
+    /// Grow the RTO of a segment by 1.5x rather than doubling it on every timeout.
+    pub nodelay: bool,
+
+    /// Interval between flushes of the session, in milliseconds (clamped to 10..=5000).
+    pub interval: u32,
+
+    /// Number of ACKs for later segments after which a segment is retransmitted
+    /// without waiting for its RTO to expire. `0` disables fast retransmit.
+    pub fast_resend: u32,
+
+    /// Maximum number of transmissions of a segment for which fast retransmit still applies.
+    /// `0` means unlimited.
+    pub fast_limit: u32,
+
+    /// Limit the number of in-flight segments with a congestion window (slow start and
+    /// congestion avoidance) on top of the local and remote windows.
+    pub congestion_control: bool,
+
+    /// Local send window, in segments.
+    pub snd_wnd: u16,
+
+    /// Local receive window, in segments.
+    pub rcv_wnd: u16,
+
+    /// Maximum payload size of a single segment.
+    pub mtu: usize,
+
+    /// Lower bound of the retransmission timeout, in milliseconds.
+    pub min_rto: u32,
+
+    /// Retransmission timeout used until the first RTT sample, in milliseconds.
+    pub initial_rto: u32,
+}
+
+impl Default for KcpConfig {
+    fn default() -> Self {
+        KcpConfig {
+            nodelay: false,
+            interval: 100,
+            fast_resend: 0,
+            fast_limit: 5,
+            congestion_control: false,
+            snd_wnd: 32,
+            rcv_wnd: 128,
+            mtu: 1376,
+            min_rto: 3000,
+            initial_rto: 3000,
+        }
+    }
+}
+
+impl KcpConfig {
+    /// Profile equivalent to `ikcp_nodelay(kcp, 1, 10, 2, 0)`: immediate flushes, fast retransmit
+    /// after two duplicate ACKs and short timeouts, with congestion control kept enabled.
+    pub fn fast() -> Self {
+        KcpConfig {
+            nodelay: true,
+            interval: 10,
+            fast_resend: 2,
+            congestion_control: true,
+            min_rto: 30,
+            initial_rto: 200,
+            ..Default::default()
+        }
+    }
+}
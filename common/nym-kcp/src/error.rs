@@ -8,6 +8,12 @@ pub enum KcpError {
     #[error("Conversation ID mismatch: expected {expected}, received {received}")]
     ConvMismatch { expected: u32, received: u32 },
 
+    #[error("Conversation {0} is already in use")]
+    ConvInUse(u32),
+
+    #[error("The underlying transport has been closed")]
+    TransportClosed,
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
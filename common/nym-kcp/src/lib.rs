@@ -1,7 +1,9 @@
 pub mod codec;
+pub mod config;
 pub mod driver;
 pub mod error;
 pub mod packet;
 pub mod session;
+pub mod stream;
 
 pub const MAX_RTO: u32 = 60000; // Same as used in update_rtt
@@ -11,6 +11,7 @@ use std::thread;
 
 use crate::MAX_RTO;
 
+use super::config::KcpConfig;
 use super::error::KcpError;
 use super::packet::{KcpCommand, KcpPacket};
 
@@ -43,6 +44,17 @@ pub struct KcpSession {
     interval: u32, // flush interval
     ts_flush: u32, // next flush timestamp
 
+    // Retransmission tuning
+    nodelay: bool,
+    fast_resend: u32, // duplicate acks triggering a fast retransmit, 0 to disable
+    fast_limit: u32, // max transmissions of a segment eligible for fast retransmit, 0 for unlimited
+
+    // Congestion control
+    congestion_control: bool,
+    cwnd: u32,     // congestion window (segments)
+    ssthresh: u32, // slow start threshold (segments)
+    incr: u32,     // bytes acknowledged during congestion avoidance
+
     // If you want to store outgoing packets from flush, do it here
     out_pkts: Vec<KcpPacket>,
     mtu: usize,
@@ -57,12 +69,12 @@ struct Segment {
     ts: u32,
     resendts: u32,
     rto: u32,
-    xmit: u32, // how many times sent
+    xmit: u32,    // how many times sent
+    fastack: u32, // how many times later segments got acked while this one wasn't
     data: Vec<u8>,
 }
 
 impl Segment {
-    #[allow(dead_code)]
     fn new(sn: u32, frg: u16, data: Vec<u8>) -> Self {
         Segment {
             sn,
@@ -71,40 +83,19 @@ impl Segment {
             resendts: 0,
             rto: 0,
             xmit: 0,
+            fastack: 0,
             data,
         }
     }
 }
 
+// Slow start threshold used before the first loss and its lower bound
+const THRESH_INIT: u32 = 2;
+const THRESH_MIN: u32 = 2;
+
 impl Default for KcpSession {
     fn default() -> Self {
-        KcpSession {
-            conv: 0,
-            snd_nxt: 0,
-            snd_una: 0,
-            snd_wnd: 32,
-            rmt_wnd: 128,
-            snd_queue: VecDeque::new(),
-            snd_buf: VecDeque::new(),
-
-            rcv_nxt: 0,
-            rcv_wnd: 128,
-            rcv_buf: VecDeque::new(),
-            rcv_queue: VecDeque::new(),
-
-            rx_srtt: 0,
-            rx_rttval: 0,
-            rx_rto: 3000,
-            rx_minrto: 3000,
-
-            current: 0,
-            interval: 100,
-            ts_flush: 100,
-
-            out_pkts: Vec::new(),
-            mtu: 1376,
-            partial_read: None,
-        }
+        KcpSession::with_config(0, KcpConfig::default())
     }
 }
 
@@ -117,7 +108,7 @@ impl KcpSession {
         self.ts_flush
     }
 
-    fn available_send_segments(&self) -> usize {
+    pub(crate) fn available_send_segments(&self) -> usize {
         // A naive approach: if `snd_queue` has length L
         // and local window is `snd_wnd`, we can add `snd_wnd - L` more segments
         let used = self.snd_queue.len();
@@ -133,6 +124,62 @@ impl KcpSession {
         }
     }
 
+    /// Create a new KCP session with a specified conv ID, tuned according to `config`.
+    pub fn with_config(conv: u32, config: KcpConfig) -> Self {
+        let interval = config.interval.clamp(10, 5000);
+        KcpSession {
+            conv,
+            snd_nxt: 0,
+            snd_una: 0,
+            snd_wnd: config.snd_wnd,
+            rmt_wnd: 128,
+            snd_queue: VecDeque::new(),
+            snd_buf: VecDeque::new(),
+
+            rcv_nxt: 0,
+            rcv_wnd: config.rcv_wnd,
+            rcv_buf: VecDeque::new(),
+            rcv_queue: VecDeque::new(),
+
+            rx_srtt: 0,
+            rx_rttval: 0,
+            rx_rto: config.initial_rto.clamp(config.min_rto, MAX_RTO),
+            rx_minrto: config.min_rto,
+
+            current: 0,
+            interval,
+            ts_flush: interval,
+
+            nodelay: config.nodelay,
+            fast_resend: config.fast_resend,
+            fast_limit: config.fast_limit,
+
+            congestion_control: config.congestion_control,
+            cwnd: 1,
+            ssthresh: THRESH_INIT,
+            incr: 0,
+
+            out_pkts: Vec::new(),
+            mtu: config.mtu,
+            partial_read: None,
+        }
+    }
+
+    /// Number of segments that haven't been acknowledged yet, including the ones still queued.
+    pub fn wait_snd(&self) -> usize {
+        self.snd_buf.len() + self.snd_queue.len()
+    }
+
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Current congestion window, in segments. It only limits the sender
+    /// if congestion control is enabled.
+    pub fn cwnd(&self) -> u32 {
+        self.cwnd
+    }
+
     /// If you want to let the user set the mtu:
     pub fn set_mtu(&mut self, mtu: usize) {
         self.mtu = mtu;
@@ -178,15 +225,7 @@ impl KcpSession {
             // i.e., the last fragment has frg=0, the first has frg=count-1.
             let frg = (count - i - 1) as u16;
 
-            let seg = Segment {
-                sn: self.snd_nxt,
-                frg,
-                ts: 0,
-                resendts: 0,
-                rto: 0,
-                xmit: 0,
-                data: chunk.to_vec(),
-            };
+            let seg = Segment::new(self.snd_nxt, frg, chunk.to_vec());
 
             debug!("Sending segment, sn: {}, frg: {}", seg.sn, seg.frg);
 
@@ -232,6 +271,8 @@ impl KcpSession {
             });
         }
 
+        let prev_una = self.snd_una;
+
         // Update remote window
         self.rmt_wnd = pkt.wnd();
 
@@ -251,6 +292,7 @@ impl KcpSession {
         match pkt.cmd() {
             KcpCommand::Ack => {
                 self.parse_ack(pkt.sn(), pkt.ts());
+                self.parse_fastack(pkt.sn(), pkt.ts());
             }
             KcpCommand::Push => {
                 debug!("Received push, sn: {}, frg: {}", pkt.sn(), pkt.frg());
@@ -271,6 +313,11 @@ impl KcpSession {
             }
         }
 
+        self.shrink_buf();
+        if self.snd_una.wrapping_sub(prev_una) as i32 > 0 {
+            self.on_acked();
+        }
+
         Ok(())
     }
 
@@ -384,21 +431,26 @@ impl KcpSession {
 
     /// Move segments from `snd_queue` into `snd_buf` respecting window
     fn move_queue_to_buf(&mut self) {
-        // Calculate the congestion window (cwnd)
-        let cwnd = std::cmp::min(self.snd_wnd, self.rmt_wnd);
-
-        // In real KCP, we check against the number of unacknowledged segments:
-        // while self.snd_nxt < self.snd_una + cwnd { ... }
-        // Here, we approximate by checking the current length of snd_buf against cwnd.
-        while let Some(mut seg) = self.snd_queue.pop_front() {
-            // Check if adding this segment would exceed the congestion window
-            if (self.snd_buf.len() as u16) >= cwnd {
+        // The effective window is the smaller of the local and remote windows,
+        // further limited by the congestion window if congestion control is enabled
+        let mut cwnd = cmp::min(self.snd_wnd, self.rmt_wnd) as u32;
+        if self.congestion_control {
+            cwnd = cmp::min(cwnd, self.cwnd);
+        }
+
+        // Only segments within `cwnd` of the first unacknowledged one can be in flight,
+        // so that we never send beyond what the receiver is able to buffer
+        while let Some(seg) = self.snd_queue.front() {
+            if seg.sn.wrapping_sub(self.snd_una) >= cwnd {
                 // Effective window is full
-                self.snd_queue.push_front(seg); // Put it back
                 break;
             }
+            let Some(mut seg) = self.snd_queue.pop_front() else {
+                break;
+            };
             // init rto
             seg.xmit = 0;
+            seg.fastack = 0;
             seg.rto = self.rx_rto;
             seg.resendts = 0; // will set later
             seg.ts = self.current;
@@ -418,6 +470,14 @@ impl KcpSession {
         //     current_sns
         // );
 
+        let fast_resend = if self.fast_resend > 0 {
+            self.fast_resend
+        } else {
+            u32::MAX
+        };
+        let mut fast_retransmitted = false;
+        let mut timed_out = false;
+
         for seg in &mut self.snd_buf {
             let mut need_send = false;
             if seg.xmit == 0 {
@@ -428,9 +488,15 @@ impl KcpSession {
             } else if self.current >= seg.resendts {
                 // time to retransmit
                 need_send = true;
+                timed_out = true;
                 seg.xmit += 1;
-                // Exponential backoff: double RTO for this segment
-                seg.rto *= 2;
+                if self.nodelay {
+                    // Gentler backoff: grow the RTO of this segment by half
+                    seg.rto += seg.rto / 2;
+                } else {
+                    // Exponential backoff: double RTO for this segment
+                    seg.rto *= 2;
+                }
                 // Clamp to the session's maximum RTO (hardcoded as 60s for now)
                 if seg.rto > MAX_RTO {
                     seg.rto = MAX_RTO;
@@ -443,16 +509,34 @@ impl KcpSession {
                         self.conv, seg.sn, seg.frg
                     ))
                 );
+            } else if seg.fastack >= fast_resend
+                && (self.fast_limit == 0 || seg.xmit <= self.fast_limit)
+            {
+                // later segments got acked enough times: assume this one got lost
+                need_send = true;
+                fast_retransmitted = true;
+                seg.xmit += 1;
+                seg.fastack = 0;
+                seg.resendts = self.current + seg.rto;
+                debug!(
+                    "{}",
+                    Yellow.paint(format!(
+                        "Fast retransmit conv_id: {}, sn: {}, frg: {}",
+                        self.conv, seg.sn, seg.frg
+                    ))
+                );
             }
 
             if need_send {
+                // the timestamp of the latest transmission is echoed back in the ack
+                seg.ts = self.current;
                 // Make a KcpPacket
                 let pkt = KcpPacket::new(
                     self.conv,
                     KcpCommand::Push,
                     seg.frg,
                     self.rcv_wnd,
-                    seg.ts, // original send timestamp
+                    seg.ts,
                     seg.sn,
                     self.rcv_nxt, // self.rcv_nxt for ack
                     seg.data.clone(),
@@ -462,6 +546,10 @@ impl KcpSession {
                 // if too many xmit => dead_link check, etc.
             }
         }
+
+        if self.congestion_control {
+            self.on_loss(fast_retransmitted, timed_out, fast_resend);
+        }
         // Possibly build ack packets
         // In real KCP, you'd track pending ack and flush them too.
         // For minimal example, we skip that or do it inline in parse_data.
@@ -563,6 +651,78 @@ impl KcpSession {
         }
     }
 
+    /// Count the ack of `sn` against every earlier segment still waiting for its own ack.
+    fn parse_fastack(&mut self, sn: u32, ts: u32) {
+        if sn.wrapping_sub(self.snd_una) as i32 <= 0 {
+            return;
+        }
+        for seg in &mut self.snd_buf {
+            if seg.sn.wrapping_sub(sn) as i32 >= 0 {
+                break;
+            }
+            // only count acks for data sent after the latest transmission of this segment
+            if ts.wrapping_sub(seg.ts) as i32 >= 0 {
+                seg.fastack += 1;
+            }
+        }
+    }
+
+    /// Recompute `snd_una` as the first segment that hasn't been acknowledged yet.
+    fn shrink_buf(&mut self) {
+        self.snd_una = self
+            .snd_buf
+            .front()
+            .or(self.snd_queue.front())
+            .map(|seg| seg.sn)
+            .unwrap_or(self.snd_nxt);
+    }
+
+    /// Grow the congestion window after new data got acknowledged:
+    /// exponentially during slow start and linearly once past `ssthresh`.
+    fn on_acked(&mut self) {
+        if !self.congestion_control || self.cwnd >= self.rmt_wnd as u32 {
+            return;
+        }
+        let mss = cmp::max(self.mtu as u32, 1);
+        if self.cwnd < self.ssthresh {
+            self.cwnd += 1;
+            self.incr += mss;
+        } else {
+            if self.incr < mss {
+                self.incr = mss;
+            }
+            self.incr += (mss * mss) / self.incr + mss / 16;
+            if (self.cwnd + 1) * mss <= self.incr {
+                self.cwnd = self.incr.div_ceil(mss);
+            }
+        }
+        if self.cwnd > self.rmt_wnd as u32 {
+            self.cwnd = self.rmt_wnd as u32;
+            self.incr = self.rmt_wnd as u32 * mss;
+        }
+    }
+
+    /// Shrink the congestion window after a loss: halve it on fast retransmit
+    /// and restart slow start from a single segment on timeout.
+    fn on_loss(&mut self, fast_retransmitted: bool, timed_out: bool, fast_resend: u32) {
+        let mss = self.mtu as u32;
+        if fast_retransmitted {
+            let inflight = self.snd_buf.len() as u32;
+            self.ssthresh = cmp::max(inflight / 2, THRESH_MIN);
+            self.cwnd = self.ssthresh.saturating_add(fast_resend);
+            self.incr = self.cwnd.saturating_mul(mss);
+        }
+        if timed_out {
+            self.ssthresh = cmp::max(self.cwnd / 2, THRESH_MIN);
+            self.cwnd = 1;
+            self.incr = mss;
+        }
+        if self.cwnd < 1 {
+            self.cwnd = 1;
+            self.incr = mss;
+        }
+    }
+
     fn parse_data(&mut self, pkt: &KcpPacket) {
         // Insert into rcv_buf if pkt.sn in [rcv_nxt .. rcv_nxt + rcv_wnd)
         if pkt.sn() >= self.rcv_nxt + self.rcv_wnd as u32 {
@@ -587,15 +747,8 @@ impl KcpSession {
             }
         }
 
-        let seg = Segment {
-            sn: pkt.sn(),
-            frg: pkt.frg(),
-            ts: pkt.ts(),
-            resendts: 0,
-            rto: 0,
-            xmit: 0,
-            data: pkt.data().into(),
-        };
+        let mut seg = Segment::new(pkt.sn(), pkt.frg(), pkt.data().into());
+        seg.ts = pkt.ts();
         self.rcv_buf.insert(insert_idx, seg);
 
         // Move ready segments from rcv_buf -> rcv_queue
@@ -1785,4 +1938,115 @@ mod tests {
         let bytes_read_again = receiver.recv(&mut read_buffer);
         assert_eq!(bytes_read_again, 0, "Subsequent recv should return 0 bytes");
     }
+
+    fn ack_for(pkt: &KcpPacket, una: u32) -> KcpPacket {
+        KcpPacket::new(
+            pkt.conv(),
+            KcpCommand::Ack,
+            0,
+            128,
+            pkt.ts(),
+            pkt.sn(),
+            una,
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_congestion_window_grows_in_slow_start() {
+        init_logger();
+        let conv = 707;
+        let mut session = KcpSession::with_config(conv, KcpConfig::fast());
+        session.set_mtu(20);
+        session.send(&[7u8; 200]); // 10 segments
+
+        // Only a single segment can be in flight initially
+        session.update(session.interval);
+        let packets = session.fetch_outgoing();
+        assert_eq!(packets.len(), 1, "Initial congestion window is one segment");
+        assert_eq!(session.cwnd(), 1);
+
+        // Every acknowledged segment opens the window by one during slow start
+        session.input(&ack_for(&packets[0], 1)).unwrap();
+        assert_eq!(session.cwnd(), 2);
+        session.update(session.interval);
+        let packets = session.fetch_outgoing();
+        assert_eq!(packets.len(), 2, "Window should allow two segments now");
+
+        // Past ssthresh the window only grows by about one segment per window of acks
+        session.input(&ack_for(&packets[0], 2)).unwrap();
+        session.input(&ack_for(&packets[1], 3)).unwrap();
+        assert_eq!(session.cwnd(), 2);
+        for sn in 3..6 {
+            session.update(session.interval);
+            for packet in session.fetch_outgoing() {
+                session.input(&ack_for(&packet, sn)).unwrap();
+            }
+        }
+        assert!(session.cwnd() > 2, "Window should keep growing linearly");
+    }
+
+    #[test]
+    fn test_fast_retransmit_halves_congestion_window() {
+        init_logger();
+        let conv = 708;
+        let mut session = KcpSession::with_config(conv, KcpConfig::fast());
+        session.set_mtu(20);
+        session.cwnd = 8;
+        session.ssthresh = 16;
+        session.send(&[8u8; 160]); // 8 segments
+
+        session.update(session.interval);
+        let packets = session.fetch_outgoing();
+        assert_eq!(packets.len(), 8);
+        let resendts = session.snd_buf.front().unwrap().resendts;
+
+        // The first segment got lost, the following ones are acknowledged
+        for packet in &packets[1..] {
+            session.input(&ack_for(packet, 0)).unwrap();
+        }
+        assert_eq!(session.snd_buf.len(), 1);
+
+        session.update(session.interval);
+        assert!(session.current < resendts, "RTO must not have expired yet");
+        let retransmitted = session.fetch_outgoing();
+        assert_eq!(
+            retransmitted.len(),
+            1,
+            "Lost segment should be fast retransmitted"
+        );
+        assert_eq!(retransmitted[0].sn(), packets[0].sn());
+        assert_eq!(session.snd_buf.front().unwrap().xmit, 2);
+
+        assert_eq!(session.ssthresh, THRESH_MIN);
+        assert!(
+            session.cwnd() < 8,
+            "Congestion window should shrink after fast retransmit"
+        );
+    }
+
+    #[test]
+    fn test_timeout_collapses_congestion_window() {
+        init_logger();
+        let conv = 709;
+        let mut session = KcpSession::with_config(conv, KcpConfig::fast());
+        session.set_mtu(20);
+        session.cwnd = 8;
+        session.ssthresh = 16;
+        session.send(&[9u8; 40]); // 2 segments
+
+        session.update(session.interval);
+        assert_eq!(session.fetch_outgoing().len(), 2);
+        let segment = session.snd_buf.front().unwrap().clone();
+
+        // Nothing gets acknowledged until the RTO expires
+        session.update(segment.rto + session.interval);
+        assert_eq!(session.fetch_outgoing().len(), 2, "Both segments time out");
+        assert_eq!(session.cwnd(), 1, "Timeout restarts slow start");
+        assert_eq!(session.ssthresh, 4);
+
+        // nodelay grows the RTO by half instead of doubling it
+        let retransmitted = session.snd_buf.front().unwrap();
+        assert_eq!(retransmitted.rto, segment.rto + segment.rto / 2);
+    }
 }
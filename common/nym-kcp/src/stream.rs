@@ -0,0 +1,548 @@
+//! Tokio [`AsyncRead`]/[`AsyncWrite`] adapter driving [`KcpSession`]s over a datagram transport.
+//!
+//! A [`KcpMux`] owns the transport and runs a background task which feeds received datagrams
+//! into the right session (based on the conversation id), ticks every session on the configured
+//! interval and sends whatever they produce. Each conversation is exposed as a [`KcpStream`].
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, Weak},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use bytes::{Bytes, BytesMut};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use log::{debug, warn};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::UdpSocket,
+    sync::mpsc,
+    time::{self, Instant, MissedTickBehavior},
+};
+
+use crate::{
+    config::KcpConfig,
+    error::KcpError,
+    packet::{KCP_HEADER, KcpCommand, KcpPacket},
+    session::KcpSession,
+};
+
+/// Largest datagram we expect to receive.
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// Largest amount of data accepted by a single write.
+const MAX_WRITE_SIZE: usize = 64 * 1024;
+
+// Every KCP message sent by a stream starts with one of those tags,
+// so that the peer can tell apart data from the end of the stream
+const TAG_DATA: u8 = 0;
+const TAG_FIN: u8 = 1;
+
+/// Unreliable, message oriented transport carrying KCP packets, such as a connected UDP socket.
+pub trait DatagramTransport: Send + 'static {
+    /// Send a single datagram.
+    fn send(&mut self, datagram: Bytes) -> impl Future<Output = io::Result<()>> + Send;
+
+    /// Receive a single datagram, appending it to `buf`.
+    /// Returns `Ok(false)` once the transport got closed.
+    ///
+    /// The returned future must be cancellation safe.
+    fn recv(&mut self, buf: &mut BytesMut) -> impl Future<Output = io::Result<bool>> + Send;
+}
+
+/// The socket must be [connected](UdpSocket::connect) to the remote peer.
+impl DatagramTransport for UdpSocket {
+    async fn send(&mut self, datagram: Bytes) -> io::Result<()> {
+        UdpSocket::send(self, &datagram).await.map(|_| ())
+    }
+
+    async fn recv(&mut self, buf: &mut BytesMut) -> io::Result<bool> {
+        buf.reserve(MAX_DATAGRAM_SIZE);
+        self.recv_buf(buf).await?;
+        Ok(true)
+    }
+}
+
+/// [`DatagramTransport`] made of a [`Sink`] and a [`Stream`] of datagrams,
+/// e.g. both halves of a channel or of a split framed connection.
+pub struct PacketTransport<Si, St> {
+    sink: Si,
+    stream: St,
+}
+
+impl<Si, St> PacketTransport<Si, St> {
+    pub fn new(sink: Si, stream: St) -> Self {
+        PacketTransport { sink, stream }
+    }
+
+    pub fn into_inner(self) -> (Si, St) {
+        (self.sink, self.stream)
+    }
+}
+
+impl<Si, St> DatagramTransport for PacketTransport<Si, St>
+where
+    Si: Sink<Bytes> + Unpin + Send + 'static,
+    Si::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    St: Stream<Item = Bytes> + Unpin + Send + 'static,
+{
+    async fn send(&mut self, datagram: Bytes) -> io::Result<()> {
+        self.sink.send(datagram).await.map_err(io::Error::other)
+    }
+
+    async fn recv(&mut self, buf: &mut BytesMut) -> io::Result<bool> {
+        match self.stream.next().await {
+            Some(datagram) => {
+                buf.extend_from_slice(&datagram);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// State of a single conversation, shared between its [`KcpStream`] and the driver task.
+struct Conversation {
+    session: KcpSession,
+
+    // data received but not read yet
+    read_buf: BytesMut,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+
+    // the peer has shut down its side of the stream
+    read_closed: bool,
+    // we've shut down our side of the stream
+    write_closed: bool,
+    // the driver stopped, e.g. because of a transport failure
+    error: Option<io::ErrorKind>,
+}
+
+impl Conversation {
+    fn new(session: KcpSession) -> Self {
+        Conversation {
+            session,
+            read_buf: BytesMut::new(),
+            read_waker: None,
+            write_waker: None,
+            read_closed: false,
+            write_closed: false,
+            error: None,
+        }
+    }
+
+    /// Move the messages reassembled by the session to the read buffer.
+    fn take_incoming(&mut self) {
+        for mut message in self.session.fetch_incoming() {
+            if self.read_closed {
+                debug!(
+                    "conversation {}: dropping data received after the end of the stream",
+                    self.session.conv
+                );
+                continue;
+            }
+            let tag = match message.first() {
+                Some(tag) => *tag,
+                None => continue,
+            };
+            match tag {
+                TAG_DATA => {
+                    let data = message.split_off(1);
+                    if self.read_buf.is_empty() {
+                        self.read_buf = data;
+                    } else {
+                        self.read_buf.extend_from_slice(&data);
+                    }
+                }
+                TAG_FIN => self.read_closed = true,
+                other => warn!(
+                    "conversation {}: ignoring message with unknown tag {other}",
+                    self.session.conv
+                ),
+            }
+        }
+    }
+
+    fn wake_reader(&mut self) {
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn wake_writer(&mut self) {
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+struct Conversations {
+    active: HashMap<u32, Arc<Mutex<Conversation>>>,
+    // set once the driver stopped
+    terminated: Option<io::ErrorKind>,
+}
+
+struct Shared {
+    config: KcpConfig,
+    conversations: Mutex<Conversations>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // the state stays consistent even if a holder panicked, so just carry on
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Shared {
+    fn register(self: &Arc<Self>, conv: u32) -> Result<KcpStream, KcpError> {
+        let mut conversations = lock(&self.conversations);
+        if conversations.terminated.is_some() {
+            return Err(KcpError::TransportClosed);
+        }
+        if conversations.active.contains_key(&conv) {
+            return Err(KcpError::ConvInUse(conv));
+        }
+
+        let session = KcpSession::with_config(conv, self.config);
+        let conversation = Arc::new(Mutex::new(Conversation::new(session)));
+        conversations.active.insert(conv, conversation.clone());
+        Ok(KcpStream {
+            conv,
+            conversation,
+            shared: self.clone(),
+        })
+    }
+
+    /// Feed all the packets of a received datagram to their sessions,
+    /// returning the streams of newly started conversations.
+    fn input(
+        self: &Arc<Self>,
+        datagram: &mut BytesMut,
+        accept_new: bool,
+        outgoing: &mut Vec<Bytes>,
+    ) -> Vec<KcpStream> {
+        let mut new_streams = Vec::new();
+        loop {
+            let packet = match KcpPacket::decode(datagram) {
+                Ok(Some(packet)) => packet,
+                Ok(None) => break,
+                Err(err) => {
+                    warn!("dropping malformed datagram: {err}");
+                    break;
+                }
+            };
+
+            let existing = lock(&self.conversations)
+                .active
+                .get(&packet.conv())
+                .cloned();
+            let conversation = match existing {
+                Some(conversation) => conversation,
+                // only the very first segment of a conversation can start a new one,
+                // so that stray retransmissions for closed conversations don't resurrect them
+                None if accept_new && packet.cmd() == KcpCommand::Push && packet.sn() == 0 => {
+                    match self.register(packet.conv()) {
+                        Ok(stream) => {
+                            let conversation = stream.conversation.clone();
+                            new_streams.push(stream);
+                            conversation
+                        }
+                        Err(err) => {
+                            debug!("not accepting conversation {}: {err}", packet.conv());
+                            continue;
+                        }
+                    }
+                }
+                None => {
+                    debug!(
+                        "dropping {} packet for unknown conversation {}",
+                        packet.cmd(),
+                        packet.conv()
+                    );
+                    continue;
+                }
+            };
+
+            let mut conversation = lock(&conversation);
+            if let Err(err) = conversation.session.input(&packet) {
+                warn!("failed to process received packet: {err}");
+            }
+            let mtu = conversation.session.mtu();
+            pack_datagrams(conversation.session.fetch_outgoing(), mtu, outgoing);
+            conversation.take_incoming();
+            conversation.wake_reader();
+            conversation.wake_writer();
+        }
+        new_streams
+    }
+
+    /// Advance the clocks of all the sessions by `elapsed_ms` and collect whatever they want to send.
+    fn update(&self, elapsed_ms: u32, outgoing: &mut Vec<Bytes>) {
+        let conversations = lock(&self.conversations);
+        for conversation in conversations.active.values() {
+            let mut conversation = lock(conversation);
+            conversation.session.update(elapsed_ms);
+            let mtu = conversation.session.mtu();
+            pack_datagrams(conversation.session.fetch_outgoing(), mtu, outgoing);
+            conversation.wake_writer();
+        }
+    }
+
+    /// Fail all the conversations once the transport is gone.
+    fn terminate(&self, kind: io::ErrorKind) {
+        let mut conversations = lock(&self.conversations);
+        conversations.terminated = Some(kind);
+        for conversation in conversations.active.values() {
+            let mut conversation = lock(conversation);
+            conversation.error = Some(kind);
+            conversation.wake_reader();
+            conversation.wake_writer();
+        }
+    }
+
+    fn remove(&self, conv: u32, conversation: &Arc<Mutex<Conversation>>) {
+        let mut conversations = lock(&self.conversations);
+        if let Some(registered) = conversations.active.get(&conv)
+            && Arc::ptr_eq(registered, conversation)
+        {
+            conversations.active.remove(&conv);
+        }
+    }
+}
+
+/// Coalesce packets into datagrams of at most a single segment worth of bytes.
+fn pack_datagrams(packets: Vec<KcpPacket>, mtu: usize, outgoing: &mut Vec<Bytes>) {
+    let max_size = mtu + KCP_HEADER;
+    let mut datagram = BytesMut::new();
+    for packet in packets {
+        if !datagram.is_empty() && datagram.len() + KCP_HEADER + packet.data().len() > max_size {
+            outgoing.push(datagram.split().freeze());
+        }
+        packet.encode(&mut datagram);
+    }
+    if !datagram.is_empty() {
+        outgoing.push(datagram.freeze());
+    }
+}
+
+async fn drive<T: DatagramTransport>(
+    mut transport: T,
+    shared: Weak<Shared>,
+    incoming: mpsc::UnboundedSender<KcpStream>,
+    interval: Duration,
+) {
+    let mut ticker = time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_update = Instant::now();
+    let mut datagram = BytesMut::with_capacity(MAX_DATAGRAM_SIZE);
+    let mut outgoing = Vec::new();
+
+    let failure = loop {
+        // stop once the multiplexer and all of its streams got dropped
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+
+        tokio::select! {
+            received = transport.recv(&mut datagram) => match received {
+                Ok(true) => {
+                    for stream in shared.input(&mut datagram, !incoming.is_closed(), &mut outgoing) {
+                        // if the multiplexer got dropped in the meantime, the stream unregisters itself
+                        let _ = incoming.send(stream);
+                    }
+                    datagram.clear();
+                }
+                Ok(false) => break io::ErrorKind::ConnectionAborted,
+                // ICMP errors caused by previous sends, e.g. the peer not listening (yet)
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => {
+                    debug!("transient transport error: {err}");
+                    datagram.clear();
+                }
+                Err(err) => {
+                    warn!("failed to receive from the transport: {err}");
+                    break err.kind();
+                }
+            },
+            _ = ticker.tick() => {
+                let elapsed_ms = last_update.elapsed().as_millis() as u32;
+                last_update += Duration::from_millis(elapsed_ms as u64);
+                shared.update(elapsed_ms, &mut outgoing);
+            }
+        }
+
+        for datagram in outgoing.drain(..) {
+            // losing a datagram is fine, KCP will retransmit its content
+            if let Err(err) = transport.send(datagram).await {
+                debug!("failed to send datagram: {err}");
+            }
+        }
+    };
+
+    if let Some(shared) = shared.upgrade() {
+        shared.terminate(failure);
+    }
+}
+
+/// Multiplexes KCP conversations, identified by their conversation id, over a single transport.
+pub struct KcpMux {
+    shared: Arc<Shared>,
+    incoming: mpsc::UnboundedReceiver<KcpStream>,
+}
+
+impl KcpMux {
+    /// Start driving conversations over `transport` in a background task.
+    ///
+    /// Must be called from within a tokio runtime. The task stops once the multiplexer
+    /// and all the streams it created got dropped.
+    pub fn new<T: DatagramTransport>(transport: T, config: KcpConfig) -> Self {
+        let shared = Arc::new(Shared {
+            config,
+            conversations: Mutex::new(Conversations {
+                active: HashMap::new(),
+                terminated: None,
+            }),
+        });
+        let (incoming_tx, incoming) = mpsc::unbounded_channel();
+        let interval = Duration::from_millis(config.interval.clamp(10, 5000) as u64);
+        tokio::spawn(drive(
+            transport,
+            Arc::downgrade(&shared),
+            incoming_tx,
+            interval,
+        ));
+
+        KcpMux { shared, incoming }
+    }
+
+    /// Start a new conversation with the remote peer.
+    pub fn connect(&self, conv: u32) -> Result<KcpStream, KcpError> {
+        self.shared.register(conv)
+    }
+
+    /// Wait for the remote peer to start a new conversation.
+    ///
+    /// Returns `None` once the transport got closed.
+    pub async fn accept(&mut self) -> Option<KcpStream> {
+        // the driver task owns the sender and drops it when it stops
+        self.incoming.recv().await
+    }
+}
+
+/// Reliable, ordered byte stream over a single KCP conversation.
+///
+/// Data is only guaranteed to have been delivered once [`shutdown`](tokio::io::AsyncWriteExt::shutdown)
+/// completed: dropping the stream abandons whatever hasn't been acknowledged yet.
+pub struct KcpStream {
+    conv: u32,
+    conversation: Arc<Mutex<Conversation>>,
+    shared: Arc<Shared>,
+}
+
+impl KcpStream {
+    /// Start a conversation over a dedicated transport.
+    pub fn connect<T: DatagramTransport>(
+        transport: T,
+        conv: u32,
+        config: KcpConfig,
+    ) -> Result<Self, KcpError> {
+        KcpMux::new(transport, config).connect(conv)
+    }
+
+    pub fn conv(&self) -> u32 {
+        self.conv
+    }
+}
+
+impl Drop for KcpStream {
+    fn drop(&mut self) {
+        self.shared.remove(self.conv, &self.conversation);
+    }
+}
+
+impl AsyncRead for KcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let mut conversation = lock(&self.conversation);
+        if !conversation.read_buf.is_empty() {
+            let n = buf.remaining().min(conversation.read_buf.len());
+            buf.put_slice(&conversation.read_buf.split_to(n));
+            return Poll::Ready(Ok(()));
+        }
+        if conversation.read_closed {
+            return Poll::Ready(Ok(()));
+        }
+        if let Some(kind) = conversation.error {
+            return Poll::Ready(Err(kind.into()));
+        }
+        conversation.read_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl AsyncWrite for KcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut conversation = lock(&self.conversation);
+        if let Some(kind) = conversation.error {
+            return Poll::Ready(Err(kind.into()));
+        }
+        if conversation.write_closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let available = conversation.session.available_send_segments();
+        if available == 0 {
+            conversation.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        // leave room for the tag
+        let capacity = (available * conversation.session.mtu()).min(MAX_WRITE_SIZE) - 1;
+        let len = buf.len().min(capacity);
+        let mut message = Vec::with_capacity(len + 1);
+        message.push(TAG_DATA);
+        message.extend_from_slice(&buf[..len]);
+        conversation.session.send(&message);
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // the data has been handed over to the session which (re)transmits it on its own
+        let conversation = lock(&self.conversation);
+        match conversation.error {
+            Some(kind) => Poll::Ready(Err(kind.into())),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    /// Signal the end of the stream to the peer and wait for all the data to be acknowledged.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut conversation = lock(&self.conversation);
+        if !conversation.write_closed {
+            conversation.write_closed = true;
+            conversation.session.send(&[TAG_FIN]);
+        }
+        if conversation.session.wait_snd() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        if let Some(kind) = conversation.error {
+            return Poll::Ready(Err(kind.into()));
+        }
+        conversation.write_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
@@ -0,0 +1,204 @@
+//! Transfers over a simulated lossy link, comparing the default and the fast profiles.
+
+use std::collections::VecDeque;
+
+use nym_kcp::{config::KcpConfig, packet::KcpPacket, session::KcpSession};
+
+const TICK_MS: u32 = 10;
+const LATENCY_MS: u32 = 50;
+const MAX_DURATION_MS: u32 = 600_000;
+
+/// Deterministic xorshift PRNG so that every run drops the same packets.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+}
+
+/// One direction of the link: packets are delivered `LATENCY_MS` after being sent, unless dropped.
+struct Link {
+    loss_percent: u64,
+    rng: Rng,
+    in_flight: VecDeque<(u32, KcpPacket)>,
+}
+
+impl Link {
+    fn new(loss_percent: u64, seed: u64) -> Self {
+        Link {
+            loss_percent,
+            rng: Rng(seed),
+            in_flight: VecDeque::new(),
+        }
+    }
+
+    fn send(&mut self, now: u32, packets: Vec<KcpPacket>) {
+        for packet in packets {
+            if !self.rng.chance(self.loss_percent) {
+                self.in_flight.push_back((now + LATENCY_MS, packet));
+            }
+        }
+    }
+
+    fn deliver(&mut self, now: u32, to: &mut KcpSession) {
+        while let Some((at, _)) = self.in_flight.front() {
+            if *at > now {
+                break;
+            }
+            let (_, packet) = self.in_flight.pop_front().unwrap();
+            to.input(&packet).unwrap();
+        }
+    }
+}
+
+struct Transfer {
+    received: Vec<u8>,
+    duration_ms: u32,
+}
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// Send `data` across links dropping `loss_percent` of the packets in each direction.
+fn transfer(config: KcpConfig, data: &[u8], loss_percent: u64, seed: u64) -> Transfer {
+    let conv = 42;
+    let mut sender = KcpSession::with_config(conv, config);
+    let mut receiver = KcpSession::with_config(conv, config);
+    let mut forward = Link::new(loss_percent, seed);
+    let mut backward = Link::new(loss_percent, seed.wrapping_mul(31).wrapping_add(7));
+
+    // hand the data over in chunks, like a stream writer would
+    for chunk in data.chunks(4096) {
+        sender.send(chunk);
+    }
+
+    let mut received = Vec::with_capacity(data.len());
+    let mut buf = vec![0u8; 8192];
+    let mut now = 0;
+    while received.len() < data.len() && now < MAX_DURATION_MS {
+        now += TICK_MS;
+
+        forward.deliver(now, &mut receiver);
+        backward.deliver(now, &mut sender);
+
+        sender.update(TICK_MS);
+        receiver.update(TICK_MS);
+        forward.send(now, sender.fetch_outgoing());
+        backward.send(now, receiver.fetch_outgoing());
+
+        loop {
+            let n = receiver.recv(&mut buf);
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    Transfer {
+        received,
+        duration_ms: now,
+    }
+}
+
+#[test]
+fn fast_profile_delivers_in_order_with_10_percent_loss() {
+    let data = payload(200_000);
+    let transfer = transfer(KcpConfig::fast(), &data, 10, 0x9e37_79b9_7f4a_7c15);
+    assert_eq!(transfer.received.len(), data.len());
+    assert!(transfer.received == data, "data got corrupted or reordered");
+}
+
+#[test]
+fn fast_profile_delivers_in_order_with_30_percent_loss() {
+    let data = payload(100_000);
+    let transfer = transfer(KcpConfig::fast(), &data, 30, 0xdead_beef_cafe_f00d);
+    assert_eq!(transfer.received.len(), data.len());
+    assert!(transfer.received == data, "data got corrupted or reordered");
+}
+
+#[test]
+fn fast_profile_outperforms_default_under_loss() {
+    let data = payload(100_000);
+    let seed = 0x0123_4567_89ab_cdef;
+
+    let default = transfer(KcpConfig::default(), &data, 10, seed);
+    let fast = transfer(KcpConfig::fast(), &data, 10, seed);
+    assert!(default.received == data);
+    assert!(fast.received == data);
+
+    assert!(
+        fast.duration_ms * 2 < default.duration_ms,
+        "fast profile took {}ms while the default one took {}ms",
+        fast.duration_ms,
+        default.duration_ms
+    );
+}
+
+#[test]
+fn lost_segment_is_fast_retransmitted_before_rto() {
+    let conv = 43;
+    let config = KcpConfig::fast();
+    let mut sender = KcpSession::with_config(conv, config);
+    let mut receiver = KcpSession::with_config(conv, config);
+    sender.set_mtu(100);
+    receiver.set_mtu(100);
+
+    // open up the congestion window first
+    let mut now = 0;
+    let mut warmup = Vec::new();
+    sender.send(&payload(2_000));
+    while warmup.len() < 2_000 {
+        now += TICK_MS;
+        sender.update(TICK_MS);
+        for packet in sender.fetch_outgoing() {
+            receiver.input(&packet).unwrap();
+        }
+        receiver.update(TICK_MS);
+        for packet in receiver.fetch_outgoing() {
+            sender.input(&packet).unwrap();
+        }
+        let mut buf = [0u8; 4096];
+        let n = receiver.recv(&mut buf);
+        warmup.extend_from_slice(&buf[..n]);
+    }
+    assert!(sender.cwnd() >= 4, "cwnd: {}", sender.cwnd());
+
+    // drop the first segment of the next message, deliver the rest
+    sender.send(&payload(400));
+    sender.update(TICK_MS);
+    let packets = sender.fetch_outgoing();
+    assert!(packets.len() >= 3);
+    let lost_sn = packets[0].sn();
+    let sent_at = now + TICK_MS;
+    for packet in &packets[1..] {
+        receiver.input(packet).unwrap();
+    }
+    for packet in receiver.fetch_outgoing() {
+        sender.input(&packet).unwrap();
+    }
+
+    // the duplicate acks trigger a retransmission on the next flush, well before the RTO of 30ms+
+    sender.update(TICK_MS);
+    let retransmitted = sender.fetch_outgoing();
+    assert!(
+        retransmitted.iter().any(|packet| packet.sn() == lost_sn),
+        "segment {lost_sn} sent at {sent_at}ms wasn't fast retransmitted"
+    );
+    for packet in &retransmitted {
+        receiver.input(packet).unwrap();
+    }
+
+    let mut buf = [0u8; 4096];
+    assert_eq!(receiver.recv(&mut buf), 400);
+    assert_eq!(&buf[..400], payload(400).as_slice());
+}
@@ -0,0 +1,188 @@
+//! `KcpStream`s talking to each other over real and simulated datagram transports.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::{StreamExt, channel::mpsc};
+use nym_kcp::{
+    config::KcpConfig,
+    stream::{KcpMux, KcpStream, PacketTransport},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::UdpSocket,
+    time::timeout,
+};
+
+const TEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn payload(len: usize, seed: u8) -> Vec<u8> {
+    (0..len).map(|i| (i as u8).wrapping_mul(seed)).collect()
+}
+
+type ChannelTransport =
+    PacketTransport<mpsc::UnboundedSender<Bytes>, mpsc::UnboundedReceiver<Bytes>>;
+
+/// Two in-memory transports connected to each other, dropping every `drop_every`-th datagram.
+fn lossy_pair(drop_every: usize) -> (ChannelTransport, ChannelTransport) {
+    fn lossy_link(
+        drop_every: usize,
+    ) -> (mpsc::UnboundedSender<Bytes>, mpsc::UnboundedReceiver<Bytes>) {
+        let (tx, rx) = mpsc::unbounded();
+        let (lossy_tx, lossy_rx) = mpsc::unbounded();
+        tokio::spawn(
+            rx.enumerate()
+                .filter(move |(i, _)| {
+                    futures::future::ready(drop_every == 0 || (i + 1) % drop_every != 0)
+                })
+                .map(|(_, datagram)| Ok(datagram))
+                .forward(lossy_tx),
+        );
+        (tx, lossy_rx)
+    }
+
+    let (a_tx, b_rx) = lossy_link(drop_every);
+    let (b_tx, a_rx) = lossy_link(drop_every);
+    (
+        PacketTransport::new(a_tx, a_rx),
+        PacketTransport::new(b_tx, b_rx),
+    )
+}
+
+#[tokio::test]
+async fn udp_roundtrip_with_shutdown() {
+    let a = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    a.connect(b.local_addr().unwrap()).await.unwrap();
+    b.connect(a.local_addr().unwrap()).await.unwrap();
+
+    let mut client = KcpStream::connect(a, 7, KcpConfig::fast()).unwrap();
+    let mut server_mux = KcpMux::new(b, KcpConfig::fast());
+
+    let request = payload(50_000, 3);
+    let server = tokio::spawn(async move {
+        let mut stream = server_mux.accept().await.unwrap();
+        assert_eq!(stream.conv(), 7);
+
+        // echo everything back until the client is done
+        let mut received = Vec::new();
+        stream.read_to_end(&mut received).await.unwrap();
+        stream.write_all(&received).await.unwrap();
+        stream.shutdown().await.unwrap();
+    });
+
+    timeout(TEST_TIMEOUT, async {
+        client.write_all(&request).await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let mut response = Vec::new();
+        client.read_to_end(&mut response).await.unwrap();
+        assert!(response == request, "echoed data doesn't match");
+        server.await.unwrap();
+    })
+    .await
+    .unwrap();
+
+    // the stream has been shut down, so writing any more is an error
+    assert!(client.write_all(b"more").await.is_err());
+}
+
+#[tokio::test]
+async fn conversations_are_multiplexed_over_a_single_transport() {
+    let (a, b) = lossy_pair(0);
+    let client_mux = KcpMux::new(a, KcpConfig::fast());
+    let mut server_mux = KcpMux::new(b, KcpConfig::fast());
+
+    let server = tokio::spawn(async move {
+        let mut handles = Vec::new();
+        for _ in 0..3 {
+            let mut stream = server_mux.accept().await.unwrap();
+            handles.push(tokio::spawn(async move {
+                let mut received = Vec::new();
+                stream.read_to_end(&mut received).await.unwrap();
+                // prefix the echo with our conversation id so that crossed streams would be noticed
+                stream
+                    .write_all(&stream.conv().to_be_bytes())
+                    .await
+                    .unwrap();
+                stream.write_all(&received).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    });
+
+    drop(client_mux.connect(1).unwrap());
+    assert!(
+        client_mux.connect(1).is_ok(),
+        "a dropped stream releases its conversation id"
+    );
+
+    let mut clients = Vec::new();
+    for conv in [10u32, 20, 30] {
+        let mut stream = client_mux.connect(conv).unwrap();
+        assert!(client_mux.connect(conv).is_err(), "conversation id reused");
+        clients.push(tokio::spawn(async move {
+            let data = payload(20_000, conv as u8);
+            stream.write_all(&data).await.unwrap();
+            stream.shutdown().await.unwrap();
+
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).await.unwrap();
+            assert_eq!(&response[..4], &conv.to_be_bytes());
+            assert!(response[4..] == data, "conversation {conv} got mixed up");
+        }));
+    }
+
+    timeout(TEST_TIMEOUT, async {
+        for client in clients {
+            client.await.unwrap();
+        }
+        server.await.unwrap();
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn stream_recovers_from_lossy_transport() {
+    // drop every 7th datagram in both directions
+    let (a, b) = lossy_pair(7);
+    let mut client = KcpStream::connect(a, 99, KcpConfig::fast()).unwrap();
+    let mut server_mux = KcpMux::new(b, KcpConfig::fast());
+
+    let data = payload(100_000, 11);
+    let expected = data.clone();
+    let server = tokio::spawn(async move {
+        let mut stream = server_mux.accept().await.unwrap();
+        let mut received = Vec::new();
+        stream.read_to_end(&mut received).await.unwrap();
+        received
+    });
+
+    let received = timeout(TEST_TIMEOUT, async {
+        client.write_all(&data).await.unwrap();
+        client.shutdown().await.unwrap();
+        server.await.unwrap()
+    })
+    .await
+    .unwrap();
+    assert_eq!(received.len(), expected.len());
+    assert!(received == expected, "data got corrupted or reordered");
+}
+
+#[tokio::test]
+async fn closed_transport_fails_pending_reads() {
+    let (a, b) = lossy_pair(0);
+    let mut client = KcpStream::connect(a, 5, KcpConfig::fast()).unwrap();
+    drop(b);
+
+    let mut buf = [0u8; 16];
+    let result = timeout(TEST_TIMEOUT, client.read(&mut buf)).await.unwrap();
+    assert!(
+        result.is_err(),
+        "read should fail once the transport is gone"
+    );
+}
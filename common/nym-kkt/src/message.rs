@@ -9,7 +9,7 @@ use crate::keys::EncapsulationKey;
 use crate::masked_byte::{MASKED_BYTE_LEN, MaskedByte};
 use libcrux_chacha20poly1305::TAG_LEN;
 use libcrux_psq::handshake::types::{DHKeyPair, DHPrivateKey, DHPublicKey};
-use nym_kkt_ciphersuite::{KEM, x25519};
+use nym_kkt_ciphersuite::{Ciphersuite, KEM, x25519};
 
 pub struct KKTRequest {
     /// The plaintext part of the request
@@ -219,6 +219,9 @@ pub struct ProcessedKKTRequest {
     /// The KEM key requested in the original request
     pub requested_kem: KEM,
 
+    /// The full ciphersuite requested in the original request
+    pub requested_ciphersuite: Ciphersuite,
+
     /// The unmasked byte representing the outer protocol version sent by the initiator
     pub outer_protocol_version: u8,
 
@@ -156,6 +156,7 @@ impl<'a> KKTResponder<'a> {
             response: KKTResponse { encrypted_frame },
             remote_encapsulation_key,
             requested_kem: remote_context.ciphersuite().kem(),
+            requested_ciphersuite: remote_context.ciphersuite(),
             outer_protocol_version: processed_req.outer_protocol_version,
             request_payload,
         })
@@ -65,6 +65,14 @@ pub enum LpError {
     #[error("State machine not found for lp_id: {0}")]
     StateMachineNotFound(LpReceiverIndex),
 
+    #[error("the selected protocol version or ciphersuite is not part of the initiator's offer")]
+    InconsistentProtocolOffer,
+
+    #[error(
+        "the initiator selected protocol version {selected} while both parties support version {expected}"
+    )]
+    ProtocolDowngrade { selected: u8, expected: u8 },
+
     #[error("attempted to create an LP responder without providing a valid KEM keys")]
     ResponderWithMissingKEMKeys,
 
@@ -117,6 +125,12 @@ pub enum LpError {
 
     #[error("the current session is not in transport state")]
     NotInTransport,
+
+    #[error("the presented resumption handle is unknown, expired or has already been used")]
+    UnknownResumptionHandle,
+
+    #[error("the session resumption message failed authentication")]
+    InvalidResumptionTag,
 }
 
 impl LpError {
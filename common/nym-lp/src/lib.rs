@@ -3,11 +3,13 @@
 
 pub mod codec;
 pub mod error;
+pub mod negotiation;
 pub mod packet;
 pub mod peer;
 pub mod peer_config;
 pub mod psq;
 pub mod replay;
+pub mod resumption;
 pub mod session;
 mod session_integration;
 pub mod session_manager;
@@ -20,8 +22,9 @@ pub use nym_kkt_ciphersuite::{
 
 #[cfg(any(feature = "mock", test))]
 pub use replay::{ReceivingKeyCounterValidator, ReplayError};
+pub use resumption::{ResumableSession, ResumptionHandle};
 pub use session::LpTransportSession;
-pub use session_manager::SessionManager;
+pub use session_manager::{ResumptionStore, SessionManager};
 
 #[cfg(any(feature = "mock", test))]
 use nym_test_utils::helpers::u64_seeded_rng_09;
@@ -0,0 +1,256 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Negotiation of the protocol version and the ciphersuite used by the KKT/PSQ handshake.
+//!
+//! The initiator attaches a compact [`ProtocolOffer`] describing everything it is capable of
+//! to its KKT request (as part of the [`LpPeerConfig`](crate::peer_config::LpPeerConfig))
+//! alongside its actual selection. The responder verifies the selection is consistent
+//! with the offer and that no higher mutually supported protocol version exists.
+//!
+//! Both parties then bind the offer and the selection into the PSQ session context,
+//! so tampering with either of them results in a failed handshake
+//! rather than in a silently downgraded session.
+
+use crate::LpError;
+use crate::packet::version;
+use nym_crypto::hkdf::blake3::derive_key_blake3_multi_input;
+use nym_kkt_ciphersuite::{Ciphersuite, HashFunction, IntoEnumIterator, KEM, SignatureScheme};
+
+pub const PROTOCOL_OFFER_SIZE: usize = 3;
+
+const NEGOTIATION_TRANSCRIPT_CONTEXT: &str = "NYM-LP-NEGOTIATION-TRANSCRIPT-V1";
+
+/// Largest protocol version that can be represented in an offer
+pub const MAX_OFFERED_VERSION: u8 = 8;
+
+/// Compact description of protocol versions and cryptographic primitives supported by the initiator.
+///
+/// Encoded as three bitmaps:
+/// - versions: bit `n - 1` is set if version `n` is supported
+/// - KEMs: bit `n` is set if the KEM with discriminant `n` is supported
+/// - hash functions (low nibble) and signature schemes (high nibble),
+///   again indexed by their discriminants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolOffer {
+    versions: u8,
+    kems: u8,
+    hash_functions: u8,
+    signature_schemes: u8,
+}
+
+fn bit(index: u8) -> u8 {
+    1u8.checked_shl(index as u32).unwrap_or_default()
+}
+
+fn nibble_bit(index: u8) -> u8 {
+    if index < 4 { bit(index) } else { 0 }
+}
+
+impl ProtocolOffer {
+    pub fn new(
+        versions: &[u8],
+        kems: &[KEM],
+        hash_functions: &[HashFunction],
+        signature_schemes: &[SignatureScheme],
+    ) -> Self {
+        ProtocolOffer {
+            versions: versions
+                .iter()
+                .filter(|v| (1..=MAX_OFFERED_VERSION).contains(*v))
+                .fold(0, |acc, v| acc | bit(v - 1)),
+            kems: kems.iter().fold(0, |acc, kem| acc | bit((*kem).into())),
+            hash_functions: hash_functions
+                .iter()
+                .fold(0, |acc, hash| acc | nibble_bit((*hash).into())),
+            signature_schemes: signature_schemes
+                .iter()
+                .fold(0, |acc, sig| acc | nibble_bit((*sig).into())),
+        }
+    }
+
+    /// Offer including everything supported by this implementation
+    pub fn local() -> Self {
+        Self::new(
+            version::SUPPORTED,
+            &KEM::iter().collect::<Vec<_>>(),
+            &HashFunction::iter().collect::<Vec<_>>(),
+            &SignatureScheme::iter().collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn versions(&self) -> Vec<u8> {
+        (1..=MAX_OFFERED_VERSION)
+            .filter(|v| self.supports_version(*v))
+            .collect()
+    }
+
+    pub fn supports_version(&self, version: u8) -> bool {
+        (1..=MAX_OFFERED_VERSION).contains(&version) && self.versions & bit(version - 1) != 0
+    }
+
+    pub fn supports_ciphersuite(&self, ciphersuite: &Ciphersuite) -> bool {
+        self.kems & bit(ciphersuite.kem().into()) != 0
+            && self.hash_functions & nibble_bit(ciphersuite.hash_function().into()) != 0
+            && self.signature_schemes & nibble_bit(ciphersuite.signature_scheme().into()) != 0
+    }
+
+    pub fn to_bytes(&self) -> [u8; PROTOCOL_OFFER_SIZE] {
+        [
+            self.versions,
+            self.kems,
+            self.hash_functions | (self.signature_schemes << 4),
+        ]
+    }
+
+    pub fn from_bytes(bytes: [u8; PROTOCOL_OFFER_SIZE]) -> Self {
+        ProtocolOffer {
+            versions: bytes[0],
+            kems: bytes[1],
+            hash_functions: bytes[2] & 0x0f,
+            signature_schemes: bytes[2] >> 4,
+        }
+    }
+
+    /// Ensure the selection made by the initiator is consistent with its offer
+    /// and that it has not been downgraded with respect to the versions supported by the responder.
+    pub fn verify_selection(
+        &self,
+        selected_version: u8,
+        selected_ciphersuite: &Ciphersuite,
+        responder_versions: &[u8],
+    ) -> Result<(), LpError> {
+        if !self.supports_version(selected_version)
+            || !self.supports_ciphersuite(selected_ciphersuite)
+        {
+            return Err(LpError::InconsistentProtocolOffer);
+        }
+
+        let best_version = responder_versions
+            .iter()
+            .copied()
+            .filter(|v| self.supports_version(*v))
+            .max();
+
+        match best_version {
+            Some(expected) if expected == selected_version => Ok(()),
+            Some(expected) => Err(LpError::ProtocolDowngrade {
+                selected: selected_version,
+                expected,
+            }),
+            None => Err(LpError::UnsupportedVersion {
+                version: selected_version,
+            }),
+        }
+    }
+
+    /// Digest of the offer and the final selection that both parties mix into the session context
+    pub fn transcript_binding(
+        &self,
+        selected_version: u8,
+        selected_ciphersuite: &Ciphersuite,
+    ) -> [u8; 32] {
+        derive_key_blake3_multi_input(
+            NEGOTIATION_TRANSCRIPT_CONTEXT,
+            &[
+                &self.to_bytes(),
+                &[selected_version],
+                &selected_ciphersuite.encode(),
+            ],
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offer_roundtrip() {
+        let offer = ProtocolOffer::new(
+            &[1, 3],
+            &[KEM::McEliece],
+            &[HashFunction::Blake3, HashFunction::SHA256],
+            &[SignatureScheme::Ed25519],
+        );
+        let recovered = ProtocolOffer::from_bytes(offer.to_bytes());
+        assert_eq!(offer, recovered);
+        assert_eq!(recovered.versions(), vec![1, 3]);
+
+        let local = ProtocolOffer::local();
+        assert_eq!(local, ProtocolOffer::from_bytes(local.to_bytes()));
+        assert_eq!(local.versions(), version::SUPPORTED.to_vec());
+        for kem in KEM::iter() {
+            assert!(local.supports_ciphersuite(&Ciphersuite::default().with_kem(kem)));
+        }
+    }
+
+    #[test]
+    fn selection_must_be_part_of_the_offer() {
+        let offer = ProtocolOffer::new(
+            &[1],
+            &[KEM::MlKem768],
+            &[HashFunction::Blake3],
+            &[SignatureScheme::Ed25519],
+        );
+        let offered = Ciphersuite::default().with_kem(KEM::MlKem768);
+        let not_offered = Ciphersuite::default().with_kem(KEM::McEliece);
+
+        assert!(offer.verify_selection(1, &offered, &[1]).is_ok());
+        assert!(matches!(
+            offer.verify_selection(1, &not_offered, &[1]),
+            Err(LpError::InconsistentProtocolOffer)
+        ));
+        assert!(matches!(
+            offer.verify_selection(2, &offered, &[1, 2]),
+            Err(LpError::InconsistentProtocolOffer)
+        ));
+    }
+
+    #[test]
+    fn downgraded_version_is_rejected() {
+        let offer = ProtocolOffer::new(
+            &[1, 2],
+            &[KEM::MlKem768],
+            &[HashFunction::Blake3],
+            &[SignatureScheme::Ed25519],
+        );
+        let ciphersuite = Ciphersuite::default();
+
+        assert!(offer.verify_selection(2, &ciphersuite, &[1, 2]).is_ok());
+        assert!(offer.verify_selection(1, &ciphersuite, &[1]).is_ok());
+        assert!(matches!(
+            offer.verify_selection(1, &ciphersuite, &[1, 2]),
+            Err(LpError::ProtocolDowngrade {
+                selected: 1,
+                expected: 2
+            })
+        ));
+        assert!(matches!(
+            offer.verify_selection(1, &ciphersuite, &[3]),
+            Err(LpError::UnsupportedVersion { version: 1 })
+        ));
+    }
+
+    #[test]
+    fn transcript_binding_depends_on_offer_and_selection() {
+        let ciphersuite = Ciphersuite::default();
+        let offer = ProtocolOffer::local();
+        let reduced = ProtocolOffer::new(
+            version::SUPPORTED,
+            &[KEM::MlKem768],
+            &[HashFunction::Blake3],
+            &[SignatureScheme::Ed25519],
+        );
+
+        let binding = offer.transcript_binding(1, &ciphersuite);
+        assert_eq!(binding, offer.transcript_binding(1, &ciphersuite));
+        assert_ne!(binding, reduced.transcript_binding(1, &ciphersuite));
+        assert_ne!(binding, offer.transcript_binding(2, &ciphersuite));
+        assert_ne!(
+            binding,
+            offer.transcript_binding(1, &ciphersuite.with_kem(KEM::McEliece))
+        );
+    }
+}
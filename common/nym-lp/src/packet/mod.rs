@@ -18,6 +18,18 @@ pub mod replay;
 pub mod version {
     /// The current version of the Lewes Protocol that is put into each new constructed header.
     pub const CURRENT: u8 = 1;
+
+    /// All versions of the Lewes Protocol this implementation is capable of speaking.
+    pub const SUPPORTED: &[u8] = &[CURRENT];
+
+    /// Select the highest protocol version supported by both this implementation and the remote.
+    pub fn negotiate(remote_supported: &[u8]) -> Option<u8> {
+        SUPPORTED
+            .iter()
+            .filter(|version| remote_supported.contains(version))
+            .max()
+            .copied()
+    }
 }
 
 #[allow(dead_code)]
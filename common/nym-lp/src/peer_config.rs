@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::LpError;
+use crate::negotiation::{PROTOCOL_OFFER_SIZE, ProtocolOffer};
 use libcrux_psq::handshake::types::Authenticator;
 
 use nym_crypto::hkdf::blake3::derive_key_blake3_multi_input;
@@ -19,13 +20,15 @@ const SEED_LEN: usize = 16;
 const CONFIG_LEN: usize = 1;
 const FILLER_LEN: usize = LP_PEER_CONFIG_SIZE - SEED_LEN - CONFIG_LEN;
 
+// the protocol offer, if present, occupies the beginning of the filler
+const _: () = assert!(PROTOCOL_OFFER_SIZE <= FILLER_LEN);
+
 const RECEIVER_INDEX_DERIVATION_CONTEXT: &str = "LP_PEER_CONFIG_RECEIVER_INDEX_DERIVATION_V1";
 
 // 20 bytes
 #[derive(PartialEq)]
 pub struct LpPeerConfig {
-    // The first 4 fields will be packed in one u8
-    // with 1 bit left at the end
+    // The first 5 fields will be packed in one u8
 
     // Determine the hop id.
     // Should be 0 if node_initiator is true
@@ -42,7 +45,11 @@ pub struct LpPeerConfig {
     // Enable censorship resistance countermeasures
     censorship_resistance: bool,
 
+    // Determine if the filler contains the initiator's protocol offer
+    has_protocol_offer: bool,
+
     // If we add more config params later, we can use this
+    // (currently holds the protocol offer if `has_protocol_offer` is set)
     filler: [u8; FILLER_LEN],
 
     seed: [u8; SEED_LEN],
@@ -146,6 +153,7 @@ impl LpPeerConfig {
             is_exit,
             node_initiator,
             censorship_resistance,
+            has_protocol_offer: false,
             filler,
             seed,
         }
@@ -193,6 +201,24 @@ impl LpPeerConfig {
         }
     }
 
+    /// Attach the protocol offer of the initiator used for version and ciphersuite negotiation
+    #[must_use]
+    pub fn with_protocol_offer(mut self, offer: ProtocolOffer) -> Self {
+        self.has_protocol_offer = true;
+        self.filler[..PROTOCOL_OFFER_SIZE].copy_from_slice(&offer.to_bytes());
+        self
+    }
+
+    /// Protocol offer of the initiator, if it has been included
+    pub fn protocol_offer(&self) -> Option<ProtocolOffer> {
+        if !self.has_protocol_offer {
+            return None;
+        }
+        let mut offer = [0u8; PROTOCOL_OFFER_SIZE];
+        offer.copy_from_slice(&self.filler[..PROTOCOL_OFFER_SIZE]);
+        Some(ProtocolOffer::from_bytes(offer))
+    }
+
     pub fn hop_id(&self) -> u8 {
         self.hop_id
     }
@@ -216,7 +242,7 @@ impl LpPeerConfig {
                 LP_PEER_CONFIG_SIZE
             )));
         }
-        let (hop_id, is_exit, node_initiator, censorship_resistance) =
+        let (hop_id, is_exit, node_initiator, censorship_resistance, has_protocol_offer) =
             Self::unpack_first_byte(bytes[0]);
 
         let mut filler = [0u8; FILLER_LEN];
@@ -225,14 +251,16 @@ impl LpPeerConfig {
         let mut seed = [0u8; SEED_LEN];
         seed.copy_from_slice(&bytes[CONFIG_LEN + FILLER_LEN..LP_PEER_CONFIG_SIZE]);
 
-        Self::build_checked(
+        let mut config = Self::build_checked(
             hop_id,
             is_exit,
             node_initiator,
             censorship_resistance,
             seed,
             filler,
-        )
+        )?;
+        config.has_protocol_offer = has_protocol_offer;
+        Ok(config)
     }
 
     fn pack_config(&self) -> [u8; 4] {
@@ -259,13 +287,15 @@ impl LpPeerConfig {
         if self.censorship_resistance {
             byte |= 0b0100_0000;
         }
-
-        // There will be 1 free bit at the end
+        // Set the 8th bit to determine if the filler contains the protocol offer
+        if self.has_protocol_offer {
+            byte |= 0b1000_0000;
+        }
 
         byte
     }
 
-    fn unpack_first_byte(byte: u8) -> (u8, bool, bool, bool) {
+    fn unpack_first_byte(byte: u8) -> (u8, bool, bool, bool, bool) {
         // extract 4 bits
         let hop_id = byte & 0b0000_1111;
 
@@ -275,9 +305,16 @@ impl LpPeerConfig {
         let node_initiator = (byte & 0b0010_0000) >> 5 == 1;
         // extract 7th bit
         let censorship_resistance = (byte & 0b0100_0000) >> 6 == 1;
+        // extract 8th bit
+        let has_protocol_offer = (byte & 0b1000_0000) >> 7 == 1;
 
-        // If we need to use the last bit, we can add something here
-        (hop_id, is_exit, node_initiator, censorship_resistance)
+        (
+            hop_id,
+            is_exit,
+            node_initiator,
+            censorship_resistance,
+            has_protocol_offer,
+        )
     }
 
     pub fn is_client_entry(&self) -> bool {
@@ -321,6 +358,7 @@ impl LpPeerConfig {
 
 #[cfg(test)]
 mod test {
+    use crate::negotiation::ProtocolOffer;
     use crate::peer_config::LpPeerConfig;
 
     #[test]
@@ -449,6 +487,26 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_protocol_offer() {
+        let mut rng = rand09::rng();
+
+        let conf = LpPeerConfig::new_client_to_entry(&mut rng, false);
+        assert!(conf.protocol_offer().is_none());
+        let deserialized = LpPeerConfig::deserialize(&conf.serialize()).unwrap();
+        assert!(deserialized.protocol_offer().is_none());
+
+        let offer = ProtocolOffer::local();
+        let conf = LpPeerConfig::new_client_to_entry(&mut rng, true).with_protocol_offer(offer);
+        let conf_bytes = conf.serialize();
+        assert_eq!(conf_bytes[0], 0b1100_0000);
+
+        let deserialized = LpPeerConfig::deserialize(&conf_bytes).unwrap();
+        assert_eq!(deserialized.protocol_offer(), Some(offer));
+        assert!(deserialized.is_client_entry());
+        assert_eq!(deserialized.seed(), conf.seed());
+    }
+
     #[test]
     fn test_failures() {
         let mut rng = rand09::rng();
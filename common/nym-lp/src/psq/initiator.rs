@@ -7,7 +7,7 @@ use crate::psq::handshake_message::{PSQMsg1, PSQMsg2};
 use crate::psq::helpers::kem_to_ciphersuite;
 use crate::psq::{
     AAD_INITIATOR_INNER_V1, AAD_INITIATOR_OUTER_V1, InitiatorData, PSQ_MSG2_SIZE,
-    PSQHandshakeState, SESSION_CONTEXT_V1, handshake_message, psq_msg1_size,
+    PSQHandshakeState, SESSION_CONTEXT_V1, bound_session_context, handshake_message, psq_msg1_size,
 };
 use crate::session::PersistentSessionBinding;
 use crate::transport::traits::LpHandshakeChannel;
@@ -51,11 +51,26 @@ pub struct PSQHandshakeStateInitiator<'a, S> {
     pub(super) mode: HandshakeMode,
 }
 
+#[cfg(any(feature = "mock", test))]
 pub(crate) fn build_psq_principal<R>(
     rng: R,
     version: u8,
     ciphersuite: InitiatorCiphersuite,
 ) -> Result<RegistrationInitiator<R>, LpError>
+where
+    R: rand09::CryptoRng,
+{
+    build_bound_psq_principal(rng, version, ciphersuite, None)
+}
+
+/// Build the PSQ initiator, optionally replacing the default session context of the version
+/// with the one bound to the negotiation transcript
+pub(crate) fn build_bound_psq_principal<'a, R>(
+    rng: R,
+    version: u8,
+    ciphersuite: InitiatorCiphersuite<'a>,
+    bound_context: Option<&'a [u8]>,
+) -> Result<RegistrationInitiator<'a, R>, LpError>
 where
     R: rand09::CryptoRng,
 {
@@ -71,7 +86,7 @@ where
     PrincipalBuilder::new(rng)
         .outer_aad(outer_aad)
         .inner_aad(inner_aad)
-        .context(ctx)
+        .context(bound_context.unwrap_or(ctx))
         .build_registration_initiator(ciphersuite)
         .map_err(|inner| LpError::PSQInitiatorBuilderFailure { inner })
 }
@@ -106,16 +121,20 @@ where
         // for now we don't support censorship resistance flag
         let censorship_resistance = false;
 
-        match self.mode {
-            HandshakeMode::OneWayEntry => Ok(LpPeerConfig::new_client_to_entry(
-                rng,
-                censorship_resistance,
-            )),
+        let config = match self.mode {
+            HandshakeMode::OneWayEntry => {
+                LpPeerConfig::new_client_to_entry(rng, censorship_resistance)
+            }
             HandshakeMode::OneWayExit => {
-                LpPeerConfig::new_client_to_exit(rng, 1, censorship_resistance)
+                LpPeerConfig::new_client_to_exit(rng, 1, censorship_resistance)?
             }
-            HandshakeMode::MutualInternode => LpPeerConfig::new_node_to_node(rng),
-        }
+            HandshakeMode::MutualInternode => LpPeerConfig::new_node_to_node(rng)?,
+        };
+
+        Ok(match self.initiator_data.protocol_offer {
+            Some(offer) => config.with_protocol_offer(offer),
+            None => config,
+        })
     }
 
     /// Attempt to send KKT request to begin the handshake
@@ -225,7 +244,21 @@ where
             &response.encapsulation_key,
         )?;
 
-        let mut psq_initiator = build_psq_principal(rng, protocol, initiator_ciphersuite)?;
+        // bind our offer and the final selection into the session
+        let bound_context = self
+            .initiator_data
+            .protocol_offer
+            .map(|offer| {
+                bound_session_context(protocol, &offer.transcript_binding(protocol, &ciphersuite))
+            })
+            .transpose()?;
+
+        let mut psq_initiator = build_bound_psq_principal(
+            rng,
+            protocol,
+            initiator_ciphersuite,
+            bound_context.as_deref(),
+        )?;
 
         // PSQ msg 1 send
         let mut buf = vec![0u8; psq_msg1_size(kem)];
@@ -355,10 +388,21 @@ mod tests {
                 .timeboxed()
                 .await??;
 
-            // 3. read PSQ req
+            // 3. read PSQ req (bound to the offer sent alongside the KKT request)
+            let offer = LpPeerConfig::deserialize(&processed_req.request_payload)?
+                .protocol_offer()
+                .unwrap();
+            let bound_context = bound_session_context(
+                1,
+                &offer.transcript_binding(1, &processed_req.requested_ciphersuite),
+            )?;
             let responder_ciphersuite = responder::build_psq_ciphersuite(&resp, kem)?;
-            let mut responder =
-                responder::build_psq_principal(rand09::rng(), 1, responder_ciphersuite)?;
+            let mut responder = responder::build_bound_psq_principal(
+                rand09::rng(),
+                1,
+                responder_ciphersuite,
+                Some(&bound_context),
+            )?;
             let response_len = psq_msg1_size(kem);
 
             let msg: PSQMsg1 = conn_resp
@@ -477,10 +521,21 @@ mod tests {
                 .timeboxed()
                 .await??;
 
-            // 3. read PSQ req
+            // 3. read PSQ req (bound to the offer sent alongside the KKT request)
+            let offer = LpPeerConfig::deserialize(&processed_req.request_payload)?
+                .protocol_offer()
+                .unwrap();
+            let bound_context = bound_session_context(
+                1,
+                &offer.transcript_binding(1, &processed_req.requested_ciphersuite),
+            )?;
             let responder_ciphersuite = responder::build_psq_ciphersuite(&resp, kem)?;
-            let mut responder =
-                responder::build_psq_principal(rand09::rng(), 1, responder_ciphersuite)?;
+            let mut responder = responder::build_bound_psq_principal(
+                rand09::rng(),
+                1,
+                responder_ciphersuite,
+                Some(&bound_context),
+            )?;
             let response_len = psq_msg1_size(kem);
 
             let msg: PSQMsg1 = conn_resp
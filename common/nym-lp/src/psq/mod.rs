@@ -13,15 +13,28 @@ pub mod initiator;
 pub mod responder;
 
 use crate::LpError;
+use crate::negotiation::ProtocolOffer;
 use crate::psq::initiator::HandshakeMode;
 pub use initiator::PSQHandshakeStateInitiator;
-pub use responder::PSQHandshakeStateResponder;
+pub use responder::{PSQHandshakeStateResponder, SessionOrigin};
 
 pub(crate) const AAD_INITIATOR_OUTER_V1: &[u8] = b"NYM-PQ-AAD-INIT-OUTER-V1";
 pub(crate) const AAD_INITIATOR_INNER_V1: &[u8] = b"NYM-PQ-AAD-INIT-INNER-V1";
 pub(crate) const AAD_RESPONDER_V1: &[u8] = b"NYM-PQ-AAD-RESP-V1";
 pub(crate) const SESSION_CONTEXT_V1: &[u8] = b"NYM-PQ-SESSION-CONTEXT-V1";
 
+/// PSQ session context of the given protocol version bound to the negotiation transcript
+pub(crate) fn bound_session_context(
+    version: u8,
+    transcript_binding: &[u8; 32],
+) -> Result<Vec<u8>, LpError> {
+    let base = match version {
+        1 => SESSION_CONTEXT_V1,
+        other => return Err(LpError::UnsupportedVersion { version: other }),
+    };
+    Ok([base, transcript_binding.as_slice()].concat())
+}
+
 /// Size of the first (initiator) PSQ message including all serialisation overheads if no additional payload has been attached
 pub(crate) fn psq_msg1_size(kem: KEM) -> usize {
     match kem {
@@ -50,6 +63,11 @@ pub struct InitiatorData {
     /// Representation of a remote Lewes Protocol peer
     /// encapsulating all the known information and keys.
     pub remote_peer: LpRemotePeer,
+
+    /// Versions and primitives supported by the initiator that are bound into the handshake
+    /// transcript for downgrade protection.
+    /// It should only be omitted when talking to legacy responders that predate negotiation.
+    pub protocol_offer: Option<ProtocolOffer>,
 }
 
 impl InitiatorData {
@@ -57,8 +75,16 @@ impl InitiatorData {
         InitiatorData {
             protocol_version,
             remote_peer,
+            protocol_offer: Some(ProtocolOffer::local()),
         }
     }
+
+    /// Do not include the protocol offer in the handshake, as required by legacy responders
+    #[must_use]
+    pub fn without_protocol_offer(mut self) -> Self {
+        self.protocol_offer = None;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -226,6 +252,150 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn e2e_psq_handshake_with_legacy_initiator() -> anyhow::Result<()> {
+        let conn_init = MockIOStream::default();
+        let conn_resp = conn_init.try_get_remote_handle();
+        let conn_init = conn_init.leak();
+        let conn_resp = conn_resp.leak();
+
+        let (init, resp) = mock_peers();
+        let resp_remote = resp.as_remote();
+
+        // initiators that predate negotiation do not include any offer
+        let handshake_init = PSQHandshakeState::new(conn_init, init).as_initiator(
+            InitiatorData::new(1, resp_remote).without_protocol_offer(),
+            HandshakeMode::OneWayEntry,
+        )?;
+        let handshake_resp =
+            PSQHandshakeState::new(conn_resp, resp).as_responder(ResponderData::default());
+
+        let init_fut = handshake_init.complete_handshake().spawn_timeboxed();
+        let resp_fut = handshake_resp.complete_handshake().spawn_timeboxed();
+        let (session_init, session_resp) = join!(init_fut, resp_fut);
+        let mut session_init = session_init???;
+        let mut session_resp = session_resp???;
+
+        let ct = encrypt_data(b"legacy", session_init.active_transport())?;
+        let pt = decrypt_data(&ct, session_resp.active_transport())?;
+        assert_eq!(pt, b"legacy");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn e2e_psq_handshake_rejects_version_downgrade() -> anyhow::Result<()> {
+        let conn_init = MockIOStream::default();
+        let conn_resp = conn_init.try_get_remote_handle();
+        let conn_init = conn_init.leak();
+        let conn_resp = conn_resp.leak();
+
+        let (init, resp) = mock_peers();
+        let resp_remote = resp.as_remote();
+
+        // both parties claim to support version 2, yet version 1 has been selected
+        let offer = ProtocolOffer::new(
+            &[1, 2],
+            &KEM::iter().collect::<Vec<_>>(),
+            &HashFunction::iter().collect::<Vec<_>>(),
+            &SignatureScheme::iter().collect::<Vec<_>>(),
+        );
+        let mut initiator_data = InitiatorData::new(1, resp_remote);
+        initiator_data.protocol_offer = Some(offer);
+        let responder_data = ResponderData {
+            supported_outer_protocol_versions: vec![1, 2],
+            ..Default::default()
+        };
+
+        let handshake_init = PSQHandshakeState::new(conn_init, init)
+            .as_initiator(initiator_data, HandshakeMode::OneWayEntry)?;
+        let handshake_resp = PSQHandshakeState::new(conn_resp, resp).as_responder(responder_data);
+
+        let _init_fut = handshake_init.complete_handshake().spawn_timeboxed();
+        let resp_res = handshake_resp
+            .complete_handshake()
+            .spawn_timeboxed()
+            .await??;
+        assert!(matches!(
+            resp_res,
+            Err(LpError::ProtocolDowngrade {
+                selected: 1,
+                expected: 2
+            })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn e2e_psq_handshake_followed_by_resumption() -> anyhow::Result<()> {
+        use crate::resumption::resume_as_initiator;
+        use crate::session_manager::ResumptionStore;
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        let ttl = Duration::from_secs(60);
+        let store = Arc::new(Mutex::new(ResumptionStore::new()));
+        let (init, resp) = mock_peers();
+        let resp_remote = resp.as_remote();
+
+        let take_resumable = |store: &Arc<Mutex<ResumptionStore>>| {
+            let store = store.clone();
+            move |handle: &_| store.lock().unwrap().take(handle)
+        };
+
+        // 1. full handshake
+        let conn_init = MockIOStream::default();
+        let conn_resp = conn_init.try_get_remote_handle();
+        let conn_init = conn_init.leak();
+        let conn_resp = conn_resp.leak();
+
+        let handshake_init = PSQHandshakeState::new(conn_init, init).as_initiator(
+            InitiatorData::new(1, resp_remote),
+            HandshakeMode::OneWayEntry,
+        )?;
+        let handshake_resp =
+            PSQHandshakeState::new(conn_resp, resp.clone()).as_responder(ResponderData::default());
+
+        let init_fut = handshake_init.complete_handshake().spawn_timeboxed();
+        let resp_fut = handshake_resp
+            .complete_handshake_or_resume(take_resumable(&store))
+            .spawn_timeboxed();
+        let (session_init, session_resp) = join!(init_fut, resp_fut);
+        let session_init = session_init???;
+        let (session_resp, origin) = session_resp???;
+        assert_eq!(origin, SessionOrigin::Handshake);
+
+        store.lock().unwrap().insert(session_resp.resumable(ttl)?);
+        let resumable = session_init.resumable(ttl)?;
+
+        // 2. resumption on a new connection
+        let conn_init = MockIOStream::default();
+        let conn_resp = conn_init.try_get_remote_handle();
+        let conn_init = conn_init.leak();
+        let conn_resp = conn_resp.leak();
+
+        let handshake_resp =
+            PSQHandshakeState::new(conn_resp, resp).as_responder(ResponderData::default());
+        let init_fut = tokio::spawn(async move {
+            let mut rng = u64_seeded_rng_09(1);
+            resume_as_initiator(conn_init, resumable, &mut rng).await
+        });
+        let resp_fut = handshake_resp
+            .complete_handshake_or_resume(take_resumable(&store))
+            .spawn_timeboxed();
+        let (session_init, session_resp) = join!(init_fut, resp_fut);
+        let mut session_init = session_init??;
+        let (mut session_resp, origin) = session_resp???;
+        assert_eq!(origin, SessionOrigin::Resumption);
+        assert!(store.lock().unwrap().is_empty());
+
+        assert_eq!(session_init.receiver_index(), session_resp.receiver_index());
+        let ct = encrypt_data(b"resumed", session_init.active_transport())?;
+        let pt = decrypt_data(&ct, session_resp.active_transport())?;
+        assert_eq!(pt, b"resumed");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn e2e_psq_mutual_handshake() -> anyhow::Result<()> {
         for kem in KEM::iter() {
@@ -7,7 +7,11 @@ use crate::psq::handshake_message::{PSQMsg1, PSQMsg2};
 use crate::psq::helpers::kem_to_ciphersuite;
 use crate::psq::{
     AAD_RESPONDER_V1, PSQ_MSG2_SIZE, PSQHandshakeState, ResponderData, SESSION_CONTEXT_V1,
-    handshake_message, psq_msg1_size,
+    bound_session_context, handshake_message, psq_msg1_size,
+};
+use crate::resumption::{
+    RESUMPTION_PREFIX_SIZE, ResumableSession, ResumptionHandle, is_resumption_request,
+    resume_as_responder,
 };
 use crate::session::PersistentSessionBinding;
 use crate::transport::traits::{HandshakeMessage, LpHandshakeChannel};
@@ -29,11 +33,36 @@ pub struct PSQHandshakeStateResponder<'a, S> {
     pub(super) responder_data: ResponderData,
 }
 
+/// Describes how a session has been established by the responder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionOrigin {
+    /// Full KKT/PSQ handshake
+    Handshake,
+
+    /// Resumption of a previously established session
+    Resumption,
+}
+
+#[cfg(any(feature = "mock", test))]
 pub(crate) fn build_psq_principal<R>(
     rng: R,
     version: u8,
     ciphersuite: ResponderCiphersuite,
 ) -> Result<Responder<R>, LpError>
+where
+    R: rand09::CryptoRng,
+{
+    build_bound_psq_principal(rng, version, ciphersuite, None)
+}
+
+/// Build the PSQ responder, optionally replacing the default session context of the version
+/// with the one bound to the negotiation transcript
+pub(crate) fn build_bound_psq_principal<'a, R>(
+    rng: R,
+    version: u8,
+    ciphersuite: ResponderCiphersuite<'a>,
+    bound_context: Option<&'a [u8]>,
+) -> Result<Responder<'a, R>, LpError>
 where
     R: rand09::CryptoRng,
 {
@@ -43,7 +72,7 @@ where
     };
 
     PrincipalBuilder::new(rng)
-        .context(ctx)
+        .context(bound_context.unwrap_or(ctx))
         .outer_aad(aad)
         .recent_keys_upper_bound(30)
         .build_responder(ciphersuite)
@@ -77,10 +106,13 @@ impl<'a, S> PSQHandshakeStateResponder<'a, S>
 where
     S: LpHandshakeChannel + Unpin,
 {
+    fn kkt_request_size(&self, mode: KKTMode) -> usize {
+        KKTRequest::size_excluding_payload(mode, self.inner_state.local_peer.ciphersuite.kem())
+            + LP_PEER_CONFIG_SIZE
+    }
+
     async fn receive_kkt_request(&mut self, mode: KKTMode) -> Result<KKTRequest, LpError> {
-        let packet_len =
-            KKTRequest::size_excluding_payload(mode, self.inner_state.local_peer.ciphersuite.kem())
-                + LP_PEER_CONFIG_SIZE;
+        let packet_len = self.kkt_request_size(mode);
 
         // TODO: we have an issue here: if initiator sends us a KEM key of different type
         // than our ciphersuite, we will fail to receive it.
@@ -100,6 +132,24 @@ where
         Self::receive_kkt_request(self, KKTMode::OneWay).await
     }
 
+    /// Attempt to receive the rest of a one-way KKT request
+    /// after its prefix has already been read from the connection
+    async fn receive_one_way_kkt_request_remainder(
+        &mut self,
+        mut prefix: Vec<u8>,
+    ) -> Result<KKTRequest, LpError> {
+        let packet_len = self.kkt_request_size(KKTMode::OneWay);
+        let remainder = self
+            .inner_state
+            .connection
+            .read_n_bytes(packet_len - prefix.len())
+            .await?;
+        prefix.extend_from_slice(&remainder);
+
+        let req = handshake_message::KKTRequest::try_from_bytes(prefix)?;
+        Ok(req.into())
+    }
+
     /// Attempt to receive a KKT request from a mutual client
     async fn receive_mutual_kkt_request(&mut self) -> Result<KKTRequest, LpError> {
         Self::receive_kkt_request(self, KKTMode::Mutual).await
@@ -172,11 +222,101 @@ where
         };
         debug!("received KKT request");
 
+        self.complete_handshake_from_request(kkt_request, rng).await
+    }
+
+    /// Either complete the full KKT/PSQ handshake or resume a previously established session
+    /// if the initiator presents a resumption handle.
+    ///
+    /// `take_resumable` is only called for resumption attempts and is expected to return
+    /// the (not yet expired) session associated with the handle while revoking the handle.
+    /// Resumption is only supported for one-way (client) handshakes.
+    pub async fn complete_handshake_or_resume<F>(
+        self,
+        take_resumable: F,
+    ) -> Result<(LpTransportSession, SessionOrigin), LpError>
+    where
+        S: LpHandshakeChannel + Unpin,
+        F: FnOnce(&ResumptionHandle) -> Option<ResumableSession>,
+    {
+        let mut rng = rand09::rngs::StdRng::from_os_rng();
+        self.complete_handshake_or_resume_with_rng(take_resumable, &mut rng)
+            .await
+    }
+
+    pub async fn complete_handshake_or_resume_with_rng<F, R>(
+        mut self,
+        take_resumable: F,
+        rng: &mut R,
+    ) -> Result<(LpTransportSession, SessionOrigin), LpError>
+    where
+        S: LpHandshakeChannel + Unpin,
+        F: FnOnce(&ResumptionHandle) -> Option<ResumableSession>,
+        R: rand09::CryptoRng,
+    {
+        if !self.responder_data.initiator_kem_hashes.is_empty() {
+            let session = self.complete_handshake_with_rng(rng).await?;
+            return Ok((session, SessionOrigin::Handshake));
+        }
+
+        // both message types start with the same layout, so read just enough to tell them apart
+        let prefix = self
+            .inner_state
+            .connection
+            .read_n_bytes(RESUMPTION_PREFIX_SIZE)
+            .await?;
+
+        if is_resumption_request(&prefix, &self.inner_state.local_peer.x25519().pk) {
+            debug!("received session resumption request");
+            let session =
+                resume_as_responder(self.inner_state.connection, prefix, take_resumable, rng)
+                    .await?;
+            return Ok((session, SessionOrigin::Resumption));
+        }
+
+        let kkt_request = self.receive_one_way_kkt_request_remainder(prefix).await?;
+        debug!("received KKT request");
+
+        let session = self
+            .complete_handshake_from_request(kkt_request, rng)
+            .await?;
+        Ok((session, SessionOrigin::Handshake))
+    }
+
+    async fn complete_handshake_from_request<R>(
+        mut self,
+        kkt_request: KKTRequest,
+        rng: &mut R,
+    ) -> Result<LpTransportSession, LpError>
+    where
+        S: LpHandshakeChannel + Unpin,
+        R: rand09::CryptoRng,
+    {
         let processed_req = self.process_kkt_request(kkt_request)?;
         let kem = processed_req.requested_kem;
         let init_kem = processed_req.remote_encapsulation_key;
 
         let lp_peer_config = LpPeerConfig::deserialize(&processed_req.request_payload)?;
+        let version = processed_req.outer_protocol_version;
+
+        // make sure the initiator has not been tricked into using a weaker protocol version
+        // and bind its offer into the session.
+        // legacy initiators do not send any offer and thus get no downgrade protection
+        let bound_context = match lp_peer_config.protocol_offer() {
+            Some(offer) => {
+                let ciphersuite = processed_req.requested_ciphersuite;
+                offer.verify_selection(
+                    version,
+                    &ciphersuite,
+                    &self.responder_data.supported_outer_protocol_versions,
+                )?;
+                Some(bound_session_context(
+                    version,
+                    &offer.transcript_binding(version, &ciphersuite),
+                )?)
+            }
+            None => None,
+        };
 
         // 2. send back the KKTResponse
         debug!("sending KKT response");
@@ -188,8 +328,12 @@ where
 
         // construct the responder and process the message
         let responder_ciphersuite = build_psq_ciphersuite(&self.inner_state.local_peer, kem)?;
-        let version = processed_req.outer_protocol_version;
-        let mut psq_responder = build_psq_principal(rng, version, responder_ciphersuite)?;
+        let mut psq_responder = build_bound_psq_principal(
+            rng,
+            version,
+            responder_ciphersuite,
+            bound_context.as_deref(),
+        )?;
         psq_responder.read_message(&raw_psq1, &mut [])?;
 
         let initiator_authenticator = psq_responder
@@ -232,12 +376,7 @@ where
         };
 
         let psq_session = psq_responder.into_session()?;
-        LpTransportSession::new(
-            psq_session,
-            binding,
-            receiver_index,
-            processed_req.outer_protocol_version,
-        )
+        LpTransportSession::new(psq_session, binding, receiver_index, version)
     }
 }
 
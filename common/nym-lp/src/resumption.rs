@@ -0,0 +1,489 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resumption of previously established Lewes Protocol sessions.
+//!
+//! Once a KKT/PSQ handshake completes, both parties export a single-use resumption handle
+//! and a resumption secret from the PSQ session and retain a snapshot of its state.
+//! A returning initiator can then present the handle on a fresh connection instead of
+//! repeating the (expensive) KKT and PSQ exchanges:
+//!
+//! ```text
+//! Initiator                                                 Responder
+//!   nonce_i || mask(RESUME) || handle || tag_i   ───►   look up and revoke `handle`, verify `tag_i`
+//!                                                ◄───   nonce_r || tag_r
+//!
+//! both: psk'     = KDF(secret, nonce_i || nonce_r)
+//!       session' = Import(session, psk')
+//! ```
+//!
+//! The first 48 bytes of the request share the layout of the plaintext part of a KKT request
+//! (an x25519-sized value followed by a masked byte), so the responder can tell the two apart
+//! without the initiator revealing anything to a passive observer beyond a random looking prefix.
+//! Since the imported PSQ session is keyed by fresh nonces from both parties,
+//! re-deriving channels from the stored snapshot never results in nonce re-use.
+
+use crate::peer_config::LpReceiverIndex;
+use crate::session::PersistentSessionBinding;
+use crate::transport::LpHandshakeChannel;
+use crate::{LpError, LpTransportSession};
+use libcrux_psq::handshake::types::DHPublicKey;
+use libcrux_psq::session::{Session, SessionBinding, SessionError};
+use nym_crypto::hkdf::blake3::derive_key_blake3_multi_input;
+use nym_kkt::masked_byte::{MASKED_BYTE_LEN, MaskedByte};
+use nym_kkt_ciphersuite::x25519;
+use rand09::RngCore;
+use std::fmt::{Debug, Formatter};
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use tls_codec::Serialize;
+use tracing::debug;
+use zeroize::Zeroizing;
+
+/// Value of the masked byte announcing a resumption request rather than a KKT request
+/// (where the byte carries the outer protocol version)
+pub const RESUMPTION_MARKER: u8 = u8::MAX;
+
+pub const RESUMPTION_HANDLE_SIZE: usize = 32;
+const RESUMPTION_NONCE_SIZE: usize = 32;
+const RESUMPTION_TAG_SIZE: usize = 32;
+
+/// Size of the plaintext prefix shared between resumption and KKT requests
+pub(crate) const RESUMPTION_PREFIX_SIZE: usize = x25519::PUBLIC_KEY_LENGTH + MASKED_BYTE_LEN;
+
+pub(crate) const RESUMPTION_REQUEST_SIZE: usize =
+    RESUMPTION_PREFIX_SIZE + RESUMPTION_HANDLE_SIZE + RESUMPTION_TAG_SIZE;
+
+pub(crate) const RESUMPTION_RESPONSE_SIZE: usize = RESUMPTION_NONCE_SIZE + RESUMPTION_TAG_SIZE;
+
+// note: the x25519-sized prefix is a random nonce rather than a public key,
+// but it has to occupy the same space as the ephemeral key of a KKT request
+const _: () = assert!(RESUMPTION_NONCE_SIZE == x25519::PUBLIC_KEY_LENGTH);
+
+const RESUMPTION_HANDLE_CONTEXT: &[u8] = b"NYM-LP-RESUMPTION-HANDLE-V1";
+const RESUMPTION_SECRET_CONTEXT: &[u8] = b"NYM-LP-RESUMPTION-SECRET-V1";
+const RESUMPTION_REQUEST_TAG_CONTEXT: &str = "NYM-LP-RESUMPTION-REQUEST-TAG-V1";
+const RESUMPTION_RESPONSE_TAG_CONTEXT: &str = "NYM-LP-RESUMPTION-RESPONSE-TAG-V1";
+const RESUMPTION_PSK_CONTEXT: &str = "NYM-LP-RESUMPTION-PSK-V1";
+const RESUMPTION_RECEIVER_INDEX_CONTEXT: &str = "NYM-LP-RESUMPTION-RECEIVER-INDEX-V1";
+
+/// Opaque, single-use identifier of a resumable session
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResumptionHandle([u8; RESUMPTION_HANDLE_SIZE]);
+
+impl ResumptionHandle {
+    pub fn as_bytes(&self) -> &[u8; RESUMPTION_HANDLE_SIZE] {
+        &self.0
+    }
+}
+
+impl From<[u8; RESUMPTION_HANDLE_SIZE]> for ResumptionHandle {
+    fn from(bytes: [u8; RESUMPTION_HANDLE_SIZE]) -> Self {
+        ResumptionHandle(bytes)
+    }
+}
+
+impl Debug for ResumptionHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ResumptionHandle({})",
+            bs58::encode(&self.0).into_string()
+        )
+    }
+}
+
+/// Everything needed to derive a fresh session from a previously completed KKT/PSQ handshake.
+///
+/// It is held by both parties: the initiator presents its handle in order to resume,
+/// while the responder keeps track of the handles it's willing to accept.
+#[derive(Clone)]
+pub struct ResumableSession {
+    /// Identifier of this resumable session
+    handle: ResumptionHandle,
+
+    /// Secret exported from the original session, proving possession of the handle
+    secret: Zeroizing<[u8; 32]>,
+
+    /// Snapshot of the underlying PSQ session state
+    session_state: Zeroizing<Vec<u8>>,
+
+    /// The public key material bound to the underlying session
+    binding: PersistentSessionBinding,
+
+    /// Protocol version negotiated during the original handshake
+    protocol_version: u8,
+
+    /// Point in time after which this session can no longer be resumed
+    expires_at: Instant,
+}
+
+impl Debug for ResumableSession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResumableSession")
+            .field("handle", &self.handle)
+            .field("binding", &self.binding)
+            .field("protocol_version", &self.protocol_version)
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResumableSession {
+    /// Export the resumption data from an established session.
+    pub(crate) fn export(
+        psq_session: &Session,
+        binding: PersistentSessionBinding,
+        protocol_version: u8,
+        ttl: Duration,
+    ) -> Result<Self, LpError> {
+        let mut handle = [0u8; RESUMPTION_HANDLE_SIZE];
+        psq_session.export_secret(RESUMPTION_HANDLE_CONTEXT, &mut handle)?;
+
+        let mut secret = Zeroizing::new([0u8; 32]);
+        psq_session.export_secret(RESUMPTION_SECRET_CONTEXT, secret.as_mut())?;
+
+        // SAFETY of the snapshot: the state is never used for deriving channels directly,
+        // it's always re-keyed with a fresh PSK first (see `Self::resume`)
+        let session_state =
+            psq_session
+                .tls_serialize_detached()
+                .map_err(|err| LpError::PSQSessionFailure {
+                    inner: SessionError::Serialize(err),
+                })?;
+
+        Ok(ResumableSession {
+            handle: handle.into(),
+            secret,
+            session_state: Zeroizing::new(session_state),
+            binding,
+            protocol_version,
+            expires_at: Instant::now() + ttl,
+        })
+    }
+
+    pub fn handle(&self) -> ResumptionHandle {
+        self.handle
+    }
+
+    pub fn expires_at(&self) -> Instant {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn protocol_version(&self) -> u8 {
+        self.protocol_version
+    }
+
+    fn request_tag(&self, initiator_nonce: &[u8]) -> [u8; RESUMPTION_TAG_SIZE] {
+        derive_key_blake3_multi_input(
+            RESUMPTION_REQUEST_TAG_CONTEXT,
+            &[self.secret.as_slice(), initiator_nonce],
+            self.handle.as_bytes(),
+        )
+    }
+
+    fn response_tag(
+        &self,
+        initiator_nonce: &[u8],
+        responder_nonce: &[u8],
+    ) -> [u8; RESUMPTION_TAG_SIZE] {
+        derive_key_blake3_multi_input(
+            RESUMPTION_RESPONSE_TAG_CONTEXT,
+            &[self.secret.as_slice(), initiator_nonce, responder_nonce],
+            self.handle.as_bytes(),
+        )
+    }
+
+    /// Derive a new transport session from the stored state
+    /// using the nonces contributed by both parties.
+    fn resume(
+        self,
+        initiator_nonce: &[u8],
+        responder_nonce: &[u8],
+    ) -> Result<LpTransportSession, LpError> {
+        let psk = Zeroizing::new(derive_key_blake3_multi_input(
+            RESUMPTION_PSK_CONTEXT,
+            &[self.secret.as_slice(), initiator_nonce, responder_nonce],
+            self.handle.as_bytes(),
+        ));
+
+        let index_bytes = derive_key_blake3_multi_input(
+            RESUMPTION_RECEIVER_INDEX_CONTEXT,
+            &[psk.as_slice()],
+            self.handle.as_bytes(),
+        );
+        let receiver_index = LpReceiverIndex::from_le_bytes([
+            index_bytes[0],
+            index_bytes[1],
+            index_bytes[2],
+            index_bytes[3],
+        ]);
+
+        let psq_session =
+            Session::deserialize(&self.session_state, SessionBinding::from(&self.binding))?
+                .import(psk.as_slice(), SessionBinding::from(&self.binding))?;
+
+        LpTransportSession::new(
+            psq_session,
+            self.binding,
+            receiver_index,
+            self.protocol_version,
+        )
+    }
+}
+
+fn resumption_mask(initiator_nonce: &[u8], responder_key: &DHPublicKey) -> Vec<u8> {
+    let mut mask = Vec::with_capacity(RESUMPTION_NONCE_SIZE + x25519::PUBLIC_KEY_LENGTH);
+    mask.extend_from_slice(initiator_nonce);
+    mask.extend_from_slice(responder_key.as_ref());
+    mask
+}
+
+/// Check whether the provided prefix of the first handshake message
+/// announces a resumption request rather than a KKT request.
+pub(crate) fn is_resumption_request(prefix: &[u8], responder_key: &DHPublicKey) -> bool {
+    if prefix.len() != RESUMPTION_PREFIX_SIZE {
+        return false;
+    }
+    let (nonce, masked) = prefix.split_at(RESUMPTION_NONCE_SIZE);
+    let Ok(masked) = MaskedByte::try_from(masked) else {
+        return false;
+    };
+    masked
+        .unmask_check_version(&resumption_mask(nonce, responder_key), &[RESUMPTION_MARKER])
+        .is_ok()
+}
+
+/// Attempt to resume the provided session as the initiator over the given connection.
+pub async fn resume_as_initiator<S, R>(
+    connection: &mut S,
+    resumable: ResumableSession,
+    rng: &mut R,
+) -> Result<LpTransportSession, LpError>
+where
+    S: LpHandshakeChannel + Unpin,
+    R: RngCore,
+{
+    let mut initiator_nonce = [0u8; RESUMPTION_NONCE_SIZE];
+    rng.fill_bytes(&mut initiator_nonce);
+
+    let mask = resumption_mask(&initiator_nonce, &resumable.binding.responder_ecdh_pk);
+    let marker = MaskedByte::new(RESUMPTION_MARKER, &mask);
+
+    let mut request = Vec::with_capacity(RESUMPTION_REQUEST_SIZE);
+    request.extend_from_slice(&initiator_nonce);
+    request.extend_from_slice(marker.as_slice());
+    request.extend_from_slice(resumable.handle.as_bytes());
+    request.extend_from_slice(&resumable.request_tag(&initiator_nonce));
+
+    debug!("sending session resumption request");
+    connection.write_all_and_flush(&request).await?;
+
+    // the responder simply closes the connection if it doesn't recognise the handle
+    let response = connection.read_n_bytes(RESUMPTION_RESPONSE_SIZE).await?;
+    let (responder_nonce, tag) = response.split_at(RESUMPTION_NONCE_SIZE);
+
+    let expected = resumable.response_tag(&initiator_nonce, responder_nonce);
+    if !bool::from(expected.ct_eq(tag)) {
+        return Err(LpError::InvalidResumptionTag);
+    }
+    debug!("session resumption accepted");
+
+    resumable.resume(&initiator_nonce, responder_nonce)
+}
+
+/// Complete the resumption as the responder after having received the request prefix.
+///
+/// `take_resumable` is expected to remove the handle from the set of accepted handles
+/// (so that it could not be reused) and return the associated session if it hasn't expired.
+pub(crate) async fn resume_as_responder<S, R, F>(
+    connection: &mut S,
+    prefix: Vec<u8>,
+    take_resumable: F,
+    rng: &mut R,
+) -> Result<LpTransportSession, LpError>
+where
+    S: LpHandshakeChannel + Unpin,
+    R: RngCore,
+    F: FnOnce(&ResumptionHandle) -> Option<ResumableSession>,
+{
+    let remainder = connection
+        .read_n_bytes(RESUMPTION_REQUEST_SIZE - RESUMPTION_PREFIX_SIZE)
+        .await?;
+    let (handle, tag) = remainder.split_at(RESUMPTION_HANDLE_SIZE);
+    let initiator_nonce = &prefix[..RESUMPTION_NONCE_SIZE];
+
+    // SAFETY: we have just split off exactly RESUMPTION_HANDLE_SIZE bytes
+    #[allow(clippy::unwrap_used)]
+    let handle = ResumptionHandle(handle.try_into().unwrap());
+
+    let resumable = take_resumable(&handle).ok_or(LpError::UnknownResumptionHandle)?;
+    let expected = resumable.request_tag(initiator_nonce);
+    if !bool::from(expected.ct_eq(tag)) {
+        return Err(LpError::InvalidResumptionTag);
+    }
+
+    let mut responder_nonce = [0u8; RESUMPTION_NONCE_SIZE];
+    rng.fill_bytes(&mut responder_nonce);
+
+    let mut response = Vec::with_capacity(RESUMPTION_RESPONSE_SIZE);
+    response.extend_from_slice(&responder_nonce);
+    response.extend_from_slice(&resumable.response_tag(initiator_nonce, &responder_nonce));
+
+    debug!("accepting session resumption");
+    connection.write_all_and_flush(&response).await?;
+
+    resumable.resume(initiator_nonce, &responder_nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SessionsMock;
+    use crate::codec::{decrypt_data, encrypt_data};
+    use crate::packet::version;
+    use nym_kkt_ciphersuite::{IntoEnumIterator, KEM};
+    use nym_test_utils::helpers::u64_seeded_rng_09;
+    use nym_test_utils::mocks::async_read_write::MockIOStream;
+    use nym_test_utils::traits::{Leak, Timeboxed};
+    use tokio::task::JoinHandle;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn assert_channels_match(a: &mut LpTransportSession, b: &mut LpTransportSession) {
+        assert_eq!(a.session_identifier(), b.session_identifier());
+        let ct = encrypt_data(b"resumed", a.active_transport()).unwrap();
+        let pt = decrypt_data(&ct, b.active_transport()).unwrap();
+        assert_eq!(pt, b"resumed");
+    }
+
+    type ResumptionTask = JoinHandle<Result<LpTransportSession, LpError>>;
+
+    /// Run both sides of the resumption over a mock connection
+    fn spawn_resumption<F>(
+        initiator: ResumableSession,
+        take_resumable: F,
+        seed: u64,
+    ) -> (ResumptionTask, ResumptionTask)
+    where
+        F: FnOnce(&ResumptionHandle) -> Option<ResumableSession> + Send + 'static,
+    {
+        // leak the connections (JUST FOR THE PURPOSE OF THIS TEST!)
+        // so they'd get 'static lifetime
+        let conn_init = MockIOStream::default();
+        let conn_resp = conn_init.try_get_remote_handle();
+        let conn_init = conn_init.leak();
+        let conn_resp = conn_resp.leak();
+
+        let init_fut = tokio::spawn(async move {
+            let mut rng = u64_seeded_rng_09(seed);
+            resume_as_initiator(conn_init, initiator, &mut rng).await
+        });
+        let resp_fut = tokio::spawn(async move {
+            let mut rng = u64_seeded_rng_09(seed + 1);
+            let prefix = conn_resp.read_n_bytes(RESUMPTION_PREFIX_SIZE).await?;
+            resume_as_responder(conn_resp, prefix, take_resumable, &mut rng).await
+        });
+        (init_fut, resp_fut)
+    }
+
+    #[test]
+    fn both_parties_export_the_same_handle() {
+        for kem in KEM::iter() {
+            let sessions = SessionsMock::mock_post_handshake(kem);
+            let init = sessions.initiator.resumable(TTL).unwrap();
+            let resp = sessions.responder.resumable(TTL).unwrap();
+            assert_eq!(init.handle(), resp.handle());
+            assert_eq!(init.secret, resp.secret);
+        }
+    }
+
+    #[test]
+    fn resumption_prefix_is_only_recognised_by_the_responder() {
+        let sessions = SessionsMock::mock_post_handshake(KEM::default());
+        let resumable = sessions.initiator.resumable(TTL).unwrap();
+        let responder_key = resumable.binding.responder_ecdh_pk;
+        let nonce = [42u8; RESUMPTION_NONCE_SIZE];
+        let mask = resumption_mask(&nonce, &responder_key);
+
+        let mut prefix = nonce.to_vec();
+        prefix.extend_from_slice(MaskedByte::new(RESUMPTION_MARKER, &mask).as_slice());
+        assert!(is_resumption_request(&prefix, &responder_key));
+
+        // a KKT request carries the protocol version instead
+        let mut kkt_prefix = nonce.to_vec();
+        kkt_prefix.extend_from_slice(MaskedByte::new(version::CURRENT, &mask).as_slice());
+        assert!(!is_resumption_request(&kkt_prefix, &responder_key));
+
+        let other_key = DHPublicKey::from_bytes(&[7u8; 32]);
+        assert!(!is_resumption_request(&prefix, &other_key));
+    }
+
+    #[tokio::test]
+    async fn session_can_be_resumed_repeatedly() -> anyhow::Result<()> {
+        for kem in KEM::iter() {
+            let sessions = SessionsMock::mock_post_handshake(kem);
+            let original_id = *sessions.initiator.session_identifier();
+            let mut init_resumable = sessions.initiator.resumable(TTL)?;
+            let mut resp_resumable = sessions.responder.resumable(TTL)?;
+
+            for round in 0..3u64 {
+                let previous_handle = resp_resumable.handle();
+                let (init_fut, resp_fut) = spawn_resumption(
+                    init_resumable,
+                    move |handle| (handle == &previous_handle).then_some(resp_resumable),
+                    round * 2,
+                );
+                let mut initiator = init_fut.timeboxed().await???;
+                let mut responder = resp_fut.timeboxed().await???;
+
+                assert_ne!(initiator.session_identifier(), &original_id);
+                assert_eq!(initiator.receiver_index(), responder.receiver_index());
+                assert_channels_match(&mut initiator, &mut responder);
+
+                // each resumed session provides a fresh handle for the next resumption
+                init_resumable = initiator.resumable(TTL)?;
+                resp_resumable = responder.resumable(TTL)?;
+                assert_eq!(init_resumable.handle(), resp_resumable.handle());
+                assert_ne!(init_resumable.handle(), previous_handle);
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unknown_handle_is_rejected() -> anyhow::Result<()> {
+        let sessions = SessionsMock::mock_post_handshake(KEM::default());
+        let init_resumable = sessions.initiator.resumable(TTL)?;
+
+        let (init_fut, resp_fut) = spawn_resumption(init_resumable, |_| None, 1);
+        assert!(matches!(
+            resp_fut.timeboxed().await??,
+            Err(LpError::UnknownResumptionHandle)
+        ));
+
+        // the responder never replies (it closes the connection instead)
+        init_fut.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resumption_requires_knowledge_of_the_secret() -> anyhow::Result<()> {
+        let sessions = SessionsMock::mock_post_handshake(KEM::default());
+        let mut forged = sessions.initiator.resumable(TTL)?;
+        let resp_resumable = sessions.responder.resumable(TTL)?;
+        forged.secret = Zeroizing::new([0u8; 32]);
+
+        let (init_fut, resp_fut) = spawn_resumption(forged, |_| Some(resp_resumable), 1);
+        assert!(matches!(
+            resp_fut.timeboxed().await??,
+            Err(LpError::InvalidResumptionTag)
+        ));
+        init_fut.abort();
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@ use crate::psq::{
     ResponderData,
 };
 use crate::replay::validator::PacketCount;
+use crate::resumption::ResumableSession;
 use crate::transport::LpHandshakeChannel;
 use crate::{LpError, replay::ReceivingKeyCounterValidator};
 use libcrux_psq::handshake::types::{Authenticator, DHPublicKey};
@@ -23,6 +24,7 @@ use nym_kkt::keys::EncapsulationKey;
 use nym_kkt_ciphersuite::{KEM, KEMKeyDigests};
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 /// Represents inputs that drive the state machine transitions.
 #[allow(clippy::large_enum_variant)]
@@ -227,6 +229,20 @@ impl LpTransportSession {
         self.receiver_index
     }
 
+    /// Export the data required for resuming this session on a future connection
+    /// without repeating the KKT/PSQ handshake.
+    ///
+    /// Both parties derive identical resumption handles, so the responder should retain
+    /// the result to accept it later, while the initiator should retain it to present it.
+    pub fn resumable(&self, ttl: Duration) -> Result<ResumableSession, LpError> {
+        ResumableSession::export(
+            &self.psq_session,
+            self.session_binding.clone(),
+            self.protocol_version,
+            ttl,
+        )
+    }
+
     /// Returns the negotiated protocol version from the handshake.
     ///
     /// Set during `LpSession` creation after sending / receiving `ClientHelloData`
@@ -4,12 +4,15 @@
 //! Session management for the Lewes Protocol.
 //!
 //! This module implements session lifecycle management functionality, handling
-//! creation, retrieval, and storage of sessions.
+//! creation, retrieval, and storage of sessions, as well as of the handles
+//! allowing them to be resumed later.
 
 use crate::packet::{EncryptedLpPacket, LpFrame};
 use crate::peer_config::LpReceiverIndex;
+use crate::resumption::{ResumableSession, ResumptionHandle};
 use crate::{LpError, LpTransportSession};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 pub use crate::replay::validator::PacketCount;
 use crate::session::{LpAction, LpInput};
@@ -21,14 +24,76 @@ use crate::session::{LpAction, LpInput};
 pub struct SessionManager {
     /// Manages state machines directly, keyed by lp_id
     sessions: HashMap<LpReceiverIndex, LpTransportSession>,
+
+    /// Handles of sessions that can be resumed without a new KKT/PSQ handshake
+    resumptions: ResumptionStore,
+}
+
+/// Set of sessions that can be resumed, keyed by their single-use handles.
+///
+/// Handles are removed once they're used, revoked or after they have expired.
+#[derive(Debug, Default)]
+pub struct ResumptionStore {
+    handles: HashMap<ResumptionHandle, ResumableSession>,
+}
+
+impl ResumptionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    pub fn contains(&self, handle: &ResumptionHandle) -> bool {
+        self.handles.contains_key(handle)
+    }
+
+    /// Make the provided session available for resumption.
+    pub fn insert(&mut self, resumable: ResumableSession) {
+        self.handles.insert(resumable.handle(), resumable);
+    }
+
+    /// Remove the session associated with the handle and return it if it hasn't expired.
+    /// Regardless of the outcome, the handle can't be used again.
+    pub fn take(&mut self, handle: &ResumptionHandle) -> Option<ResumableSession> {
+        self.take_at(handle, Instant::now())
+    }
+
+    pub fn take_at(&mut self, handle: &ResumptionHandle, now: Instant) -> Option<ResumableSession> {
+        self.handles
+            .remove(handle)
+            .filter(|resumable| !resumable.is_expired(now))
+    }
+
+    /// Revoke the handle so that the associated session can no longer be resumed.
+    /// Returns whether the handle was known.
+    pub fn revoke(&mut self, handle: &ResumptionHandle) -> bool {
+        self.handles.remove(handle).is_some()
+    }
+
+    /// Remove all expired handles, returning the number of removed entries.
+    pub fn remove_expired(&mut self) -> usize {
+        self.remove_expired_at(Instant::now())
+    }
+
+    pub fn remove_expired_at(&mut self, now: Instant) -> usize {
+        let before = self.handles.len();
+        self.handles
+            .retain(|_, resumable| !resumable.is_expired(now));
+        before - self.handles.len()
+    }
 }
 
 impl SessionManager {
     /// Creates a new session manager with empty session storage.
     pub fn new() -> Self {
-        Self {
-            sessions: HashMap::new(),
-        }
+        Self::default()
     }
 
     pub fn process_input(
@@ -115,6 +180,38 @@ impl SessionManager {
 
         removed.is_some()
     }
+
+    /// Allow the specified session to be resumed on a later connection during the provided `ttl`.
+    /// Returns the handle that has to be presented by the remote.
+    pub fn allow_resumption(
+        &mut self,
+        lp_id: LpReceiverIndex,
+        ttl: Duration,
+    ) -> Result<ResumptionHandle, LpError> {
+        let resumable = self.with_session(lp_id, |sm| sm.resumable(ttl))??;
+        let handle = resumable.handle();
+        self.resumptions.insert(resumable);
+        Ok(handle)
+    }
+
+    /// Retrieve (and revoke) the resumable session associated with the handle, if it's still valid.
+    pub fn take_resumable(&mut self, handle: &ResumptionHandle) -> Option<ResumableSession> {
+        self.resumptions.take(handle)
+    }
+
+    /// Revoke the resumption handle. Returns whether the handle was known.
+    pub fn revoke_resumption(&mut self, handle: &ResumptionHandle) -> bool {
+        self.resumptions.revoke(handle)
+    }
+
+    /// Remove all expired resumption handles, returning the number of removed entries.
+    pub fn remove_expired_resumptions(&mut self) -> usize {
+        self.resumptions.remove_expired()
+    }
+
+    pub fn resumption_count(&self) -> usize {
+        self.resumptions.len()
+    }
 }
 
 #[cfg(test)]
@@ -177,6 +274,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resumption_handles_are_single_use() {
+        let mut manager = SessionManager::new();
+        let session = mock_session_for_test();
+        let id = manager.insert_session(session).unwrap();
+
+        let handle = manager
+            .allow_resumption(id, Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(manager.resumption_count(), 1);
+
+        assert!(manager.take_resumable(&handle).is_some());
+        assert!(manager.take_resumable(&handle).is_none());
+        assert_eq!(manager.resumption_count(), 0);
+
+        assert!(
+            manager
+                .allow_resumption(123, Duration::from_secs(60))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_resumption_handles_can_be_revoked() {
+        let mut manager = SessionManager::new();
+        let id = manager.insert_session(mock_session_for_test()).unwrap();
+
+        let handle = manager
+            .allow_resumption(id, Duration::from_secs(60))
+            .unwrap();
+        assert!(manager.revoke_resumption(&handle));
+        assert!(!manager.revoke_resumption(&handle));
+        assert!(manager.take_resumable(&handle).is_none());
+    }
+
+    #[test]
+    fn test_resumption_handles_expire() {
+        let mut store = ResumptionStore::new();
+        let ttl = Duration::from_secs(60);
+        let resumable = mock_session_for_test().resumable(ttl).unwrap();
+        let handle = resumable.handle();
+        let expiry = resumable.expires_at();
+
+        store.insert(resumable.clone());
+        assert_eq!(store.remove_expired_at(expiry - Duration::from_secs(1)), 0);
+        assert!(store.contains(&handle));
+        assert_eq!(store.remove_expired_at(expiry), 1);
+        assert!(store.is_empty());
+
+        // expired handles are never handed out
+        store.insert(resumable);
+        assert!(store.take_at(&handle, expiry).is_none());
+        assert!(store.is_empty());
+    }
+
     #[test]
     fn test_session_manager_create_session() {
         let mut manager = SessionManager::new();
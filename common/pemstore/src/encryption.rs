@@ -0,0 +1,463 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional encryption at rest of the stored keys.
+//!
+//! Encrypted keys are still stored as pem files, but their tag gets prefixed with
+//! [`ENCRYPTED_TAG_PREFIX`] and their contents are replaced with the json-encoded
+//! [`EncryptedKey`], i.e. the Argon2 parameters used for expanding the passphrase
+//! alongside the AES-256-GCM ciphertext of the original key bytes.
+//!
+//! Public keys are never encrypted.
+
+use crate::traits::{PemStorableKey, PemStorableKeyPair};
+use crate::{
+    ENCRYPTED_TAG_PREFIX, KeyPairPath, ZeroizingPem, load_key, parse_key, read_pem_file,
+    write_pem_file,
+};
+use nym_store_cipher::{Aes256Gcm, EncryptedData, KdfInfo, StoreCipher};
+use pem::Pem;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+/// Passphrase used for encrypting and decrypting the stored keys.
+#[derive(Clone)]
+pub struct KeyPassphrase(Zeroizing<Vec<u8>>);
+
+impl KeyPassphrase {
+    pub fn new(passphrase: impl Into<Vec<u8>>) -> Self {
+        KeyPassphrase(Zeroizing::new(passphrase.into()))
+    }
+
+    /// Read the passphrase from the provided file, ignoring any trailing newline.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut raw = Zeroizing::new(fs::read(path)?);
+        while matches!(raw.last(), Some(b'\n' | b'\r')) {
+            raw.pop();
+        }
+        Self::non_empty(raw.to_vec())
+    }
+
+    /// Interactively ask for the passphrase. If `confirm` is set, it has to be typed twice.
+    #[cfg(feature = "prompt")]
+    pub fn prompt(message: &str, confirm: bool) -> io::Result<Self> {
+        let mut prompt =
+            inquire::Password::new(message).with_display_mode(inquire::PasswordDisplayMode::Hidden);
+        if !confirm {
+            prompt = prompt.without_confirmation();
+        }
+        let passphrase = Zeroizing::new(prompt.prompt().map_err(io::Error::other)?);
+        Self::non_empty(passphrase.as_bytes().to_vec())
+    }
+
+    fn non_empty(passphrase: Vec<u8>) -> io::Result<Self> {
+        if passphrase.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the key passphrase must not be empty",
+            ));
+        }
+        Ok(Self::new(passphrase))
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for KeyPassphrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("KeyPassphrase(<redacted>)")
+    }
+}
+
+/// Contents of an encrypted key pem.
+#[derive(Serialize, Deserialize)]
+struct EncryptedKey {
+    kdf_info: KdfInfo,
+    data: EncryptedData,
+}
+
+fn encrypted_tag(tag: &str) -> String {
+    format!("{ENCRYPTED_TAG_PREFIX}{tag}")
+}
+
+fn should_encrypt(tag: &str) -> bool {
+    !tag.starts_with(ENCRYPTED_TAG_PREFIX) && !tag.contains("PUBLIC")
+}
+
+fn encrypt_pem(pem: &Pem, passphrase: &KeyPassphrase, kdf_info: KdfInfo) -> io::Result<Pem> {
+    let cipher = StoreCipher::<Aes256Gcm>::new(passphrase.as_bytes(), kdf_info.clone())
+        .map_err(io::Error::other)?;
+    let data = cipher
+        .encrypt_data_ref(&pem.contents)
+        .map_err(io::Error::other)?;
+    let contents = serde_json::to_vec(&EncryptedKey { kdf_info, data })?;
+
+    Ok(Pem {
+        tag: encrypted_tag(&pem.tag),
+        contents,
+    })
+}
+
+/// Decrypt the provided pem, or return `None` if it wasn't encrypted to begin with.
+fn decrypt_pem(pem: &Pem, passphrase: &KeyPassphrase) -> io::Result<Option<ZeroizingPem>> {
+    let Some(tag) = pem.tag.strip_prefix(ENCRYPTED_TAG_PREFIX) else {
+        return Ok(None);
+    };
+
+    let encrypted: EncryptedKey = serde_json::from_slice(&pem.contents)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let cipher = StoreCipher::<Aes256Gcm>::new(passphrase.as_bytes(), encrypted.kdf_info)
+        .map_err(io::Error::other)?;
+    let contents = cipher.decrypt_data(encrypted.data).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("failed to decrypt the key ('{tag}'): invalid passphrase?"),
+        )
+    })?;
+
+    Ok(Some(ZeroizingPem(Pem {
+        tag: tag.to_string(),
+        contents,
+    })))
+}
+
+/// Attempt to load the key from the provided path, decrypting it if needed.
+/// Keys that are not encrypted are loaded as they are, so that partially migrated
+/// directories keep working.
+pub fn load_key_with_passphrase<T, P>(path: P, passphrase: Option<&KeyPassphrase>) -> io::Result<T>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    let Some(passphrase) = passphrase else {
+        return load_key(path);
+    };
+
+    debug!(
+        "attempting to load (possibly encrypted) key with the following pem type: {}",
+        T::pem_type()
+    );
+    let key_pem = read_pem_file(path.as_ref())?;
+    match decrypt_pem(&key_pem, passphrase)? {
+        Some(decrypted) => parse_key(&decrypted),
+        None => {
+            if should_encrypt(&key_pem.tag) {
+                warn!(
+                    "the key at {} is not encrypted at rest. consider encrypting the key directory",
+                    path.as_ref().display()
+                );
+            }
+            parse_key(&key_pem)
+        }
+    }
+}
+
+/// Store the key at the provided path. If a passphrase is provided, private keys get encrypted.
+pub fn store_key_with_passphrase<T, P>(
+    key: &T,
+    path: P,
+    passphrase: Option<&KeyPassphrase>,
+) -> io::Result<()>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    match passphrase {
+        Some(passphrase) if should_encrypt(T::pem_type()) => {
+            let pem = ZeroizingPem(Pem {
+                tag: T::pem_type().to_string(),
+                contents: key.to_bytes(),
+            });
+            let kdf_info = KdfInfo::new_with_default_settings().map_err(io::Error::other)?;
+            let encrypted = encrypt_pem(&pem, passphrase, kdf_info)?;
+            write_pem_file(path, encrypted.contents, &encrypted.tag)
+        }
+        _ => crate::store_key(key, path),
+    }
+}
+
+pub fn load_keypair_with_passphrase<T>(
+    paths: &KeyPairPath,
+    passphrase: Option<&KeyPassphrase>,
+) -> io::Result<T>
+where
+    T: PemStorableKeyPair,
+{
+    let private: T::PrivatePemKey = load_key_with_passphrase(&paths.private_key_path, passphrase)?;
+    let public: T::PublicPemKey = load_key_with_passphrase(&paths.public_key_path, passphrase)?;
+    Ok(T::from_keys(private, public))
+}
+
+pub fn store_keypair_with_passphrase<T>(
+    keypair: &T,
+    paths: &KeyPairPath,
+    passphrase: Option<&KeyPassphrase>,
+) -> io::Result<()>
+where
+    T: PemStorableKeyPair,
+{
+    store_key_with_passphrase(keypair.public_key(), &paths.public_key_path, passphrase)?;
+    store_key_with_passphrase(keypair.private_key(), &paths.private_key_path, passphrase)
+}
+
+/// Write the pem next to its destination first, so an interruption can't leave a truncated key behind.
+fn replace_pem_file(path: &Path, pem: &Pem) -> io::Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    write_pem_file(&tmp_path, pem.contents.clone(), &pem.tag)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Read the file as a pem, if it looks like one.
+fn read_if_pem(path: &Path) -> io::Result<Option<ZeroizingPem>> {
+    const PEM_HEADER: &[u8] = b"-----BEGIN ";
+
+    let mut header = [0u8; PEM_HEADER.len()];
+    let mut file = fs::File::open(path)?;
+    if file.read_exact(&mut header).is_err() || header != PEM_HEADER {
+        return Ok(None);
+    }
+    read_pem_file(path).map(Some)
+}
+
+fn encrypt_key_file_with_kdf<F>(
+    path: &Path,
+    passphrase: &KeyPassphrase,
+    new_kdf: F,
+) -> io::Result<bool>
+where
+    F: Fn() -> io::Result<KdfInfo>,
+{
+    let Some(pem) = read_if_pem(path)? else {
+        return Ok(false);
+    };
+    if !should_encrypt(&pem.tag) {
+        return Ok(false);
+    }
+    let encrypted = encrypt_pem(&pem, passphrase, new_kdf()?)?;
+    replace_pem_file(path, &encrypted)?;
+    Ok(true)
+}
+
+fn default_kdf() -> io::Result<KdfInfo> {
+    KdfInfo::new_with_default_settings().map_err(io::Error::other)
+}
+
+/// Encrypt the private key stored at the provided path.
+/// Returns whether the file got modified, i.e. `false` for public keys, already encrypted keys
+/// and files that are not pem encoded.
+pub fn encrypt_key_file<P: AsRef<Path>>(path: P, passphrase: &KeyPassphrase) -> io::Result<bool> {
+    encrypt_key_file_with_kdf(path.as_ref(), passphrase, default_kdf)
+}
+
+/// Decrypt the key stored at the provided path.
+/// Returns whether the file got modified, i.e. `false` if it wasn't encrypted.
+pub fn decrypt_key_file<P: AsRef<Path>>(path: P, passphrase: &KeyPassphrase) -> io::Result<bool> {
+    let path = path.as_ref();
+    let Some(pem) = read_if_pem(path)? else {
+        return Ok(false);
+    };
+    let Some(decrypted) = decrypt_pem(&pem, passphrase)? else {
+        return Ok(false);
+    };
+    replace_pem_file(path, &decrypted)?;
+    Ok(true)
+}
+
+fn directory_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn encrypt_key_directory_with_kdf<F>(
+    dir: &Path,
+    passphrase: &KeyPassphrase,
+    new_kdf: F,
+) -> io::Result<Vec<PathBuf>>
+where
+    F: Fn() -> io::Result<KdfInfo>,
+{
+    let mut encrypted = Vec::new();
+    for file in directory_files(dir)? {
+        if encrypt_key_file_with_kdf(&file, passphrase, &new_kdf)? {
+            info!("encrypted {}", file.display());
+            encrypted.push(file);
+        }
+    }
+    Ok(encrypted)
+}
+
+/// Encrypt all private keys stored (directly) within the provided directory.
+/// Returns paths to all files that got encrypted.
+pub fn encrypt_key_directory<P: AsRef<Path>>(
+    dir: P,
+    passphrase: &KeyPassphrase,
+) -> io::Result<Vec<PathBuf>> {
+    encrypt_key_directory_with_kdf(dir.as_ref(), passphrase, default_kdf)
+}
+
+/// Decrypt all keys stored (directly) within the provided directory.
+/// All keys are decrypted before any file is touched, so that a wrong passphrase
+/// leaves the directory as it was.
+/// Returns paths to all files that got decrypted.
+pub fn decrypt_key_directory<P: AsRef<Path>>(
+    dir: P,
+    passphrase: &KeyPassphrase,
+) -> io::Result<Vec<PathBuf>> {
+    let mut decrypted = Vec::new();
+    for file in directory_files(dir.as_ref())? {
+        let Some(pem) = read_if_pem(&file)? else {
+            continue;
+        };
+        if let Some(plaintext) = decrypt_pem(&pem, passphrase)? {
+            decrypted.push((file, plaintext));
+        }
+    }
+
+    let mut paths = Vec::with_capacity(decrypted.len());
+    for (file, plaintext) in decrypted {
+        replace_pem_file(&file, &plaintext)?;
+        info!("decrypted {}", file.display());
+        paths.push(file);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_store_cipher::{Algorithm, Params, Version};
+
+    struct DummyKey(Vec<u8>);
+
+    impl PemStorableKey for DummyKey {
+        type Error = io::Error;
+
+        fn pem_type() -> &'static str {
+            "DUMMY PRIVATE KEY"
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.0.clone()
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+            Ok(DummyKey(bytes.to_vec()))
+        }
+    }
+
+    struct DummyPublicKey(Vec<u8>);
+
+    impl PemStorableKey for DummyPublicKey {
+        type Error = io::Error;
+
+        fn pem_type() -> &'static str {
+            "DUMMY PUBLIC KEY"
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.0.clone()
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+            Ok(DummyPublicKey(bytes.to_vec()))
+        }
+    }
+
+    // the default argon2 parameters are deliberately expensive
+    fn cheap_kdf() -> io::Result<KdfInfo> {
+        Ok(KdfInfo::Argon2 {
+            params: Params::new(8, 1, 1, None).unwrap(),
+            algorithm: Algorithm::default(),
+            version: Version::default(),
+            kdf_salt: KdfInfo::random_salt().unwrap(),
+        })
+    }
+
+    fn encrypted_tag_of(path: &Path) -> String {
+        read_pem_file(path).unwrap().tag.clone()
+    }
+
+    #[test]
+    fn directory_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let private_path = dir.path().join("private");
+        let public_path = dir.path().join("public.pem");
+        let other_path = dir.path().join("description.toml");
+
+        crate::store_key(&DummyKey(vec![1, 2, 3]), &private_path).unwrap();
+        crate::store_key(&DummyPublicKey(vec![4, 5, 6]), &public_path).unwrap();
+        fs::write(&other_path, "moniker = 'foo'").unwrap();
+
+        let passphrase = KeyPassphrase::new("hunter2");
+        let encrypted = encrypt_key_directory_with_kdf(dir.path(), &passphrase, cheap_kdf).unwrap();
+        assert_eq!(encrypted, vec![private_path.clone()]);
+        assert_eq!(
+            encrypted_tag_of(&private_path),
+            "ENCRYPTED DUMMY PRIVATE KEY"
+        );
+        assert_eq!(encrypted_tag_of(&public_path), "DUMMY PUBLIC KEY");
+
+        // running it again is a no-op
+        assert!(
+            encrypt_key_directory_with_kdf(dir.path(), &passphrase, cheap_kdf)
+                .unwrap()
+                .is_empty()
+        );
+
+        // can't be loaded without the passphrase...
+        let err = load_key::<DummyKey, _>(&private_path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        // ...nor with the wrong one
+        let wrong = KeyPassphrase::new("hunter3");
+        assert!(load_key_with_passphrase::<DummyKey, _>(&private_path, Some(&wrong)).is_err());
+        assert!(decrypt_key_directory(dir.path(), &wrong).is_err());
+        assert_eq!(
+            encrypted_tag_of(&private_path),
+            "ENCRYPTED DUMMY PRIVATE KEY"
+        );
+
+        let key: DummyKey = load_key_with_passphrase(&private_path, Some(&passphrase)).unwrap();
+        assert_eq!(key.0, vec![1, 2, 3]);
+        let key: DummyPublicKey =
+            load_key_with_passphrase(&public_path, Some(&passphrase)).unwrap();
+        assert_eq!(key.0, vec![4, 5, 6]);
+
+        let decrypted = decrypt_key_directory(dir.path(), &passphrase).unwrap();
+        assert_eq!(decrypted, vec![private_path.clone()]);
+        let key: DummyKey = load_key(&private_path).unwrap();
+        assert_eq!(key.0, vec![1, 2, 3]);
+        assert_eq!(fs::read_to_string(&other_path).unwrap(), "moniker = 'foo'");
+    }
+
+    #[test]
+    fn passphrase_file_ignores_trailing_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("passphrase");
+
+        fs::write(&path, "hunter2\n").unwrap();
+        assert_eq!(
+            KeyPassphrase::from_file(&path).unwrap().as_bytes(),
+            b"hunter2"
+        );
+
+        fs::write(&path, "\n").unwrap();
+        assert!(KeyPassphrase::from_file(&path).is_err());
+    }
+}
@@ -10,9 +10,21 @@ use std::path::{Path, PathBuf};
 use tracing::debug;
 use zeroize::{Zeroize, Zeroizing};
 
+#[cfg(feature = "encryption")]
+pub mod encryption;
 pub mod traits;
 
-struct ZeroizingPem(Pem);
+#[cfg(feature = "encryption")]
+pub use encryption::{
+    KeyPassphrase, decrypt_key_directory, decrypt_key_file, encrypt_key_directory,
+    encrypt_key_file, load_key_with_passphrase, load_keypair_with_passphrase,
+    store_key_with_passphrase, store_keypair_with_passphrase,
+};
+
+/// Prefix of the pem tag of keys that got encrypted at rest, e.g. `ENCRYPTED ED25519 PRIVATE KEY`.
+pub const ENCRYPTED_TAG_PREFIX: &str = "ENCRYPTED ";
+
+pub(crate) struct ZeroizingPem(pub(crate) Pem);
 
 impl Zeroize for ZeroizingPem {
     fn zeroize(&mut self) {
@@ -75,11 +87,27 @@ where
         T::pem_type()
     );
     let key_pem = read_pem_file(path)?;
+    parse_key(&key_pem)
+}
+
+pub(crate) fn parse_key<T>(key_pem: &Pem) -> io::Result<T>
+where
+    T: PemStorableKey,
+{
+    if key_pem.tag.starts_with(ENCRYPTED_TAG_PREFIX) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "the key ('{}') is encrypted, but no passphrase was provided",
+                key_pem.tag
+            ),
+        ));
+    }
 
     if T::pem_type() != key_pem.tag {
         return Err(io::Error::other(format!(
             "unexpected key pem tag. Got '{}', expected: '{}'",
-            key_pem.0.tag,
+            key_pem.tag,
             T::pem_type()
         )));
     }
@@ -100,14 +128,18 @@ where
     write_pem_file(path, key.to_bytes(), T::pem_type())
 }
 
-fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<ZeroizingPem> {
+pub(crate) fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<ZeroizingPem> {
     let mut pem_bytes = File::open(filepath)?;
     let mut buf = Zeroizing::new(Vec::new());
     pem_bytes.read_to_end(&mut buf)?;
     pem::parse(&buf).map(ZeroizingPem).map_err(io::Error::other)
 }
 
-fn write_pem_file<P: AsRef<Path>>(filepath: P, mut data: Vec<u8>, tag: &str) -> io::Result<()> {
+pub(crate) fn write_pem_file<P: AsRef<Path>>(
+    filepath: P,
+    mut data: Vec<u8>,
+    tag: &str,
+) -> io::Result<()> {
     // ensure the whole directory structure exists
     // don't use nested if else due to contracts still being on 2021 and this code being pulled in indirectly in tests
     #[allow(clippy::collapsible_if)]
@@ -19,7 +19,8 @@ mod tests {
     use nym_node::node::GatewayStorage;
     use nym_node::node::lp::control::ingress::client_handler::LpClientConnectionHandler;
     use nym_node::node::lp::error::LpHandlerError;
-    use nym_node::node::lp::state::ActiveLpSessions;
+    use nym_node::node::lp::rate_limit::LpRateLimiter;
+    use nym_node::node::lp::state::{ActiveLpSessions, ResumableLpSessions};
     use nym_node::node::lp::{SharedLpClientControlState, SharedLpState};
     use nym_node::wireguard::{PeerManager, PeerRegistrator};
     use nym_registration_client::{LpClientError, LpRegistrationClient};
@@ -230,6 +231,8 @@ mod tests {
                 local_lp_peer: base.peer.clone(),
 
                 forward_semaphore,
+                rate_limiter: LpRateLimiter::new(lp_config.rate_limiting),
+                resumptions: ResumableLpSessions::new(),
 
                 // handles for dealing with new peers
                 peer_registrator: Some(peer_registrator),
@@ -241,9 +241,26 @@ pub struct LewesProtocolDetailsDataV1 {
     /// for their computation.
     /// note: digests are hex encoded
     pub kem_keys: BTreeMap<LPKEM, BTreeMap<LPHashFunction, String>>,
+
+    /// Versions of the Lewes Protocol supported by this node.
+    /// Nodes that predate protocol negotiation do not advertise it and only support version 1.
+    // note: the field is omitted when empty so that signatures of legacy nodes remain valid
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supported_versions: Vec<u8>,
 }
 
 impl LewesProtocolDetailsDataV1 {
+    pub const LEGACY_VERSION: u8 = 1;
+
+    /// Protocol versions supported by the node, accounting for nodes that predate negotiation
+    pub fn supported_versions(&self) -> Vec<u8> {
+        if self.supported_versions.is_empty() {
+            vec![Self::LEGACY_VERSION]
+        } else {
+            self.supported_versions.clone()
+        }
+    }
+
     fn decode_digests(
         digests: &BTreeMap<LPHashFunction, String>,
     ) -> Result<BTreeMap<HashFunction, Vec<u8>>, MalformedLPData> {
@@ -485,6 +502,7 @@ impl From<SignedData<nym_node_requests::api::v1::lewes_protocol::models::LewesPr
                     .into_iter()
                     .map(|(kem, digests)| (kem.into(), translate_digests(digests)))
                     .collect(),
+                supported_versions: value.data.supported_versions,
             },
         }
     }
@@ -620,12 +638,24 @@ mod tests {
             data_port: 345,
             x25519: x25519_key,
             kem_keys: dummy_kems,
+            supported_versions: vec![1, 2],
         };
+
+        // nodes that predate version negotiation do not advertise their versions
+        let mut legacy_lp = dummy_lp.clone();
+        legacy_lp.supported_versions = Vec::new();
+
         let dummy_signed_lp = SignedLewesProtocol::new(dummy_lp, &signing_key).unwrap();
         // sanity check
         assert!(dummy_signed_lp.verify(&verification_key));
 
         let converted = LewesProtocolDetailsV1::from(dummy_signed_lp);
         assert!(converted.verify(&verification_key));
+        assert_eq!(converted.content.supported_versions(), vec![1, 2]);
+
+        let legacy_signed_lp = SignedLewesProtocol::new(legacy_lp, &signing_key).unwrap();
+        let converted = LewesProtocolDetailsV1::from(legacy_signed_lp);
+        assert!(converted.verify(&verification_key));
+        assert_eq!(converted.content.supported_versions(), vec![1]);
     }
 }
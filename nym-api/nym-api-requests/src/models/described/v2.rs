@@ -303,6 +303,7 @@ pub fn mock_nym_node_description(seed: u64) -> NymNodeDescriptionV2 {
         data_port: 345,
         x25519: (*x25519.public_key()).into(),
         kem_keys: dummy_kems,
+        supported_versions: vec![1],
     };
     let dummy_signed_lp =
         nym_node_requests::api::SignedLewesProtocol::new(dummy_lp, ed25519.private_key()).unwrap();
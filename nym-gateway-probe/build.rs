@@ -71,6 +71,7 @@ fn build_go() -> anyhow::Result<()> {
         // Include all Go source files in the package (except tests)
         .arg("lib.go")
         .arg("udp_forwarder.go")
+        .arg("benchmark.go")
         .spawn()?;
     let status = child.wait()?;
     if !status.success() {
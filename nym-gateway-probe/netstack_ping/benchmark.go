@@ -0,0 +1,265 @@
+/* SPDX-License-Identifier: MIT
+ *
+ * Copyright (C) 2026 Nym Technologies SA <contact@nymtech.net>. All Rights Reserved.
+ */
+
+package main
+
+// #include <stdlib.h>
+import "C"
+
+import (
+	"context"
+	"encoding/binary"
+	"encoding/json"
+	"fmt"
+	"io"
+	"log"
+	"net"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/amnezia-vpn/amneziawg-go/tun/netstack"
+)
+
+// Size of a single RTT probe frame: 8 bytes of sequence number followed by padding.
+const rttFrameSize = 16
+
+// BenchmarkRequestGo contains the tunnel details alongside the benchmark parameters.
+// The benchmark is run against a TCP echo (or sink) endpoint reachable through the tunnel.
+type BenchmarkRequestGo struct {
+	WgIp       string `json:"wg_ip"`
+	PrivateKey string `json:"private_key"`
+	PublicKey  string `json:"public_key"`
+	Endpoint   string `json:"endpoint"`
+	Dns        string `json:"dns"`
+	IpVersion  uint8  `json:"ip_version"`
+	AwgArgs    string `json:"awg_args"`
+
+	EchoEndpoint       string `json:"echo_endpoint"`
+	RttSamples         uint32 `json:"rtt_samples"`
+	RttIntervalMs      uint64 `json:"rtt_interval_ms"`
+	ThroughputDuration uint64 `json:"throughput_duration_sec"`
+	ChunkSize          uint32 `json:"chunk_size"`
+	TimeoutSec         uint64 `json:"timeout_sec"`
+}
+
+// BenchmarkResponse contains the raw measurements, aggregation happens on the rust side.
+type BenchmarkResponse struct {
+	RttSent          uint32    `json:"rtt_sent"`
+	RttMs            []float64 `json:"rtt_ms"`
+	BytesSent        uint64    `json:"bytes_sent"`
+	BytesReceived    uint64    `json:"bytes_received"`
+	UploadDurationMs uint64    `json:"upload_duration_ms"`
+	DownloadDuration uint64    `json:"download_duration_ms"`
+	Error            string    `json:"error"`
+}
+
+type BenchmarkSuccessResult = struct {
+	Response BenchmarkResponse `json:"response"`
+}
+
+//export wgBenchmark
+func wgBenchmark(cReq *C.char) *C.char {
+	reqStr := C.GoString(cReq)
+
+	var req BenchmarkRequestGo
+	err := json.Unmarshal([]byte(reqStr), &req)
+	if err != nil {
+		log.Printf("Failed to parse request: %s", err)
+		return jsonError(err)
+	}
+
+	response, err := benchmark(req)
+	if err != nil {
+		log.Printf("Failed to run benchmark: %s", err)
+		return jsonError(err)
+	}
+
+	bytes, serializeErr := json.Marshal(BenchmarkSuccessResult{
+		Response: response,
+	})
+	if serializeErr != nil {
+		return C.CString("{\"error\":\"" + serializeErr.Error() + "\"}")
+	}
+	return C.CString(string(bytes))
+}
+
+func benchmark(req BenchmarkRequestGo) (BenchmarkResponse, error) {
+	log.Printf("Benchmarking tunnel via %s against %s", req.Endpoint, req.EchoEndpoint)
+
+	dev, tnet, err := bringUpTunnel(req.WgIp, req.Dns, req.PrivateKey, req.PublicKey, req.Endpoint, req.AwgArgs, req.IpVersion)
+	if err != nil {
+		return BenchmarkResponse{}, err
+	}
+	defer dev.Close()
+
+	response := BenchmarkResponse{RttMs: []float64{}}
+	timeout := time.Duration(req.TimeoutSec) * time.Second
+
+	sent, rtts, err := measureRtt(tnet, req.EchoEndpoint, req.RttSamples, time.Duration(req.RttIntervalMs)*time.Millisecond, timeout)
+	response.RttSent = sent
+	response.RttMs = rtts
+	if err != nil {
+		log.Printf("RTT measurement failed: %v", err)
+		response.Error = err.Error()
+		return response, nil
+	}
+
+	throughput, err := measureThroughput(tnet, req.EchoEndpoint, time.Duration(req.ThroughputDuration)*time.Second, int(req.ChunkSize), timeout)
+	response.BytesSent = throughput.bytesSent
+	response.BytesReceived = throughput.bytesReceived
+	response.UploadDurationMs = uint64(throughput.uploadDuration.Milliseconds())
+	response.DownloadDuration = uint64(throughput.downloadDuration.Milliseconds())
+	if err != nil {
+		log.Printf("Throughput measurement failed: %v", err)
+		response.Error = err.Error()
+	}
+
+	return response, nil
+}
+
+func dialEcho(tnet *netstack.Net, endpoint string, timeout time.Duration) (net.Conn, error) {
+	ctx, cancel := context.WithTimeout(context.Background(), timeout)
+	defer cancel()
+	return tnet.DialContext(ctx, "tcp", endpoint)
+}
+
+// measureRtt sends sequence-numbered frames at a fixed interval and matches the echoed frames.
+// Frames that don't come back before the timeout are considered lost.
+func measureRtt(tnet *netstack.Net, endpoint string, samples uint32, interval time.Duration, timeout time.Duration) (uint32, []float64, error) {
+	conn, err := dialEcho(tnet, endpoint, timeout)
+	if err != nil {
+		return 0, []float64{}, fmt.Errorf("failed to connect to the echo endpoint: %w", err)
+	}
+	defer conn.Close()
+
+	sentAt := make([]time.Time, samples)
+	var mu sync.Mutex
+	rtts := []float64{}
+	done := make(chan struct{})
+
+	go func() {
+		defer close(done)
+		frame := make([]byte, rttFrameSize)
+		for received := uint32(0); received < samples; received++ {
+			if _, err := io.ReadFull(conn, frame); err != nil {
+				return
+			}
+			arrival := time.Now()
+			seq := binary.BigEndian.Uint64(frame)
+			if seq >= uint64(samples) {
+				return
+			}
+			mu.Lock()
+			rtts = append(rtts, float64(arrival.Sub(sentAt[seq]).Microseconds())/1000.0)
+			mu.Unlock()
+		}
+	}()
+
+	var sent uint32
+	for seq := uint32(0); seq < samples; seq++ {
+		frame := make([]byte, rttFrameSize)
+		binary.BigEndian.PutUint64(frame, uint64(seq))
+		mu.Lock()
+		sentAt[seq] = time.Now()
+		mu.Unlock()
+		if _, err := conn.Write(frame); err != nil {
+			break
+		}
+		sent++
+		time.Sleep(interval)
+	}
+
+	select {
+	case <-done:
+	case <-time.After(timeout):
+		// unblock the reader, anything still outstanding is lost
+		conn.Close()
+		<-done
+	}
+
+	mu.Lock()
+	defer mu.Unlock()
+	return sent, rtts, nil
+}
+
+type throughputResult struct {
+	bytesSent        uint64
+	bytesReceived    uint64
+	uploadDuration   time.Duration
+	downloadDuration time.Duration
+}
+
+// measureThroughput writes data for the given duration while concurrently counting
+// the bytes echoed back. Once done writing, it waits for the echo to catch up,
+// until nothing arrived for the timeout.
+func measureThroughput(tnet *netstack.Net, endpoint string, duration time.Duration, chunkSize int, timeout time.Duration) (throughputResult, error) {
+	result := throughputResult{}
+
+	conn, err := dialEcho(tnet, endpoint, timeout)
+	if err != nil {
+		return result, fmt.Errorf("failed to connect to the echo endpoint: %w", err)
+	}
+	defer conn.Close()
+
+	var received atomic.Uint64
+	var lastReceived atomic.Int64
+	start := time.Now()
+	done := make(chan struct{})
+
+	go func() {
+		defer close(done)
+		buf := make([]byte, 64*1024)
+		for {
+			n, err := conn.Read(buf)
+			if n > 0 {
+				received.Add(uint64(n))
+				lastReceived.Store(int64(time.Since(start)))
+			}
+			if err != nil {
+				return
+			}
+		}
+	}()
+
+	chunk := make([]byte, chunkSize)
+	for time.Since(start) < duration {
+		n, err := conn.Write(chunk)
+		result.bytesSent += uint64(n)
+		if err != nil {
+			break
+		}
+	}
+	result.uploadDuration = time.Since(start)
+
+	// let the endpoint know we're done, echo servers usually close their side once they caught up
+	if closer, ok := conn.(interface{ CloseWrite() error }); ok {
+		_ = closer.CloseWrite()
+	}
+
+	// wait for the echo to catch up, for as long as data keeps coming in
+	lastProgress := time.Now()
+	lastCount := received.Load()
+waiting:
+	for received.Load() < result.bytesSent {
+		select {
+		case <-done:
+			break waiting
+		case <-time.After(50 * time.Millisecond):
+		}
+		if count := received.Load(); count != lastCount {
+			lastCount = count
+			lastProgress = time.Now()
+		} else if time.Since(lastProgress) >= timeout {
+			break
+		}
+	}
+	conn.Close()
+	<-done
+
+	result.bytesReceived = received.Load()
+	result.downloadDuration = time.Duration(lastReceived.Load())
+	return result, nil
+}
@@ -411,56 +411,13 @@ func ping(req NetstackRequestGo) (NetstackResponse, error) {
 	fmt.Printf("WireGuard IP: %s\n", req.WgIp)
 	fmt.Printf("IP version: %d\n", req.IpVersion)
 
-	tun, tnet, err := netstack.CreateNetTUN(
-		[]netip.Addr{netip.MustParseAddr(req.WgIp)},
-		[]netip.Addr{netip.MustParseAddr(req.Dns)},
-		1280)
-
+	_, tnet, err := bringUpTunnel(req.WgIp, req.Dns, req.PrivateKey, req.PublicKey, req.Endpoint, req.AwgArgs, req.IpVersion)
 	if err != nil {
 		return NetstackResponse{}, err
 	}
-	dev := device.NewDevice(tun, conn.NewDefaultBind(), device.NewLogger(device.LogLevelError, ""))
-
-	var ipc strings.Builder
-
-	ipc.WriteString("private_key=")
-	ipc.WriteString(req.PrivateKey)
-	if req.AwgArgs != "" {
-		awg := strings.ReplaceAll(req.AwgArgs, "\\n", "\n")
-		ipc.WriteString(fmt.Sprintf("\n%s", awg))
-	}
-	ipc.WriteString("\npublic_key=")
-	ipc.WriteString(req.PublicKey)
-	ipc.WriteString("\nendpoint=")
-	ipc.WriteString(req.Endpoint)
-	if req.IpVersion == 4 {
-		ipc.WriteString("\nallowed_ip=0.0.0.0/0\n")
-	} else {
-		ipc.WriteString("\nallowed_ip=::/0\n")
-	}
 
 	response := NetstackResponse{false, false, 0, 0, 0, 0, false, "", 0, 0, 0, ""}
 
-	err = dev.IpcSet(ipc.String())
-	if err != nil {
-		return NetstackResponse{}, err
-	}
-
-	config, err := dev.IpcGet()
-	if err != nil {
-		return NetstackResponse{}, err
-	}
-
-	// do not print the config by default, because it contains the wg private key
-	if os.Getenv("SHOW_WG_CONFIG") == "true" {
-		log.Printf("%s", config)
-	}
-
-	err = dev.Up()
-	if err != nil {
-		return NetstackResponse{}, err
-	}
-
 	response.CanHandshake = true
 
 	// Skip metadata query if endpoint is empty (e.g., for IPv6 where the IPv4 metadata endpoint is not reachable)
@@ -569,6 +526,59 @@ func ping(req NetstackRequestGo) (NetstackResponse, error) {
 	return response, nil
 }
 
+// bringUpTunnel creates a netstack backed WireGuard device for the given peer and brings it up.
+func bringUpTunnel(wgIp string, dns string, privateKey string, publicKey string, endpoint string, awgArgs string, ipVersion uint8) (*device.Device, *netstack.Net, error) {
+	tun, tnet, err := netstack.CreateNetTUN(
+		[]netip.Addr{netip.MustParseAddr(wgIp)},
+		[]netip.Addr{netip.MustParseAddr(dns)},
+		1280)
+
+	if err != nil {
+		return nil, nil, err
+	}
+	dev := device.NewDevice(tun, conn.NewDefaultBind(), device.NewLogger(device.LogLevelError, ""))
+
+	var ipc strings.Builder
+
+	ipc.WriteString("private_key=")
+	ipc.WriteString(privateKey)
+	if awgArgs != "" {
+		awg := strings.ReplaceAll(awgArgs, "\\n", "\n")
+		ipc.WriteString(fmt.Sprintf("\n%s", awg))
+	}
+	ipc.WriteString("\npublic_key=")
+	ipc.WriteString(publicKey)
+	ipc.WriteString("\nendpoint=")
+	ipc.WriteString(endpoint)
+	if ipVersion == 4 {
+		ipc.WriteString("\nallowed_ip=0.0.0.0/0\n")
+	} else {
+		ipc.WriteString("\nallowed_ip=::/0\n")
+	}
+
+	err = dev.IpcSet(ipc.String())
+	if err != nil {
+		return nil, nil, err
+	}
+
+	config, err := dev.IpcGet()
+	if err != nil {
+		return nil, nil, err
+	}
+
+	// do not print the config by default, because it contains the wg private key
+	if os.Getenv("SHOW_WG_CONFIG") == "true" {
+		log.Printf("%s", config)
+	}
+
+	err = dev.Up()
+	if err != nil {
+		return nil, nil, err
+	}
+
+	return dev, tnet, nil
+}
+
 func sendPing(address string, seq uint8, sendTtimeoutSecs uint64, receiveTimoutSecs uint64, tnet *netstack.Net, ipVersion uint8) (time.Duration, error) {
 	maxPingRetries := 2
 	baseTimeout := receiveTimoutSecs
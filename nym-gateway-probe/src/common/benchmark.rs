@@ -0,0 +1,410 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Throughput and latency benchmarking against an echo (or sink) endpoint.
+//!
+//! The same measurements are performed through every tested path:
+//! - RTT: sequence-numbered frames are sent at a fixed interval and matched against their echo,
+//!   frames that never come back count towards packet loss
+//! - throughput: data is written for a fixed duration, on a separate connection,
+//!   while the bytes coming back are counted
+//!
+//! The mixnet exit path is measured here, over connections made through the network requester,
+//! while the WireGuard path is measured by netstack which reports the raw samples back.
+
+use crate::common::types::{PathBenchmark, RttStatistics};
+use crate::config::BenchmarkArgs;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{debug, info, warn};
+
+/// Size of a single RTT probe frame: 8 bytes of sequence number followed by padding.
+const RTT_FRAME_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BenchmarkParams {
+    pub(crate) rtt_samples: u32,
+    pub(crate) rtt_interval: Duration,
+    pub(crate) throughput_duration: Duration,
+    pub(crate) chunk_size: usize,
+    pub(crate) timeout: Duration,
+}
+
+impl From<&BenchmarkArgs> for BenchmarkParams {
+    fn from(args: &BenchmarkArgs) -> Self {
+        BenchmarkParams {
+            rtt_samples: args.benchmark_rtt_samples,
+            rtt_interval: Duration::from_millis(args.benchmark_rtt_interval_ms),
+            throughput_duration: Duration::from_secs(args.benchmark_duration_sec),
+            chunk_size: args.benchmark_chunk_size as usize,
+            timeout: Duration::from_secs(args.benchmark_timeout_sec),
+        }
+    }
+}
+
+/// Raw throughput measurement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ThroughputMeasurement {
+    pub(crate) bytes_sent: u64,
+    pub(crate) upload_duration: Duration,
+    pub(crate) bytes_received: u64,
+    pub(crate) download_duration: Duration,
+}
+
+/// Aggregate the raw measurements of a single path.
+pub(crate) fn summarise(
+    rtt_probes_sent: u32,
+    rtt_ms: &[f64],
+    throughput: ThroughputMeasurement,
+) -> PathBenchmark {
+    let rtt_probes_received = rtt_ms.len() as u32;
+    let packet_loss = if rtt_probes_sent == 0 {
+        0.0
+    } else {
+        1.0 - rtt_probes_received as f32 / rtt_probes_sent as f32
+    };
+
+    PathBenchmark {
+        rtt_probes_sent,
+        rtt_probes_received,
+        packet_loss,
+        rtt_ms: RttStatistics::from_samples(rtt_ms),
+        bytes_sent: throughput.bytes_sent,
+        upload_duration_ms: throughput.upload_duration.as_millis() as u64,
+        upload_bps: bits_per_second(throughput.bytes_sent, throughput.upload_duration),
+        bytes_received: throughput.bytes_received,
+        download_duration_ms: throughput.download_duration.as_millis() as u64,
+        download_bps: bits_per_second(throughput.bytes_received, throughput.download_duration),
+        error: None,
+    }
+}
+
+fn bits_per_second(bytes: u64, duration: Duration) -> u64 {
+    if duration.is_zero() {
+        return 0;
+    }
+    (bytes as f64 * 8.0 / duration.as_secs_f64()) as u64
+}
+
+/// Run both measurements, each over its own connection obtained from `connect`.
+pub(crate) async fn run_stream_benchmark<F, Fut, S>(
+    connect: F,
+    params: BenchmarkParams,
+) -> PathBenchmark
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<S>>,
+    S: AsyncRead + AsyncWrite,
+{
+    let connect_with_timeout = || async {
+        match tokio::time::timeout(params.timeout, connect()).await {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(err)) => Err(format!("failed to connect to the echo endpoint: {err}")),
+            Err(_) => Err("timed out while connecting to the echo endpoint".to_string()),
+        }
+    };
+
+    let rtt_stream = match connect_with_timeout().await {
+        Ok(stream) => stream,
+        Err(err) => return PathBenchmark::with_error(err),
+    };
+    let (rtt_sent, rtt_ms) = measure_rtt(
+        rtt_stream,
+        params.rtt_samples,
+        params.rtt_interval,
+        params.timeout,
+    )
+    .await;
+    info!("received {}/{rtt_sent} RTT probe echoes", rtt_ms.len());
+
+    let throughput = match connect_with_timeout().await {
+        Ok(stream) => {
+            measure_throughput(
+                stream,
+                params.throughput_duration,
+                params.chunk_size,
+                params.timeout,
+            )
+            .await
+        }
+        Err(err) => {
+            let mut benchmark = summarise(rtt_sent, &rtt_ms, Default::default());
+            benchmark.error = Some(err);
+            return benchmark;
+        }
+    };
+    info!(
+        "sent {} bytes and received {} bytes back",
+        throughput.bytes_sent, throughput.bytes_received
+    );
+
+    summarise(rtt_sent, &rtt_ms, throughput)
+}
+
+fn rtt_frame(seq: u64) -> [u8; RTT_FRAME_SIZE] {
+    let mut frame = [0u8; RTT_FRAME_SIZE];
+    frame[..8].copy_from_slice(&seq.to_be_bytes());
+    frame
+}
+
+/// Send `samples` sequence-numbered frames, `interval` apart, and match them against their echo.
+/// Returns the number of frames sent and the RTTs (in milliseconds) of the ones that came back.
+pub(crate) async fn measure_rtt<S>(
+    stream: S,
+    samples: u32,
+    interval: Duration,
+    timeout: Duration,
+) -> (u32, Vec<f64>)
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let sent_at = RefCell::new(Vec::with_capacity(samples as usize));
+    let mut rtts = Vec::with_capacity(samples as usize);
+
+    let send = async {
+        for seq in 0..samples {
+            sent_at.borrow_mut().push(Instant::now());
+            if let Err(err) = writer.write_all(&rtt_frame(seq as u64)).await {
+                warn!("failed to send RTT probe: {err}");
+                sent_at.borrow_mut().pop();
+                break;
+            }
+            tokio::time::sleep(interval).await;
+        }
+    };
+
+    let receive = async {
+        let mut frame = [0u8; RTT_FRAME_SIZE];
+        while rtts.len() < samples as usize {
+            if let Err(err) = reader.read_exact(&mut frame).await {
+                debug!("stopped receiving RTT probe echoes: {err}");
+                break;
+            }
+            let mut seq = [0u8; 8];
+            seq.copy_from_slice(&frame[..8]);
+            let seq = u64::from_be_bytes(seq) as usize;
+            match sent_at.borrow().get(seq) {
+                Some(sent) => rtts.push(sent.elapsed().as_secs_f64() * 1000.0),
+                None => {
+                    warn!("received an unexpected RTT probe echo");
+                    break;
+                }
+            }
+        }
+    };
+
+    // anything that isn't back once the last probe had `timeout` to return is considered lost
+    let receive_window = interval * samples + timeout;
+    let _ = tokio::join!(send, tokio::time::timeout(receive_window, receive));
+
+    let sent = sent_at.borrow().len() as u32;
+    (sent, rtts)
+}
+
+/// Write data for `duration` while counting the bytes that come back.
+/// Receiving stops once everything got echoed back, or nothing arrived for `idle_timeout`.
+pub(crate) async fn measure_throughput<S>(
+    stream: S,
+    duration: Duration,
+    chunk_size: usize,
+    idle_timeout: Duration,
+) -> ThroughputMeasurement
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let start = Instant::now();
+    let bytes_sent = Cell::new(0u64);
+    let done_sending = Cell::new(false);
+
+    let send = async {
+        let chunk = vec![0u8; chunk_size];
+        while start.elapsed() < duration {
+            if let Err(err) = writer.write_all(&chunk).await {
+                warn!("failed to send benchmark data: {err}");
+                break;
+            }
+            bytes_sent.set(bytes_sent.get() + chunk.len() as u64);
+        }
+        let upload_duration = start.elapsed();
+        done_sending.set(true);
+        // let the endpoint know we're done, echo servers usually close their side once they caught up
+        let _ = writer.shutdown().await;
+        upload_duration
+    };
+
+    let receive = async {
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut bytes_received = 0u64;
+        let mut last_received = Duration::ZERO;
+        while !done_sending.get() || bytes_received < bytes_sent.get() {
+            match tokio::time::timeout(idle_timeout, reader.read(&mut buf)).await {
+                Ok(Ok(0)) => break,
+                Ok(Ok(read)) => {
+                    bytes_received += read as u64;
+                    last_received = start.elapsed();
+                }
+                Ok(Err(err)) => {
+                    debug!("stopped receiving benchmark data: {err}");
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+        (bytes_received, last_received)
+    };
+
+    let (upload_duration, (bytes_received, download_duration)) = tokio::join!(send, receive);
+
+    ThroughputMeasurement {
+        bytes_sent: bytes_sent.get(),
+        upload_duration,
+        bytes_received,
+        download_duration,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::{TcpListener, TcpStream};
+
+    async fn echo_server() -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let (mut reader, mut writer) = socket.split();
+                    let _ = tokio::io::copy(&mut reader, &mut writer).await;
+                });
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn rtt_statistics_use_nearest_rank() {
+        let samples = (1..=100).rev().map(|i| i as f64).collect::<Vec<_>>();
+        let stats = RttStatistics::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 100.0);
+        assert_eq!(stats.p50, 50.0);
+        assert_eq!(stats.p90, 90.0);
+        assert_eq!(stats.p99, 99.0);
+        assert_eq!(stats.mean, 50.5);
+
+        assert!(RttStatistics::from_samples(&[]).is_none());
+        let single = RttStatistics::from_samples(&[4.2]).unwrap();
+        assert_eq!(single.p99, 4.2);
+    }
+
+    #[test]
+    fn summary_reports_loss_and_throughput() {
+        let summary = summarise(
+            4,
+            &[10.0, 20.0, 30.0],
+            ThroughputMeasurement {
+                bytes_sent: 1_000_000,
+                upload_duration: Duration::from_secs(2),
+                bytes_received: 500_000,
+                download_duration: Duration::from_secs(4),
+            },
+        );
+        assert_eq!(summary.rtt_probes_received, 3);
+        assert_eq!(summary.packet_loss, 0.25);
+        assert_eq!(summary.upload_bps, 4_000_000);
+        assert_eq!(summary.download_bps, 1_000_000);
+
+        let empty = summarise(0, &[], Default::default());
+        assert_eq!(empty.packet_loss, 0.0);
+        assert_eq!(empty.upload_bps, 0);
+        assert!(empty.rtt_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn benchmark_against_local_echo_server() {
+        let address = echo_server().await;
+        let params = BenchmarkParams {
+            rtt_samples: 5,
+            rtt_interval: Duration::from_millis(10),
+            throughput_duration: Duration::from_millis(200),
+            chunk_size: 1024,
+            timeout: Duration::from_secs(5),
+        };
+
+        let benchmark = run_stream_benchmark(
+            || async move { Ok(TcpStream::connect(address).await?) },
+            params,
+        )
+        .await;
+
+        assert!(benchmark.error.is_none());
+        assert_eq!(benchmark.rtt_probes_sent, 5);
+        assert_eq!(benchmark.rtt_probes_received, 5);
+        assert_eq!(benchmark.packet_loss, 0.0);
+        assert!(benchmark.rtt_ms.is_some());
+        assert!(benchmark.bytes_sent > 0);
+        assert_eq!(benchmark.bytes_received, benchmark.bytes_sent);
+        assert!(benchmark.upload_bps > 0);
+        assert!(benchmark.download_bps > 0);
+    }
+
+    #[tokio::test]
+    async fn sink_endpoint_reports_loss() {
+        // accepts connections but never writes anything back
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy(&mut socket, &mut tokio::io::sink()).await;
+                });
+            }
+        });
+
+        let stream = TcpStream::connect(address).await.unwrap();
+        let (sent, rtts) = measure_rtt(
+            stream,
+            3,
+            Duration::from_millis(10),
+            Duration::from_millis(200),
+        )
+        .await;
+        assert_eq!(sent, 3);
+        assert!(rtts.is_empty());
+
+        let stream = TcpStream::connect(address).await.unwrap();
+        let throughput = measure_throughput(
+            stream,
+            Duration::from_millis(100),
+            1024,
+            Duration::from_millis(200),
+        )
+        .await;
+        assert!(throughput.bytes_sent > 0);
+        assert_eq!(throughput.bytes_received, 0);
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_is_reported() {
+        let benchmark = run_stream_benchmark(
+            || async { Err::<TcpStream, _>(anyhow::anyhow!("nope")) },
+            BenchmarkParams {
+                rtt_samples: 1,
+                rtt_interval: Duration::from_millis(1),
+                throughput_duration: Duration::from_millis(1),
+                chunk_size: 1,
+                timeout: Duration::from_secs(1),
+            },
+        )
+        .await;
+        assert!(benchmark.error.unwrap().contains("nope"));
+        assert_eq!(benchmark.rtt_probes_sent, 0);
+    }
+}
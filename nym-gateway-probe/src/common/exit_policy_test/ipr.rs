@@ -0,0 +1,292 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::exit_policy_test::PlannedSample;
+use crate::common::icmp::create_input_message;
+use crate::types::ExitPolicyObservation;
+use anyhow::anyhow;
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use nym_ip_packet_client::current::response::{
+    ControlResponse, InfoResponseReply, IpPacketResponse, IpPacketResponseData,
+};
+use nym_ip_packet_client::lp_stream;
+use nym_ip_packet_requests::{IpPair, codec::MultiIpPacketCodec};
+use nym_sdk::mixnet::{MixnetClient, MixnetMessageSender, Recipient};
+use pnet_packet::ip::IpNextHeaderProtocols;
+use pnet_packet::ipv4::{Ipv4Packet, MutableIpv4Packet};
+use pnet_packet::ipv6::{Ipv6Packet, MutableIpv6Packet};
+use pnet_packet::tcp::{MutableTcpPacket, TcpFlags, TcpPacket};
+use pnet_packet::{MutablePacket, Packet};
+use rand::Rng;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio_util::codec::Decoder;
+use tracing::*;
+
+const IPV4_HEADER_LEN: usize = 20;
+const IPV6_HEADER_LEN: usize = 40;
+const TCP_HEADER_LEN: usize = 20;
+
+/// Attempt every sample through the IPR by sending a TCP SYN to it.
+///
+/// Any TCP segment (SYN-ACK or RST) coming back from the destination proves the exit forwarded
+/// our packet, while an `ExitPolicyFilterCheckFailed` info response means the IPR refused it.
+pub(crate) async fn attempt_destinations(
+    mixnet_client: &mut MixnetClient,
+    our_ips: IpPair,
+    stream_id: u64,
+    exit_router_address: Recipient,
+    samples: &[PlannedSample],
+    timeout: Duration,
+) -> anyhow::Result<Vec<ExitPolicyObservation>> {
+    let mut observations = vec![ExitPolicyObservation::NoResponse; samples.len()];
+
+    // use a distinct source port per sample so that the replies can be told apart
+    let base_port: u16 = rand::thread_rng().gen_range(40000..60000);
+    let source_ports = (0..samples.len())
+        .map(|i| {
+            u16::try_from(i)
+                .ok()
+                .and_then(|i| base_port.checked_add(i))
+                .ok_or_else(|| anyhow!("too many exit policy samples ({})", samples.len()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    for (sample, &source_port) in samples.iter().zip(&source_ports) {
+        let source = match sample.destination {
+            SocketAddr::V4(_) => IpAddr::V4(our_ips.ipv4),
+            SocketAddr::V6(_) => IpAddr::V6(our_ips.ipv6),
+        };
+        let syn = create_tcp_syn(source, source_port, sample.destination)?;
+        let bundled_packet = MultiIpPacketCodec::bundle_one_packet(syn);
+        // source ports are sequential, so this can't overflow
+        let sequence = source_port - base_port;
+        let mixnet_message =
+            create_input_message(exit_router_address, bundled_packet, stream_id, sequence)?;
+
+        debug!("sending TCP SYN to {} via the IPR", sample.destination);
+        mixnet_client.send(mixnet_message).await?;
+    }
+
+    let mut multi_ip_packet_decoder = MultiIpPacketCodec::new();
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+
+    while observations.contains(&ExitPolicyObservation::NoResponse) {
+        tokio::select! {
+            _ = &mut deadline => {
+                info!("Finished waiting for exit policy replies from the IPR");
+                break;
+            }
+            Some(reconstructed_message) = mixnet_client.next() => {
+                let payload = lp_stream::maybe_unwrap_lp_stream_payload_from_reconstructed(&reconstructed_message);
+                let response = match IpPacketResponse::from_bytes(payload) {
+                    Ok(response) => response,
+                    Err(err) => {
+                        warn!("Failed to parse mixnet message: {err}");
+                        continue;
+                    }
+                };
+
+                match response.data {
+                    IpPacketResponseData::Data(data_response) => {
+                        // IP packets are bundled together in a mixnet message
+                        let mut bytes = BytesMut::from(&*data_response.ip_packet);
+                        while let Ok(Some(packet)) = multi_ip_packet_decoder.decode(&mut bytes) {
+                            let Some((source, destination_port)) = parse_tcp_reply(&packet.into_bytes()) else {
+                                continue;
+                            };
+                            let matching = samples.iter().zip(&source_ports).position(|(sample, &port)| {
+                                sample.destination == source && port == destination_port
+                            });
+                            if let Some(i) = matching {
+                                debug!("received TCP reply from {source} via the IPR");
+                                observations[i] = ExitPolicyObservation::Reachable;
+                            }
+                        }
+                    }
+                    IpPacketResponseData::Control(control) => {
+                        let ControlResponse::Info(info) = *control else {
+                            continue;
+                        };
+                        let InfoResponseReply::ExitPolicyFilterCheckFailed { dst } = info.reply else {
+                            debug!("Received info response from the mixnet: {}", info.reply);
+                            continue;
+                        };
+                        let Ok(dst) = dst.parse::<SocketAddr>() else {
+                            warn!("IPR reported malformed filtered destination: {dst}");
+                            continue;
+                        };
+                        for (i, sample) in samples.iter().enumerate() {
+                            if sample.destination == dst {
+                                debug!("IPR refused to forward traffic to {dst}");
+                                observations[i] = ExitPolicyObservation::Rejected;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(observations)
+}
+
+pub(crate) fn create_tcp_syn(
+    source: IpAddr,
+    source_port: u16,
+    destination: SocketAddr,
+) -> anyhow::Result<Bytes> {
+    let sequence = rand::thread_rng().r#gen();
+
+    match (source, destination.ip()) {
+        (IpAddr::V4(source), IpAddr::V4(destination_ip)) => {
+            let mut buffer = vec![0u8; IPV4_HEADER_LEN + TCP_HEADER_LEN];
+            let mut ipv4_packet = MutableIpv4Packet::new(&mut buffer)
+                .ok_or_else(|| anyhow!("failed to create ipv4 packet"))?;
+            ipv4_packet.set_version(4);
+            ipv4_packet.set_header_length(5);
+            ipv4_packet.set_total_length((IPV4_HEADER_LEN + TCP_HEADER_LEN) as u16);
+            ipv4_packet.set_ttl(64);
+            ipv4_packet.set_next_level_protocol(IpNextHeaderProtocols::Tcp);
+            ipv4_packet.set_source(source);
+            ipv4_packet.set_destination(destination_ip);
+
+            let mut tcp_packet = MutableTcpPacket::new(ipv4_packet.payload_mut())
+                .ok_or_else(|| anyhow!("failed to create tcp packet"))?;
+            fill_syn(&mut tcp_packet, source_port, destination.port(), sequence);
+            let checksum = pnet_packet::tcp::ipv4_checksum(
+                &tcp_packet.to_immutable(),
+                &source,
+                &destination_ip,
+            );
+            tcp_packet.set_checksum(checksum);
+
+            let checksum = pnet_packet::ipv4::checksum(&ipv4_packet.to_immutable());
+            ipv4_packet.set_checksum(checksum);
+
+            Ok(buffer.into())
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination_ip)) => {
+            let mut buffer = vec![0u8; IPV6_HEADER_LEN + TCP_HEADER_LEN];
+            let mut ipv6_packet = MutableIpv6Packet::new(&mut buffer)
+                .ok_or_else(|| anyhow!("failed to create ipv6 packet"))?;
+            ipv6_packet.set_version(6);
+            ipv6_packet.set_payload_length(TCP_HEADER_LEN as u16);
+            ipv6_packet.set_hop_limit(64);
+            ipv6_packet.set_next_header(IpNextHeaderProtocols::Tcp);
+            ipv6_packet.set_source(source);
+            ipv6_packet.set_destination(destination_ip);
+
+            let mut tcp_packet = MutableTcpPacket::new(ipv6_packet.payload_mut())
+                .ok_or_else(|| anyhow!("failed to create tcp packet"))?;
+            fill_syn(&mut tcp_packet, source_port, destination.port(), sequence);
+            let checksum = pnet_packet::tcp::ipv6_checksum(
+                &tcp_packet.to_immutable(),
+                &source,
+                &destination_ip,
+            );
+            tcp_packet.set_checksum(checksum);
+
+            Ok(buffer.into())
+        }
+        _ => Err(anyhow!(
+            "mismatched address families between {source} and {destination}"
+        )),
+    }
+}
+
+fn fill_syn(tcp_packet: &mut MutableTcpPacket, source_port: u16, destination_port: u16, seq: u32) {
+    tcp_packet.set_source(source_port);
+    tcp_packet.set_destination(destination_port);
+    tcp_packet.set_sequence(seq);
+    tcp_packet.set_data_offset(5);
+    tcp_packet.set_flags(TcpFlags::SYN);
+    tcp_packet.set_window(64240);
+}
+
+/// Extract the source address and destination port of a TCP segment, if that's what the packet is.
+pub(crate) fn parse_tcp_reply(packet: &[u8]) -> Option<(SocketAddr, u16)> {
+    let version = packet.first()? >> 4;
+    let (source, tcp_payload) = match version {
+        4 => {
+            let ipv4_packet = Ipv4Packet::new(packet)?;
+            if ipv4_packet.get_next_level_protocol() != IpNextHeaderProtocols::Tcp {
+                return None;
+            }
+            (
+                IpAddr::V4(ipv4_packet.get_source()),
+                ipv4_packet.payload().to_vec(),
+            )
+        }
+        6 => {
+            let ipv6_packet = Ipv6Packet::new(packet)?;
+            if ipv6_packet.get_next_header() != IpNextHeaderProtocols::Tcp {
+                return None;
+            }
+            (
+                IpAddr::V6(ipv6_packet.get_source()),
+                ipv6_packet.payload().to_vec(),
+            )
+        }
+        _ => return None,
+    };
+
+    let tcp_packet = TcpPacket::new(&tcp_payload)?;
+    Some((
+        SocketAddr::new(source, tcp_packet.get_source()),
+        tcp_packet.get_destination(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syn_can_be_parsed_back() {
+        let source: IpAddr = "10.0.0.2".parse().unwrap();
+        let destination: SocketAddr = "1.1.1.1:443".parse().unwrap();
+        let syn = create_tcp_syn(source, 41234, destination).unwrap();
+
+        let ipv4_packet = Ipv4Packet::new(&syn).unwrap();
+        assert_eq!(
+            pnet_packet::ipv4::checksum(&ipv4_packet),
+            ipv4_packet.get_checksum()
+        );
+        let tcp_packet = TcpPacket::new(ipv4_packet.payload()).unwrap();
+        assert_eq!(tcp_packet.get_flags(), TcpFlags::SYN);
+        assert_eq!(tcp_packet.get_destination(), 443);
+
+        // from the point of view of a reply parser, the "source" is us
+        assert_eq!(
+            parse_tcp_reply(&syn),
+            Some((SocketAddr::new(source, 41234), 443))
+        );
+    }
+
+    #[test]
+    fn ipv6_syn_can_be_parsed_back() {
+        let source: IpAddr = "fc00::2".parse().unwrap();
+        let destination: SocketAddr = "[2606:4700:4700::1111]:80".parse().unwrap();
+        let syn = create_tcp_syn(source, 41235, destination).unwrap();
+        assert_eq!(syn.len(), IPV6_HEADER_LEN + TCP_HEADER_LEN);
+        assert_eq!(
+            parse_tcp_reply(&syn),
+            Some((SocketAddr::new(source, 41235), 80))
+        );
+    }
+
+    #[test]
+    fn mismatched_families_are_rejected() {
+        let source: IpAddr = "10.0.0.2".parse().unwrap();
+        assert!(create_tcp_syn(source, 41234, "[::1]:80".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn non_tcp_packets_are_ignored() {
+        assert!(parse_tcp_reply(&[]).is_none());
+        assert!(parse_tcp_reply(&[0x45, 0, 0]).is_none());
+    }
+}
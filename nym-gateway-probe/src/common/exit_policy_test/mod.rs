@@ -0,0 +1,266 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exit policy conformance testing.
+//!
+//! The probe retrieves the exit policy a gateway advertises, derives a set of destinations
+//! the policy allows and rejects, and then attempts them through the network requester
+//! and the IPR. Every attempt is compared against a direct (non-mixnet) attempt made by the probe
+//! itself, so that destinations which are simply down are not reported as misbehaving exits.
+
+use nym_bin_common::ip_check::is_global_ip;
+use nym_exit_policy::ExitPolicy;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::debug;
+
+pub(crate) mod ipr;
+pub(crate) mod network_requester;
+
+/// Payload written to every sampled destination. Any response (even an error page)
+/// proves the destination was reached, and echo servers used as local stand-ins reply as well.
+pub(crate) const PROBE_PAYLOAD: &[u8] = b"HEAD / HTTP/1.0\r\n\r\n";
+
+/// A destination we're going to attempt, alongside what the advertised policy says about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PlannedSample {
+    pub(crate) destination: SocketAddr,
+    pub(crate) expected_allowed: bool,
+}
+
+/// Derive the destinations to attempt from the reference targets and the advertised policy.
+///
+/// Apart from the targets themselves, for every distinct target host we consider the boundaries
+/// of the port ranges used by the policy rules, so that both allowed and rejected ports are found
+/// even if all the reference targets fall under the same rule.
+/// At most `max_per_class` allowed and `max_per_class` rejected samples are returned.
+pub(crate) fn plan_samples(
+    policy: &ExitPolicy,
+    targets: &[SocketAddr],
+    max_per_class: usize,
+    allow_local_targets: bool,
+) -> Vec<PlannedSample> {
+    let mut candidates = targets.to_vec();
+
+    let mut seen_hosts = HashSet::new();
+    for host in targets.iter().map(|target| target.ip()) {
+        if !seen_hosts.insert(host) {
+            continue;
+        }
+        for rule in policy.rules() {
+            let ports = rule.pattern().ports();
+            // portless and catch-all rules don't tell us anything interesting
+            if ports.start == 0 || ports.is_all() {
+                continue;
+            }
+            candidates.push(SocketAddr::new(host, ports.start));
+            if let Some(past_end) = ports.end.checked_add(1) {
+                candidates.push(SocketAddr::new(host, past_end));
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut allowed = Vec::new();
+    let mut rejected = Vec::new();
+    for destination in candidates {
+        if !seen.insert(destination) {
+            continue;
+        }
+        let expected_allowed = expected_verdict(policy, destination, allow_local_targets);
+        let class = if expected_allowed {
+            &mut allowed
+        } else {
+            &mut rejected
+        };
+        if class.len() < max_per_class {
+            class.push(PlannedSample {
+                destination,
+                expected_allowed,
+            })
+        }
+    }
+
+    allowed.into_iter().chain(rejected).collect()
+}
+
+/// Mirrors the filtering performed by the exits: non-global destinations are rejected
+/// (unless the exit is known to run with local IPs allowed) and anything not covered
+/// by the policy is rejected as well.
+pub(crate) fn expected_verdict(
+    policy: &ExitPolicy,
+    destination: SocketAddr,
+    allow_local_targets: bool,
+) -> bool {
+    if !allow_local_targets && !is_global_ip(&destination.ip()) {
+        return false;
+    }
+    policy.allows_sockaddr(&destination).unwrap_or(false)
+}
+
+/// Attempt the destination directly, without going through the mixnet,
+/// to establish whether it's reachable at all.
+pub(crate) async fn is_directly_reachable(destination: SocketAddr, timeout: Duration) -> bool {
+    let attempt = async {
+        let mut stream = TcpStream::connect(destination).await?;
+        exchange_probe_payload(&mut stream).await
+    };
+
+    match tokio::time::timeout(timeout, attempt).await {
+        Ok(Ok(responded)) => responded,
+        Ok(Err(err)) => {
+            debug!("{destination} is not directly reachable: {err}");
+            false
+        }
+        Err(_) => {
+            debug!("timed out while directly contacting {destination}");
+            false
+        }
+    }
+}
+
+/// Write the probe payload and check whether anything comes back.
+pub(crate) async fn exchange_probe_payload<S>(stream: &mut S) -> std::io::Result<bool>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(PROBE_PAYLOAD).await?;
+    stream.flush().await?;
+
+    let mut buf = [0u8; 64];
+    let read = stream.read(&mut buf).await?;
+    Ok(read > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_exit_policy::parse_exit_policy;
+
+    fn policy() -> ExitPolicy {
+        parse_exit_policy(
+            r#"
+ExitPolicy reject 1.2.3.0/24:*
+ExitPolicy accept *:53
+ExitPolicy accept *:80-81
+ExitPolicy accept *:443
+ExitPolicy reject *:*
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn verdict_follows_policy() {
+        let policy = policy();
+        assert!(expected_verdict(
+            &policy,
+            "1.1.1.1:443".parse().unwrap(),
+            false
+        ));
+        assert!(!expected_verdict(
+            &policy,
+            "1.1.1.1:25".parse().unwrap(),
+            false
+        ));
+        assert!(!expected_verdict(
+            &policy,
+            "1.2.3.4:443".parse().unwrap(),
+            false
+        ));
+    }
+
+    #[test]
+    fn local_targets_are_rejected_unless_explicitly_allowed() {
+        let policy = policy();
+        let local = "127.0.0.1:80".parse().unwrap();
+        assert!(!expected_verdict(&policy, local, false));
+        assert!(expected_verdict(&policy, local, true));
+    }
+
+    #[test]
+    fn samples_include_derived_ports() {
+        let policy = policy();
+        let targets = vec!["1.1.1.1:443".parse().unwrap()];
+        let samples = plan_samples(&policy, &targets, 10, false);
+
+        let allowed = samples
+            .iter()
+            .filter(|s| s.expected_allowed)
+            .map(|s| s.destination.port())
+            .collect::<Vec<_>>();
+        let rejected = samples
+            .iter()
+            .filter(|s| !s.expected_allowed)
+            .map(|s| s.destination.port())
+            .collect::<Vec<_>>();
+
+        assert_eq!(allowed, vec![443, 53, 80]);
+        assert_eq!(rejected, vec![54, 82, 444]);
+    }
+
+    #[test]
+    fn samples_are_bounded_per_class_and_deduplicated() {
+        let policy = policy();
+        let targets = vec![
+            "1.1.1.1:443".parse().unwrap(),
+            "1.1.1.1:443".parse().unwrap(),
+            "8.8.8.8:25".parse().unwrap(),
+        ];
+        let samples = plan_samples(&policy, &targets, 2, false);
+
+        assert_eq!(samples.len(), 4);
+        assert_eq!(samples.iter().filter(|s| s.expected_allowed).count(), 2);
+        assert_eq!(samples.iter().filter(|s| !s.expected_allowed).count(), 2);
+
+        let unique = samples
+            .iter()
+            .map(|s| s.destination)
+            .collect::<HashSet<_>>();
+        assert_eq!(unique.len(), samples.len());
+
+        // explicitly provided targets take precedence over derived ones
+        assert_eq!(samples[0].destination, targets[0]);
+        assert_eq!(samples[2].destination, targets[2]);
+    }
+
+    #[test]
+    fn local_stand_in_can_be_used() {
+        let policy = policy();
+        let targets = vec!["127.0.0.1:443".parse().unwrap()];
+        let samples = plan_samples(&policy, &targets, 1, true);
+        assert_eq!(
+            samples,
+            vec![
+                PlannedSample {
+                    destination: "127.0.0.1:443".parse().unwrap(),
+                    expected_allowed: true,
+                },
+                PlannedSample {
+                    destination: "127.0.0.1:54".parse().unwrap(),
+                    expected_allowed: false,
+                }
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn direct_reachability_uses_local_stand_in() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 64];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket.write_all(&buf[..n]).await.unwrap();
+        });
+
+        assert!(is_directly_reachable(address, Duration::from_secs(5)).await);
+
+        // nothing is listening there anymore
+        assert!(!is_directly_reachable(address, Duration::from_secs(1)).await);
+    }
+}
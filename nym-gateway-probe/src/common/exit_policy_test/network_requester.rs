@@ -0,0 +1,164 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::exit_policy_test::exchange_probe_payload;
+use crate::types::ExitPolicyObservation;
+use anyhow::{Context, bail};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::{debug, warn};
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_NO_AUTH: u8 = 0x00;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+const SOCKS5_REPLY_SUCCEEDED: u8 = 0x00;
+
+/// Attempt the destination through the local SOCKS5 client (and thus the network requester).
+pub(crate) async fn attempt_destination(
+    socks5_proxy: SocketAddr,
+    destination: SocketAddr,
+    timeout: Duration,
+) -> ExitPolicyObservation {
+    let attempt = async {
+        // failing to set up the proxied connection is a problem on our side,
+        // it doesn't tell us anything about the exit
+        let mut stream = match socks5_connect(socks5_proxy, destination).await {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!(
+                    "failed to request connection to {destination} from the socks5 client: {err}"
+                );
+                return ExitPolicyObservation::NotTested;
+            }
+        };
+
+        // the socks5 client acknowledges the request straight away, so the network requester
+        // refusing the connection (when its filter check fails) can only be observed as the
+        // connection getting closed
+        match exchange_probe_payload(&mut stream).await {
+            Ok(true) => ExitPolicyObservation::Reachable,
+            Ok(false) => ExitPolicyObservation::Closed,
+            Err(err) => {
+                debug!("connection to {destination} via the network requester failed: {err}");
+                ExitPolicyObservation::Closed
+            }
+        }
+    };
+
+    match tokio::time::timeout(timeout, attempt).await {
+        Ok(observation) => observation,
+        Err(_) => ExitPolicyObservation::NoResponse,
+    }
+}
+
+/// Perform a minimal, unauthenticated SOCKS5 CONNECT handshake.
+pub(crate) async fn socks5_connect(
+    socks5_proxy: SocketAddr,
+    destination: SocketAddr,
+) -> anyhow::Result<TcpStream> {
+    let mut stream = TcpStream::connect(socks5_proxy)
+        .await
+        .context("failed to connect to the local socks5 client")?;
+
+    stream
+        .write_all(&[SOCKS5_VERSION, 1, SOCKS5_NO_AUTH])
+        .await?;
+    let mut method = [0u8; 2];
+    stream.read_exact(&mut method).await?;
+    if method != [SOCKS5_VERSION, SOCKS5_NO_AUTH] {
+        bail!("socks5 client did not accept unauthenticated connection");
+    }
+
+    stream.write_all(&connect_request(destination)).await?;
+
+    // error replies only consist of the version and the reply code
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != SOCKS5_VERSION {
+        bail!("unexpected socks version {} in the reply", reply[0]);
+    }
+    if reply[1] != SOCKS5_REPLY_SUCCEEDED {
+        bail!("socks5 client refused the request with code {}", reply[1]);
+    }
+
+    // discard the reserved byte and the bound address
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).await?;
+    let address_len = match header[1] {
+        SOCKS5_ATYP_IPV4 => 4,
+        SOCKS5_ATYP_IPV6 => 16,
+        SOCKS5_ATYP_DOMAIN => stream.read_u8().await? as usize,
+        other => bail!("unknown socks5 address type {other}"),
+    };
+    let mut bound = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound).await?;
+
+    Ok(stream)
+}
+
+fn connect_request(destination: SocketAddr) -> Vec<u8> {
+    let mut request = vec![SOCKS5_VERSION, SOCKS5_CMD_CONNECT, 0x00];
+    match destination {
+        SocketAddr::V4(addr) => {
+            request.push(SOCKS5_ATYP_IPV4);
+            request.extend_from_slice(&addr.ip().octets());
+        }
+        SocketAddr::V6(addr) => {
+            request.push(SOCKS5_ATYP_IPV6);
+            request.extend_from_slice(&addr.ip().octets());
+        }
+    }
+    request.extend_from_slice(&destination.port().to_be_bytes());
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_request_encoding() {
+        assert_eq!(
+            connect_request("1.2.3.4:443".parse().unwrap()),
+            vec![5, 1, 0, 1, 1, 2, 3, 4, 1, 187]
+        );
+
+        let v6 = connect_request("[::1]:80".parse().unwrap());
+        assert_eq!(&v6[..4], &[5, 1, 0, 4]);
+        assert_eq!(v6.len(), 4 + 16 + 2);
+        assert_eq!(&v6[20..], &[0, 80]);
+    }
+
+    #[tokio::test]
+    async fn short_error_reply_is_not_awaited() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            socket.read_exact(&mut greeting).await.unwrap();
+            socket
+                .write_all(&[SOCKS5_VERSION, SOCKS5_NO_AUTH])
+                .await
+                .unwrap();
+            let mut request = [0u8; 10];
+            socket.read_exact(&mut request).await.unwrap();
+            // mimic the error reply of our socks5 client, and keep the connection open
+            socket.write_all(&[SOCKS5_VERSION, 0x08]).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let observation = attempt_destination(
+            proxy,
+            "1.2.3.4:443".parse().unwrap(),
+            Duration::from_secs(10),
+        )
+        .await;
+        assert_eq!(observation, ExitPolicyObservation::NotTested);
+    }
+}
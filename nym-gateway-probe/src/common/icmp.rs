@@ -81,7 +81,7 @@ pub async fn send_ping_v6(
     Ok(())
 }
 
-fn create_input_message(
+pub(crate) fn create_input_message(
     recipient: impl Into<Recipient>,
     bundled_packets: Bytes,
     stream_id: u64,
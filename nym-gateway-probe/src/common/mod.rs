@@ -5,8 +5,12 @@
 //!
 //! This module contains shared functionality used by multiple test modes:
 //! - WireGuard tunnel testing via netstack
+//! - Exit policy conformance sampling
+//! - Throughput and latency benchmarking
 
 pub(crate) mod bandwidth_helpers;
+pub(crate) mod benchmark;
+pub(crate) mod exit_policy_test;
 pub(crate) mod helpers;
 pub(crate) mod icmp;
 pub(crate) mod netstack;
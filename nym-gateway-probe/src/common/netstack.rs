@@ -3,7 +3,6 @@
 
 use crate::config::NetstackArgs;
 use anyhow::Context;
-use serde::Deserialize;
 use std::ffi::{CStr, CString};
 
 mod sys {
@@ -11,6 +10,7 @@ mod sys {
 
     unsafe extern "C" {
         pub unsafe fn wgPing(req: *const c_char) -> *const c_char;
+        pub unsafe fn wgBenchmark(req: *const c_char) -> *const c_char;
         pub unsafe fn wgFreePtr(ptr: *mut c_void);
     }
 }
@@ -182,14 +182,71 @@ pub enum NetstackResult {
     Error { error: String },
 }
 
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct NetstackBenchmarkRequestGo {
+    pub wg_ip: String,
+    pub private_key: String,
+    pub public_key: String,
+    pub endpoint: String,
+    pub dns: String,
+    pub ip_version: u8,
+    pub awg_args: String,
+    pub echo_endpoint: String,
+    pub rtt_samples: u32,
+    pub rtt_interval_ms: u64,
+    pub throughput_duration_sec: u64,
+    pub chunk_size: u32,
+    pub timeout_sec: u64,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct NetstackBenchmarkResponse {
+    pub rtt_sent: u32,
+    pub rtt_ms: Vec<f64>,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub upload_duration_ms: u64,
+    pub download_duration_ms: u64,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetstackBenchmarkResult {
+    Response(NetstackBenchmarkResponse),
+    Error { error: String },
+}
+
 pub fn ping(req: &NetstackRequestGo) -> anyhow::Result<NetstackResult> {
+    // SAFETY: wgPing takes a json encoded request and returns a json encoded response
+    unsafe { call_netstack(sys::wgPing, "wgPing", req) }
+}
+
+pub fn benchmark(req: &NetstackBenchmarkRequestGo) -> anyhow::Result<NetstackBenchmarkResult> {
+    // SAFETY: wgBenchmark takes a json encoded request and returns a json encoded response
+    unsafe { call_netstack(sys::wgBenchmark, "wgBenchmark", req) }
+}
+
+/// # Safety
+///
+/// `func` must accept a nul-terminated json string and return a nul-terminated string
+/// allocated by CGO (or null), which is released with `wgFreePtr`.
+unsafe fn call_netstack<Req, Res>(
+    func: unsafe extern "C" fn(*const std::ffi::c_char) -> *const std::ffi::c_char,
+    name: &str,
+    req: &Req,
+) -> anyhow::Result<Res>
+where
+    Req: serde::Serialize,
+    Res: serde::de::DeserializeOwned,
+{
     let req_json = serde_json::to_string_pretty(req)?;
     let req_json_cstr = CString::new(req_json)?;
 
     // SAFETY: safety guarantees are upheld by CGO
-    let response_str_ptr = unsafe { sys::wgPing(req_json_cstr.as_ptr()) };
+    let response_str_ptr = unsafe { func(req_json_cstr.as_ptr()) };
     if response_str_ptr.is_null() {
-        return Err(anyhow::anyhow!("wgPing() returned null"));
+        return Err(anyhow::anyhow!("{name}() returned null"));
     }
 
     // SAFETY: safety guarantees are upheld by CGO
@@ -197,7 +254,7 @@ pub fn ping(req: &NetstackRequestGo) -> anyhow::Result<NetstackResult> {
     let result = match response_cstr.to_str() {
         Ok(response_str) => {
             let mut de = serde_json::Deserializer::from_str(response_str);
-            let response = NetstackResult::deserialize(&mut de);
+            let response = Res::deserialize(&mut de);
 
             response.context("Failed to deserialize ffi response")
         }
@@ -16,6 +16,7 @@ use nym_lp::packet::version;
 use nym_lp::peer::{DHPublicKey, LpRemotePeer};
 use nym_network_defaults::DEFAULT_NYM_NODE_HTTP_PORT;
 use nym_node_requests::api::client::NymNodeApiClientExt;
+use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use nym_node_requests::api::v1::node::models::AuxiliaryDetails as NodeAuxiliaryDetails;
 use nym_sdk::mixnet::NodeIdentity;
 use nym_sdk::mixnet::Recipient;
@@ -140,17 +141,23 @@ impl DirectoryNode {
                     bail!("{identity} provided malformed lp data");
                 }
 
-                let version: semver::Version =
+                let node_version: semver::Version =
                     description.build_information.build_version.parse()?;
-                let Some(ciphersuite) = Ciphersuite::from_node_version(version) else {
+                let Some(ciphersuite) = Ciphersuite::from_node_version(node_version) else {
                     bail!("failed to identify valid ciphersuite for {identity}");
                 };
 
+                // 2. pick the highest protocol version supported by both sides
+                let Some(lp_version) = version::negotiate(&lp_data.content.supported_versions())
+                else {
+                    bail!("{identity} does not support any known LP protocol version");
+                };
+
                 Some(TestedNodeLpDetails {
                     address: SocketAddr::new(ip_address, lp_data.content.control_port),
                     expected_kem_key_hashes: lp_data.content.kem_keys()?,
                     x25519: lp_data.content.x25519,
-                    lp_version: version::CURRENT,
+                    lp_version,
                     ciphersuite,
                 })
             }
@@ -180,43 +187,17 @@ impl DirectoryNode {
 pub async fn query_gateway_by_ip(address: String) -> anyhow::Result<DirectoryNode> {
     info!("Querying gateway directly at address: {address}");
 
-    // Parse the address to check if it contains a port
-    let addresses_to_try = if address.contains(':') {
-        // Address already has port specified, use it directly
-        vec![format!("http://{address}"), format!("https://{address}")]
-    } else {
-        // No port specified, try multiple ports in order of likelihood
-        vec![
-            format!("http://{address}:{DEFAULT_NYM_NODE_HTTP_PORT}"), // Standard port 8080
-            format!("https://{address}"),                             // HTTPS proxy (443)
-            format!("http://{address}"),                              // HTTP proxy (80)
-        ]
-    };
-
     let user_agent: UserAgent = nym_bin_common::bin_info_local_vergen!().into();
     let mut last_error = None;
 
-    for address in addresses_to_try {
+    for address in node_api_urls_to_try(&address) {
         debug!("Trying to connect to gateway at: {address}");
 
         // Build client with timeout
-        let client = match nym_node_requests::api::Client::builder(address.clone()) {
-            Ok(builder) => match builder
-                .with_timeout(Duration::from_secs(5))
-                .no_hickory_dns()
-                .with_user_agent(user_agent.clone())
-                .build()
-            {
-                Ok(c) => c,
-                Err(e) => {
-                    warn!("Failed to build client for {}: {}", address, e);
-                    last_error = Some(e.into());
-                    continue;
-                }
-            },
+        let client = match build_node_api_client(&address, user_agent.clone()) {
+            Ok(c) => c,
             Err(e) => {
-                warn!("Failed to create client builder for {}: {}", address, e);
-                last_error = Some(e.into());
+                last_error = Some(e);
                 continue;
             }
         };
@@ -361,6 +342,70 @@ pub async fn query_gateway_by_ip(address: String) -> anyhow::Result<DirectoryNod
     Err(last_error.unwrap_or_else(|| anyhow!("Failed to connect to gateway at {}", address)))
 }
 
+/// Query the exit policy a gateway advertises on its `network_requester/exit_policy` route.
+///
+/// # Arguments
+/// * `address` - The address of the gateway (IP, IP:PORT, or HOST:PORT format)
+pub async fn query_exit_policy(address: String) -> anyhow::Result<UsedExitPolicy> {
+    info!("Querying exit policy of gateway at address: {address}");
+
+    let user_agent: UserAgent = nym_bin_common::bin_info_local_vergen!().into();
+    let mut last_error = None;
+
+    for address in node_api_urls_to_try(&address) {
+        debug!("Trying to retrieve exit policy from: {address}");
+
+        let client = match build_node_api_client(&address, user_agent.clone()) {
+            Ok(c) => c,
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        match client.get_exit_policy().await {
+            Ok(exit_policy) => return Ok(exit_policy),
+            Err(e) => {
+                warn!("Failed to retrieve exit policy from {}: {}", address, e);
+                last_error = Some(e.into());
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow!("Failed to retrieve exit policy from {}", address)))
+}
+
+fn node_api_urls_to_try(address: &str) -> Vec<String> {
+    // Parse the address to check if it contains a port
+    if address.contains(':') {
+        // Address already has port specified, use it directly
+        vec![format!("http://{address}"), format!("https://{address}")]
+    } else {
+        // No port specified, try multiple ports in order of likelihood
+        vec![
+            format!("http://{address}:{DEFAULT_NYM_NODE_HTTP_PORT}"), // Standard port 8080
+            format!("https://{address}"),                             // HTTPS proxy (443)
+            format!("http://{address}"),                              // HTTP proxy (80)
+        ]
+    }
+}
+
+fn build_node_api_client(
+    address: &str,
+    user_agent: UserAgent,
+) -> anyhow::Result<nym_node_requests::api::Client> {
+    // Build client with timeout
+    let builder = nym_node_requests::api::Client::builder(address.to_string())
+        .inspect_err(|e| warn!("Failed to create client builder for {}: {}", address, e))?;
+    let client = builder
+        .with_timeout(Duration::from_secs(5))
+        .no_hickory_dns()
+        .with_user_agent(user_agent)
+        .build()
+        .inspect_err(|e| warn!("Failed to build client for {}: {}", address, e))?;
+    Ok(client)
+}
+
 pub struct NymApiDirectory {
     // nodes: HashMap<NodeIdentity, DescribedNodeWithPerformance>,
     nodes: HashMap<NodeIdentity, DirectoryNode>,
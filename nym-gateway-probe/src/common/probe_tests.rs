@@ -2,14 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::common::helpers::mixnet_debug_config;
-use crate::common::nodes::TestedNodeLpDetails;
+use crate::common::nodes::{TestedNodeDetails, TestedNodeLpDetails, query_exit_policy};
 use crate::common::socks5_test::HttpsConnectivityTest;
 use crate::common::types::{
-    Entry, Exit, IpPingReplies, LpProbeResults, ProbeOutcome, Socks5ProbeResults, WgProbeResults,
+    Entry, Exit, ExitPolicyObservation, ExitPolicyProbeResults, ExitPolicySample, IpPingReplies,
+    LpProbeResults, PathBenchmark, ProbeOutcome, Socks5ProbeResults, WgProbeResults,
 };
-use crate::common::wireguard::{WgTunnelConfig, run_tunnel_tests};
+use crate::common::wireguard::{WgTunnelConfig, run_tunnel_benchmark, run_tunnel_tests};
+use crate::common::{benchmark, exit_policy_test};
 use crate::common::{helpers, icmp};
-use crate::config::{NetstackArgs, Socks5Args};
+use crate::config::{BenchmarkArgs, ExitPolicyArgs, NetstackArgs, Socks5Args};
 use anyhow::bail;
 use base64::{Engine, engine::general_purpose};
 use bytes::BytesMut;
@@ -28,11 +30,13 @@ use nym_ip_packet_requests::{IpPair, codec::MultiIpPacketCodec};
 use nym_lp::peer::DHKeyPair;
 use nym_registration_client::LpRegistrationClient;
 use nym_sdk::NymNetworkDetails;
-use nym_sdk::mixnet::{MixnetClient, MixnetClientBuilder, NodeIdentity, Recipient, Socks5};
+use nym_sdk::mixnet::{
+    MixnetClient, MixnetClientBuilder, NodeIdentity, Recipient, Socks5, Socks5MixnetClient,
+};
 use nym_topology::HardcodedTopologyProvider;
 use rand09::SeedableRng;
 use std::{
-    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     sync::Arc,
     time::Duration,
 };
@@ -48,7 +52,8 @@ pub async fn wg_probe(
     netstack_args: NetstackArgs,
     // TODO: update type
     credential: CredentialSpendingData,
-) -> anyhow::Result<WgProbeResults> {
+    benchmark: Option<(SocketAddr, BenchmarkArgs)>,
+) -> anyhow::Result<(WgProbeResults, Option<PathBenchmark>)> {
     info!("attempting to use authenticator version {auth_version:?}");
 
     let mut rng = rand::thread_rng();
@@ -145,14 +150,21 @@ pub async fn wg_probe(
         wg_endpoint,
     );
 
-    run_tunnel_tests(
-        &tunnel_config,
-        &netstack_args,
-        &awg_args.unwrap_or_default(),
-        &mut wg_outcome,
-    );
+    let awg_args = awg_args.unwrap_or_default();
+    run_tunnel_tests(&tunnel_config, &netstack_args, &awg_args, &mut wg_outcome);
+
+    // reuse the registration for the benchmark rather than going through the authenticator again
+    let benchmark_result = benchmark.map(|(echo_endpoint, benchmark_args)| {
+        run_tunnel_benchmark(
+            &tunnel_config,
+            &netstack_args,
+            &awg_args,
+            echo_endpoint,
+            &benchmark_args,
+        )
+    });
 
-    Ok(wg_outcome)
+    Ok((wg_outcome, benchmark_result))
 }
 
 pub async fn lp_registration_probe(
@@ -273,6 +285,8 @@ pub async fn do_ping(
             socks5: None,
             wg: None,
             lp: None,
+            exit_policy: None,
+            benchmark: None,
         }),
         mixnet_client,
     )
@@ -474,6 +488,281 @@ pub(crate) async fn do_socks5_connectivity_test(
         nr_recipient.identity().to_base58_string()
     );
 
+    let socks5_client =
+        match connect_socks5_client(nr_recipient, entry_gateway_id, network_details).await {
+            Ok(client) => client,
+            Err(e) => return Ok(Socks5ProbeResults::error_before_connecting(e)),
+        };
+
+    let test = match HttpsConnectivityTest::new(
+        socks5_args.test_count,
+        socks5_args.mixnet_client_timeout_sec,
+        socks5_args.failure_count_cutoff,
+        socks5_args.socks5_json_rpc_url_list,
+        socks5_client.socks5_url(),
+    ) {
+        Ok(test) => test,
+        Err(err) => {
+            socks5_client.disconnect().await;
+
+            error!("{err}");
+            return Ok(Socks5ProbeResults::error_after_connecting(
+                "Failed to create client",
+            ));
+        }
+    };
+
+    let result = test.run_tests().await;
+    socks5_client.disconnect().await;
+
+    Ok(Socks5ProbeResults::with_http_result(result))
+}
+
+/// Runs the throughput and latency benchmark against `endpoint` through an ephemeral
+/// SOCKS5 client, i.e. via the mixnet and the exit network requester.
+#[instrument(level = "info", name = "mixnet_benchmark", skip_all)]
+pub(crate) async fn do_mixnet_benchmark(
+    nr_recipient: &Recipient,
+    entry_gateway_id: NodeIdentity,
+    network_details: NymNetworkDetails,
+    endpoint: SocketAddr,
+    args: &BenchmarkArgs,
+) -> PathBenchmark {
+    info!("Benchmarking the mixnet exit through the Network Requester: {nr_recipient}");
+    let socks5_client =
+        match connect_socks5_client(nr_recipient, entry_gateway_id, network_details).await {
+            Ok(client) => client,
+            Err(err) => return PathBenchmark::with_error(err),
+        };
+
+    let socks5_address = socks5_client.socks5_bind_address();
+    let result = benchmark::run_stream_benchmark(
+        || exit_policy_test::network_requester::socks5_connect(socks5_address, endpoint),
+        args.into(),
+    )
+    .await;
+    socks5_client.disconnect().await;
+
+    result
+}
+
+/// Retrieves the exit policy advertised by the exit and checks whether the network requester
+/// and the IPR actually enforce it, by attempting sampled allowed and rejected destinations.
+#[instrument(level = "info", name = "exit_policy_test", skip_all)]
+pub(crate) async fn do_exit_policy_test(
+    mixnet_client: MixnetClient,
+    exit_node: &TestedNodeDetails,
+    entry_gateway_id: NodeIdentity,
+    network_details: NymNetworkDetails,
+    args: ExitPolicyArgs,
+) -> (ExitPolicyProbeResults, MixnetClient) {
+    let api_address = match (&args.exit_policy_node_api, exit_node.ip_address) {
+        (Some(address), _) => address.clone(),
+        (None, Some(ip_address)) => ip_address.to_string(),
+        (None, None) => {
+            return (
+                ExitPolicyProbeResults::with_error("no address known to query the exit policy"),
+                mixnet_client,
+            );
+        }
+    };
+
+    let used_policy = match query_exit_policy(api_address).await {
+        Ok(used_policy) => used_policy,
+        Err(err) => {
+            error!("Failed to retrieve the exit policy: {err}");
+            return (
+                ExitPolicyProbeResults::with_error(format!(
+                    "failed to retrieve the exit policy: {err}"
+                )),
+                mixnet_client,
+            );
+        }
+    };
+    let Some(policy) = used_policy.policy.filter(|_| used_policy.enabled) else {
+        warn!("Exit does not advertise an exit policy");
+        return (
+            ExitPolicyProbeResults::with_error("exit does not advertise an exit policy"),
+            mixnet_client,
+        );
+    };
+
+    let mut results = ExitPolicyProbeResults {
+        policy_retrieved: true,
+        ..Default::default()
+    };
+
+    let mut targets = Vec::new();
+    for target in &args.exit_policy_targets {
+        match target.parse::<SocketAddr>() {
+            Ok(target) => targets.push(target),
+            Err(err) => results
+                .errors
+                .push(format!("invalid exit policy target '{target}': {err}")),
+        }
+    }
+
+    let samples = exit_policy_test::plan_samples(
+        &policy,
+        &targets,
+        usize::from(args.exit_policy_max_samples),
+        args.exit_policy_allow_local_targets,
+    );
+    if samples.is_empty() {
+        results
+            .errors
+            .push("could not derive any destinations to attempt".to_string());
+        return (results, mixnet_client);
+    }
+
+    let timeout = Duration::from_secs(args.exit_policy_timeout_sec);
+
+    info!(
+        "Checking direct reachability of {} destinations",
+        samples.len()
+    );
+    let directly_reachable = futures::future::join_all(
+        samples
+            .iter()
+            .map(|sample| exit_policy_test::is_directly_reachable(sample.destination, timeout)),
+    )
+    .await;
+
+    let not_tested = || {
+        samples
+            .iter()
+            .zip(&directly_reachable)
+            .map(|(sample, reachable)| {
+                ExitPolicySample::new(
+                    sample.destination.to_string(),
+                    sample.expected_allowed,
+                    *reachable,
+                    ExitPolicyObservation::NotTested,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Network requester
+    results.network_requester = if let Some(network_requester) =
+        &exit_node.network_requester_address
+    {
+        info!("Testing exit policy through the Network Requester: {network_requester}");
+        match connect_socks5_client(network_requester, entry_gateway_id, network_details).await {
+            Ok(socks5_client) => {
+                let socks5_address = socks5_client.socks5_bind_address();
+                let mut attempted = Vec::with_capacity(samples.len());
+                for (sample, reachable) in samples.iter().zip(&directly_reachable) {
+                    let observed = exit_policy_test::network_requester::attempt_destination(
+                        socks5_address,
+                        sample.destination,
+                        timeout,
+                    )
+                    .await;
+                    attempted.push(ExitPolicySample::new(
+                        sample.destination.to_string(),
+                        sample.expected_allowed,
+                        *reachable,
+                        observed,
+                    ));
+                }
+                socks5_client.disconnect().await;
+                attempted
+            }
+            Err(err) => {
+                results.errors.push(err);
+                not_tested()
+            }
+        }
+    } else {
+        warn!("No NR available, skipping exit policy test through the Network Requester");
+        results
+            .errors
+            .push("exit policy not tested through the network requester: none available".into());
+        not_tested()
+    };
+
+    // IP packet router
+    let mixnet_client = if let Some(exit_router_address) = exit_node.exit_router_address {
+        info!("Testing exit policy through the IPR: {exit_router_address}");
+        let (maybe_ip_pair, mut mixnet_client) =
+            connect_exit(mixnet_client, exit_router_address).await;
+        results.ip_packet_router = match maybe_ip_pair {
+            Some((our_ips, stream_id)) => {
+                match exit_policy_test::ipr::attempt_destinations(
+                    &mut mixnet_client,
+                    our_ips,
+                    stream_id,
+                    exit_router_address,
+                    &samples,
+                    timeout,
+                )
+                .await
+                {
+                    Ok(observations) => samples
+                        .iter()
+                        .zip(&directly_reachable)
+                        .zip(observations)
+                        .map(|((sample, reachable), observed)| {
+                            ExitPolicySample::new(
+                                sample.destination.to_string(),
+                                sample.expected_allowed,
+                                *reachable,
+                                observed,
+                            )
+                        })
+                        .collect(),
+                    Err(err) => {
+                        results.errors.push(format!(
+                            "failed to attempt destinations through the IPR: {err}"
+                        ));
+                        not_tested()
+                    }
+                }
+            }
+            None => {
+                results
+                    .errors
+                    .push("failed to connect to the IPR".to_string());
+                not_tested()
+            }
+        };
+        mixnet_client
+    } else {
+        warn!("No IPR available, skipping exit policy test through the IPR");
+        results
+            .errors
+            .push("exit policy not tested through the IPR: none available".into());
+        results.ip_packet_router = not_tested();
+        mixnet_client
+    };
+
+    results.update_summary();
+    if results.mismatches > 0 {
+        warn!(
+            "Exit does not enforce its advertised exit policy: {} mismatches",
+            results.mismatches
+        );
+    } else if !results.is_conformant() {
+        warn!(
+            "Could not confirm exit policy conformance ({} inconclusive samples, {} errors)",
+            results.inconclusive,
+            results.errors.len()
+        );
+    } else {
+        info!("Exit enforces its advertised exit policy");
+    }
+
+    (results, mixnet_client)
+}
+
+/// Starts an ephemeral SOCKS5 client connected through the tested entry gateway
+/// that uses the provided network requester.
+async fn connect_socks5_client(
+    nr_recipient: &Recipient,
+    entry_gateway_id: NodeIdentity,
+    network_details: NymNetworkDetails,
+) -> Result<Socks5MixnetClient, String> {
     // since we define both entry & exit gateways to be the same tested GW,
     // this shouldn't negatively affect mixnet layers but it will force route
     // construction in case GW would get filtered out of topology
@@ -486,11 +775,8 @@ pub(crate) async fn do_socks5_connectivity_test(
 
     // don't reuse topology: our gateway might be filtered out in it
     info!("Fetching topology for SOCKS5 client...");
-    let topology_provider: Box<HardcodedTopologyProvider> =
-        match helpers::fetch_topology(&network_details, &debug_config).await {
-            Ok(topology) => Box::new(HardcodedTopologyProvider::new(topology)),
-            Err(e) => return Ok(Socks5ProbeResults::error_before_connecting(e)),
-        };
+    let topology = helpers::fetch_topology(&network_details, &debug_config).await?;
+    let topology_provider = Box::new(HardcodedTopologyProvider::new(topology));
 
     let socks5_client_builder = MixnetClientBuilder::new_ephemeral()
         // Specify entry gateway explicitly
@@ -500,10 +786,10 @@ pub(crate) async fn do_socks5_connectivity_test(
         .debug_config(debug_config)
         .custom_topology_provider(topology_provider);
 
-    let disconnected_socks5_client = socks5_client_builder.build()?;
+    let disconnected_socks5_client = socks5_client_builder.build().map_err(|e| e.to_string())?;
 
     // connect to mixnet via SOCKS5
-    let socks5_client = match disconnected_socks5_client
+    match disconnected_socks5_client
         .connect_to_mixnet_via_socks5()
         .await
     {
@@ -512,37 +798,11 @@ pub(crate) async fn do_socks5_connectivity_test(
                 "🌐 Successfully connected to mixnet via SOCKS5 on entry gateway: {}",
                 client.nym_address().gateway().to_base58_string()
             );
-            client
+            Ok(client)
         }
         Err(e) => {
             error!("Failed to establish SOCKS5 connection: {}", e);
-            return Ok(Socks5ProbeResults::error_before_connecting(format!(
-                "SOCKS5 connection failed: {}",
-                e
-            )));
-        }
-    };
-
-    let test = match HttpsConnectivityTest::new(
-        socks5_args.test_count,
-        socks5_args.mixnet_client_timeout_sec,
-        socks5_args.failure_count_cutoff,
-        socks5_args.socks5_json_rpc_url_list,
-        socks5_client.socks5_url(),
-    ) {
-        Ok(test) => test,
-        Err(err) => {
-            socks5_client.disconnect().await;
-
-            error!("{err}");
-            return Ok(Socks5ProbeResults::error_after_connecting(
-                "Failed to create client",
-            ));
+            Err(format!("SOCKS5 connection failed: {}", e))
         }
-    };
-
-    let result = test.run_tests().await;
-    socks5_client.disconnect().await;
-
-    Ok(Socks5ProbeResults::with_http_result(result))
+    }
 }
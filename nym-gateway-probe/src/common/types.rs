@@ -20,6 +20,8 @@ pub struct ProbeOutcome {
     pub socks5: Option<Socks5ProbeResults>,
     pub wg: Option<WgProbeResults>,
     pub lp: Option<LpProbeResults>,
+    pub exit_policy: Option<ExitPolicyProbeResults>,
+    pub benchmark: Option<BenchmarkProbeResults>,
 }
 
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -62,6 +64,237 @@ pub struct LpProbeResults {
     pub error: Option<String>,
 }
 
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename = "exit_policy")]
+pub struct ExitPolicyProbeResults {
+    /// whether the advertised exit policy could be retrieved from the node
+    pub policy_retrieved: bool,
+
+    /// destinations attempted through the network requester
+    pub network_requester: Vec<ExitPolicySample>,
+
+    /// destinations attempted through the ip packet router
+    pub ip_packet_router: Vec<ExitPolicySample>,
+
+    /// number of samples where the exit did not behave as its policy advertises
+    pub mismatches: usize,
+
+    /// number of samples whose outcome neither confirms nor contradicts the advertised policy
+    pub inconclusive: usize,
+
+    /// error message(s) (if any)
+    pub errors: Vec<String>,
+}
+
+impl ExitPolicyProbeResults {
+    pub fn with_error(error: impl Into<String>) -> Self {
+        Self {
+            errors: vec![error.into()],
+            ..Default::default()
+        }
+    }
+
+    /// The exit is only considered conformant if every path could be tested,
+    /// at least one sample was conclusive and none of them contradicted the policy.
+    pub fn is_conformant(&self) -> bool {
+        self.policy_retrieved
+            && self.errors.is_empty()
+            && self.mismatches == 0
+            && self.samples().any(|sample| sample.conclusive)
+    }
+
+    fn samples(&self) -> impl Iterator<Item = &ExitPolicySample> {
+        self.network_requester
+            .iter()
+            .chain(self.ip_packet_router.iter())
+    }
+
+    pub(crate) fn update_summary(&mut self) {
+        self.mismatches = self.samples().filter(|sample| sample.mismatch).count();
+        self.inconclusive = self.samples().filter(|sample| !sample.conclusive).count();
+    }
+}
+
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitPolicySample {
+    /// the attempted destination
+    pub destination: String,
+
+    /// whether the advertised policy allows that destination
+    pub expected_allowed: bool,
+
+    /// whether the destination responded when contacted directly by the probe
+    pub directly_reachable: bool,
+
+    /// what happened when contacting the destination through the exit
+    pub observed: ExitPolicyObservation,
+
+    /// whether the observation tells us anything about the exit's behaviour
+    pub conclusive: bool,
+
+    /// whether the observation contradicts the advertised policy
+    pub mismatch: bool,
+}
+
+impl ExitPolicySample {
+    pub fn new(
+        destination: impl Into<String>,
+        expected_allowed: bool,
+        directly_reachable: bool,
+        observed: ExitPolicyObservation,
+    ) -> Self {
+        let (conclusive, mismatch) = match observed {
+            // traffic went through, regardless of whether it should have
+            ExitPolicyObservation::Reachable => (true, !expected_allowed),
+            // the exit told us explicitly it won't forward the traffic
+            ExitPolicyObservation::Rejected => (true, expected_allowed),
+            // the connection got closed without any data coming back. that's only meaningful
+            // for destinations we know to respond, otherwise they might have just closed it themselves
+            ExitPolicyObservation::Closed => {
+                (directly_reachable, expected_allowed && directly_reachable)
+            }
+            // silence might just as well be caused by a lost packet or a slow route
+            ExitPolicyObservation::NoResponse | ExitPolicyObservation::NotTested => (false, false),
+        };
+
+        Self {
+            destination: destination.into(),
+            expected_allowed,
+            directly_reachable,
+            observed,
+            conclusive,
+            mismatch,
+        }
+    }
+}
+
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitPolicyObservation {
+    /// the destination responded through the exit
+    Reachable,
+
+    /// the exit explicitly refused to forward the traffic
+    Rejected,
+
+    /// the connection got closed before anything came back
+    Closed,
+
+    /// nothing came back before the timeout
+    NoResponse,
+
+    /// the attempt could not be made
+    NotTested,
+}
+
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename = "benchmark")]
+pub struct BenchmarkProbeResults {
+    /// the echo endpoint the measurements were made against
+    pub endpoint: String,
+
+    /// measurements through the mixnet exit path (network requester)
+    pub mixnet: Option<PathBenchmark>,
+
+    /// measurements through the WireGuard tunnel
+    pub wireguard: Option<PathBenchmark>,
+
+    /// error message(s) (if any)
+    pub errors: Vec<String>,
+}
+
+impl BenchmarkProbeResults {
+    pub fn with_error(error: impl Into<String>) -> Self {
+        Self {
+            errors: vec![error.into()],
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PathBenchmark {
+    /// number of RTT probes sent
+    pub rtt_probes_sent: u32,
+
+    /// number of RTT probes echoed back before the timeout
+    pub rtt_probes_received: u32,
+
+    /// fraction of RTT probes that never came back
+    pub packet_loss: f32,
+
+    /// RTT statistics, if any of the probes came back
+    pub rtt_ms: Option<RttStatistics>,
+
+    /// total bytes written while measuring throughput
+    pub bytes_sent: u64,
+
+    pub upload_duration_ms: u64,
+
+    /// sustained upload throughput, in bits per second
+    pub upload_bps: u64,
+
+    /// total bytes echoed back while measuring throughput
+    pub bytes_received: u64,
+
+    pub download_duration_ms: u64,
+
+    /// sustained download throughput, in bits per second
+    pub download_bps: u64,
+
+    pub error: Option<String>,
+}
+
+impl PathBenchmark {
+    pub fn with_error(error: impl Into<String>) -> Self {
+        Self {
+            error: Some(error.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RttStatistics {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl RttStatistics {
+    /// Nearest-rank percentiles over the provided samples.
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+
+        Some(RttStatistics {
+            min: sorted[0],
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p50: percentile(50.0),
+            p90: percentile(90.0),
+            p99: percentile(99.0),
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
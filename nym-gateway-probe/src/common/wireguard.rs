@@ -7,11 +7,18 @@
 //! that is shared between different test modes (authenticator-based and LP-based).
 
 use nym_config::defaults::{WG_METADATA_PORT, WG_TUN_DEVICE_IP_ADDRESS_V4};
+use std::net::SocketAddr;
+use std::time::Duration;
 use tracing::{error, info};
 
 use crate::NetstackArgs;
-use crate::common::netstack::{NetstackRequest, NetstackRequestGo, NetstackResult};
-use crate::common::types::WgProbeResults;
+use crate::common::benchmark::{self, ThroughputMeasurement};
+use crate::common::netstack::{
+    NetstackBenchmarkRequestGo, NetstackBenchmarkResult, NetstackRequest, NetstackRequestGo,
+    NetstackResult,
+};
+use crate::common::types::{PathBenchmark, WgProbeResults};
+use crate::config::BenchmarkArgs;
 
 /// Safe division that returns 0.0 when divisor is 0 (instead of NaN/Inf)
 fn safe_ratio(received: u16, sent: u16) -> f32 {
@@ -168,3 +175,60 @@ pub fn run_tunnel_tests(
         }
     }
 }
+
+/// Run the throughput and latency benchmark through the WireGuard tunnel using netstack.
+///
+/// The tunnel address family is chosen to match the echo endpoint.
+pub fn run_tunnel_benchmark(
+    config: &WgTunnelConfig,
+    netstack_args: &NetstackArgs,
+    awg_args: &str,
+    echo_endpoint: SocketAddr,
+    benchmark_args: &BenchmarkArgs,
+) -> PathBenchmark {
+    let (wg_ip, dns, ip_version) = match echo_endpoint {
+        SocketAddr::V4(_) => (&config.private_ipv4, &netstack_args.netstack_v4_dns, 4),
+        SocketAddr::V6(_) => (&config.private_ipv6, &netstack_args.netstack_v6_dns, 6),
+    };
+
+    let request = NetstackBenchmarkRequestGo {
+        wg_ip: wg_ip.clone(),
+        private_key: config.private_key_hex.clone(),
+        public_key: config.public_key_hex.clone(),
+        endpoint: config.endpoint.clone(),
+        dns: dns.clone(),
+        ip_version,
+        awg_args: awg_args.to_string(),
+        echo_endpoint: echo_endpoint.to_string(),
+        rtt_samples: benchmark_args.benchmark_rtt_samples,
+        rtt_interval_ms: benchmark_args.benchmark_rtt_interval_ms,
+        throughput_duration_sec: benchmark_args.benchmark_duration_sec,
+        chunk_size: benchmark_args.benchmark_chunk_size,
+        timeout_sec: benchmark_args.benchmark_timeout_sec,
+    };
+
+    info!("Benchmarking WireGuard tunnel against {echo_endpoint}...");
+    match crate::common::netstack::benchmark(&request) {
+        Ok(NetstackBenchmarkResult::Response(response)) => {
+            let throughput = ThroughputMeasurement {
+                bytes_sent: response.bytes_sent,
+                upload_duration: Duration::from_millis(response.upload_duration_ms),
+                bytes_received: response.bytes_received,
+                download_duration: Duration::from_millis(response.download_duration_ms),
+            };
+            let mut result = benchmark::summarise(response.rtt_sent, &response.rtt_ms, throughput);
+            if !response.error.is_empty() {
+                result.error = Some(response.error);
+            }
+            result
+        }
+        Ok(NetstackBenchmarkResult::Error { error }) => {
+            error!("Netstack runtime error (benchmark): {error}");
+            PathBenchmark::with_error(error)
+        }
+        Err(error) => {
+            error!("Internal error (benchmark): {error}");
+            PathBenchmark::with_error(error.to_string())
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use clap::Args;
+use std::net::SocketAddr;
+
+/// Upper bound on a single throughput write, so a typo can't make us allocate gigabytes.
+const MAX_CHUNK_SIZE: i64 = 1024 * 1024;
+
+#[derive(Args, Clone, Debug)]
+pub struct BenchmarkArgs {
+    /// Plain TCP echo (or sink) endpoint (`ip:port`) the benchmark is run against,
+    /// e.g. `socat TCP-LISTEN:7000,fork,reuseaddr EXEC:cat`
+    #[arg(long, hide = true, env = "PROBE_BENCHMARK_ENDPOINT")]
+    pub benchmark_endpoint: Option<SocketAddr>,
+
+    /// Number of RTT probes to send through every tested path
+    #[arg(long, hide = true, env = "PROBE_BENCHMARK_RTT_SAMPLES", default_value_t = BenchmarkArgs::default().benchmark_rtt_samples)]
+    pub benchmark_rtt_samples: u32,
+
+    /// Interval between consecutive RTT probes
+    #[arg(long, hide = true, env = "PROBE_BENCHMARK_RTT_INTERVAL_MS", default_value_t = BenchmarkArgs::default().benchmark_rtt_interval_ms)]
+    pub benchmark_rtt_interval_ms: u64,
+
+    /// For how long to keep sending data when measuring throughput
+    #[arg(long, hide = true, env = "PROBE_BENCHMARK_DURATION_SEC", default_value_t = BenchmarkArgs::default().benchmark_duration_sec)]
+    pub benchmark_duration_sec: u64,
+
+    /// Size of a single write when measuring throughput
+    #[arg(long, hide = true, env = "PROBE_BENCHMARK_CHUNK_SIZE", default_value_t = BenchmarkArgs::default().benchmark_chunk_size, value_parser = clap::value_parser!(u32).range(1..=MAX_CHUNK_SIZE))]
+    pub benchmark_chunk_size: u32,
+
+    /// How long to wait for outstanding echoes (and for connections to get established)
+    #[arg(long, hide = true, env = "PROBE_BENCHMARK_TIMEOUT_SEC", default_value_t = BenchmarkArgs::default().benchmark_timeout_sec)]
+    pub benchmark_timeout_sec: u64,
+}
+
+impl Default for BenchmarkArgs {
+    fn default() -> Self {
+        Self {
+            benchmark_endpoint: None,
+            benchmark_rtt_samples: 20,
+            benchmark_rtt_interval_ms: 200,
+            benchmark_duration_sec: 10,
+            benchmark_chunk_size: 16 * 1024,
+            benchmark_timeout_sec: 15,
+        }
+    }
+}
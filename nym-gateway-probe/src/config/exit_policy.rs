@@ -0,0 +1,52 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Args;
+
+/// Every sample sent through the IPR uses its own source port, so keep that bounded.
+const MAX_SAMPLES_PER_CLASS: i64 = 100;
+
+#[derive(Args, Clone, Debug)]
+pub struct ExitPolicyArgs {
+    /// Reference destinations (`ip:port`) used to derive exit policy samples.
+    /// Additional ports are derived from the advertised policy rules for every listed host.
+    /// Rejected samples are only conclusive if the destination responds on that port,
+    /// so prefer hosts that answer on any port (e.g. local stand-ins)
+    #[arg(long, hide = true, env = "PROBE_EXIT_POLICY_TARGETS", default_values_t = ExitPolicyArgs::default().exit_policy_targets, value_delimiter = ';')]
+    pub exit_policy_targets: Vec<String>,
+
+    /// Maximum number of allowed and of rejected samples to attempt (each)
+    #[arg(long, hide = true, env = "PROBE_EXIT_POLICY_MAX_SAMPLES", default_value_t = ExitPolicyArgs::default().exit_policy_max_samples, value_parser = clap::value_parser!(u16).range(1..=MAX_SAMPLES_PER_CLASS))]
+    pub exit_policy_max_samples: u16,
+
+    /// How long to wait for a response from a single sampled destination
+    #[arg(long, hide = true, env = "PROBE_EXIT_POLICY_TIMEOUT_SEC", default_value_t = ExitPolicyArgs::default().exit_policy_timeout_sec)]
+    pub exit_policy_timeout_sec: u64,
+
+    /// Address of the node HTTP API to retrieve the exit policy from (IP, IP:PORT or HOST:PORT).
+    /// If not provided, the tested exit's IP address is used
+    #[arg(long, hide = true, env = "PROBE_EXIT_POLICY_NODE_API")]
+    pub exit_policy_node_api: Option<String>,
+
+    /// Expect the exit to forward traffic to non-global addresses allowed by its policy.
+    /// Only meaningful for local setups where the exit runs with `allow_local_ips`
+    /// and the targets are local stand-in destinations
+    #[arg(long, hide = true, env = "PROBE_EXIT_POLICY_ALLOW_LOCAL_TARGETS")]
+    pub exit_policy_allow_local_targets: bool,
+}
+
+impl Default for ExitPolicyArgs {
+    fn default() -> Self {
+        Self {
+            exit_policy_targets: vec![
+                "1.1.1.1:80".to_string(),
+                "1.1.1.1:443".to_string(),
+                "1.1.1.1:25".to_string(),
+            ],
+            exit_policy_max_samples: 3,
+            exit_policy_timeout_sec: 10,
+            exit_policy_node_api: None,
+            exit_policy_allow_local_targets: false,
+        }
+    }
+}
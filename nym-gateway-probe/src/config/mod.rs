@@ -3,12 +3,16 @@
 
 use clap::Args;
 
+mod benchmark;
 mod credentials;
+mod exit_policy;
 mod netstack;
 mod socks5;
 mod test_mode;
 
+pub use benchmark::BenchmarkArgs;
 pub use credentials::{CredentialArgs, CredentialMode};
+pub use exit_policy::ExitPolicyArgs;
 pub use netstack::NetstackArgs;
 pub use socks5::Socks5Args;
 pub use test_mode::TestMode;
@@ -27,7 +31,9 @@ pub struct ProbeConfig {
     ///   wg-lp       - Entry LP + Exit LP (nested forwarding) + WireGuard
     ///   lp-only     - LP registration only (no WireGuard)
     ///   socks5-only - Socks5 network requester test
-    ///   all         - Mixnet, wireguard over authenticator and LP registration
+    ///   exit-policy - Exit policy conformance via network requester and IPR
+    ///   benchmark   - WireGuard via authenticator + throughput/latency benchmark (mixnet exit and WireGuard)
+    ///   all         - Mixnet, wireguard over authenticator, LP registration, socks5 and exit policy
     ///
     #[arg(long, default_value_t = TestMode::default(), verbatim_doc_comment)]
     pub test_mode: TestMode,
@@ -46,4 +52,12 @@ pub struct ProbeConfig {
     /// Arguments to configure socks5 probe
     #[command(flatten)]
     pub socks5_args: Socks5Args,
+
+    /// Arguments to configure exit policy conformance probe
+    #[command(flatten)]
+    pub exit_policy_args: ExitPolicyArgs,
+
+    /// Arguments to configure the throughput and latency benchmark
+    #[command(flatten)]
+    pub benchmark_args: BenchmarkArgs,
 }
@@ -9,7 +9,9 @@
 //! - WgLp: Entry LP + Exit LP (nested forwarding) + WireGuard
 //! - LpOnly: LP registration only, no WireGuard
 //! - Socks5Only: Socks5 test
-//! - All: Mixnet, wireguard over authenticator and LP registration
+//! - ExitPolicy: Exit policy conformance through the network requester and IPR
+//! - Benchmark: WireGuard via authenticator, plus throughput and latency benchmarks
+//! - All: Mixnet, Wireguard, LP, Socks5 and exit policy tests
 
 /// Test mode for the gateway probe.
 ///
@@ -27,7 +29,12 @@ pub enum TestMode {
     LpOnly,
     /// Socks5 test only
     Socks5Only,
-    /// Mixnet tests, Wireguard tests, LP tests, Socks5 test
+    /// Exit policy conformance test only
+    ExitPolicy,
+    /// Wireguard via authenticator + throughput and latency benchmark
+    /// through the mixnet exit and the WireGuard tunnel
+    Benchmark,
+    /// Mixnet tests, Wireguard tests, LP tests, Socks5 test, exit policy test
     All,
 }
 
@@ -41,7 +48,7 @@ impl TestMode {
     pub fn wireguard_tests(&self) -> bool {
         matches!(
             self,
-            TestMode::Core | TestMode::WgMix | TestMode::WgLp | TestMode::All
+            TestMode::Core | TestMode::WgMix | TestMode::WgLp | TestMode::Benchmark | TestMode::All
         )
     }
 
@@ -55,9 +62,26 @@ impl TestMode {
         matches!(self, TestMode::Socks5Only | TestMode::All)
     }
 
+    // Wether we need to run exit policy conformance tests
+    pub fn exit_policy_tests(&self) -> bool {
+        matches!(self, TestMode::ExitPolicy | TestMode::All)
+    }
+
+    // Wether we need to run throughput and latency benchmarks
+    pub fn benchmark_tests(&self) -> bool {
+        matches!(self, TestMode::Benchmark)
+    }
+
     /// Whether this mode requires a mixnet client
     pub fn needs_mixnet(&self) -> bool {
-        matches!(self, TestMode::Core | TestMode::All | TestMode::WgMix)
+        matches!(
+            self,
+            TestMode::Core
+                | TestMode::All
+                | TestMode::WgMix
+                | TestMode::ExitPolicy
+                | TestMode::Benchmark
+        )
     }
 }
 
@@ -69,6 +93,8 @@ impl std::fmt::Display for TestMode {
             TestMode::WgLp => write!(f, "wg-lp"),
             TestMode::LpOnly => write!(f, "lp-only"),
             TestMode::Socks5Only => write!(f, "socks5-only"),
+            TestMode::ExitPolicy => write!(f, "exit-policy"),
+            TestMode::Benchmark => write!(f, "benchmark"),
             TestMode::All => write!(f, "all"),
         }
     }
@@ -84,9 +110,11 @@ impl std::str::FromStr for TestMode {
             "wg-lp" | "wglp" | "wg_lp" => Ok(TestMode::WgLp),
             "lp-only" | "lponly" | "lp_only" => Ok(TestMode::LpOnly),
             "socks5-only" | "socks5only" | "socks5_only" => Ok(TestMode::Socks5Only),
+            "exit-policy" | "exitpolicy" | "exit_policy" => Ok(TestMode::ExitPolicy),
+            "benchmark" | "bench" => Ok(TestMode::Benchmark),
             "all" => Ok(TestMode::All),
             _ => Err(format!(
-                "Unknown test mode: '{}'. Valid modes: core, wg-mix, wg-lp, lp-only, socks5-only, all",
+                "Unknown test mode: '{}'. Valid modes: core, wg-mix, wg-lp, lp-only, socks5-only, exit-policy, benchmark, all",
                 s
             )),
         }
@@ -106,9 +134,32 @@ mod tests {
         assert!(!TestMode::WgLp.needs_mixnet());
         assert!(!TestMode::LpOnly.needs_mixnet());
         assert!(!TestMode::Socks5Only.needs_mixnet());
+        assert!(TestMode::ExitPolicy.needs_mixnet());
+        assert!(TestMode::Benchmark.needs_mixnet());
         assert!(TestMode::All.needs_mixnet());
     }
 
+    #[test]
+    fn test_exit_policy_tests() {
+        assert!(TestMode::ExitPolicy.exit_policy_tests());
+        assert!(TestMode::All.exit_policy_tests());
+        assert!(!TestMode::Core.exit_policy_tests());
+        assert!(!TestMode::Socks5Only.exit_policy_tests());
+        assert!(!TestMode::ExitPolicy.mixnet_tests());
+        assert!(!TestMode::ExitPolicy.wireguard_tests());
+    }
+
+    #[test]
+    fn test_benchmark_tests() {
+        assert!(TestMode::Benchmark.benchmark_tests());
+        assert!(TestMode::Benchmark.wireguard_tests());
+        assert!(!TestMode::Benchmark.mixnet_tests());
+        assert!(!TestMode::Benchmark.exit_policy_tests());
+        // benchmarks are heavy, so they're never part of the `all` run
+        assert!(!TestMode::All.benchmark_tests());
+        assert!(!TestMode::Core.benchmark_tests());
+    }
+
     // ============ Display tests ============
 
     #[test]
@@ -118,6 +169,8 @@ mod tests {
         assert_eq!(TestMode::WgLp.to_string(), "wg-lp");
         assert_eq!(TestMode::LpOnly.to_string(), "lp-only");
         assert_eq!(TestMode::Socks5Only.to_string(), "socks5-only");
+        assert_eq!(TestMode::ExitPolicy.to_string(), "exit-policy");
+        assert_eq!(TestMode::Benchmark.to_string(), "benchmark");
         assert_eq!(TestMode::All.to_string(), "all");
     }
 
@@ -140,6 +193,7 @@ mod tests {
     fn test_from_str_alternate_formats() {
         // Default aliases
         assert_eq!("mixnet".parse::<TestMode>().unwrap(), TestMode::Core);
+        assert_eq!("bench".parse::<TestMode>().unwrap(), TestMode::Benchmark);
 
         // snake_case
         assert_eq!("wg_mix".parse::<TestMode>().unwrap(), TestMode::WgMix);
@@ -190,6 +244,8 @@ mod tests {
             TestMode::WgLp,
             TestMode::LpOnly,
             TestMode::Socks5Only,
+            TestMode::ExitPolicy,
+            TestMode::Benchmark,
             TestMode::All,
         ] {
             let s = mode.to_string();
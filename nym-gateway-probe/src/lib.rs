@@ -5,9 +5,12 @@ use crate::common::bandwidth_helpers::build_bandwidth_controller;
 use crate::common::helpers;
 use crate::common::nodes::TestedNodeDetails;
 use crate::common::probe_tests::{
-    do_ping, do_socks5_connectivity_test, lp_registration_probe, wg_probe,
+    do_exit_policy_test, do_mixnet_benchmark, do_ping, do_socks5_connectivity_test,
+    lp_registration_probe, wg_probe,
+};
+use crate::common::types::{
+    BenchmarkProbeResults, Entry, ExitPolicyProbeResults, LpProbeResults, PathBenchmark,
 };
-use crate::common::types::{Entry, LpProbeResults};
 use crate::config::{CredentialArgs, CredentialMode, NetstackArgs, ProbeConfig};
 use nym_authenticator_client::{AuthClientMixnetListener, AuthenticatorClient};
 use nym_bandwidth_controller::BandwidthTicketProvider;
@@ -274,7 +277,9 @@ impl Probe {
                 as_exit: None,
                 wg: None,
                 lp: None,
+                exit_policy: None,
                 socks5: None,
+                benchmark: None,
             },
         };
 
@@ -342,6 +347,83 @@ impl Probe {
             mixnet_client
         };
 
+        // Exit policy conformance test
+        let mixnet_client = if self.config.test_mode.exit_policy_tests() {
+            match mixnet_client {
+                Some(client) => {
+                    let (outcome, client) = do_exit_policy_test(
+                        client,
+                        &exit_node,
+                        self.entry_node.identity,
+                        self.network.clone(),
+                        self.config.exit_policy_args,
+                    )
+                    .await;
+                    probe_result.outcome.exit_policy = Some(outcome);
+                    Some(client)
+                }
+                None => {
+                    error!("Exit policy tests cannot be run without a mixnet client");
+                    probe_result.outcome.exit_policy = Some(ExitPolicyProbeResults::with_error(
+                        "no mixnet client available",
+                    ));
+                    None
+                }
+            }
+        } else {
+            mixnet_client
+        };
+
+        // Throughput and latency benchmark through the mixnet exit.
+        // The WireGuard part is run alongside the WireGuard tests below.
+        let benchmark_endpoint = if self.config.test_mode.benchmark_tests() {
+            match self.config.benchmark_args.benchmark_endpoint {
+                Some(endpoint) => {
+                    let mut results = BenchmarkProbeResults {
+                        endpoint: endpoint.to_string(),
+                        ..Default::default()
+                    };
+                    if let Some(network_requester) = &exit_node.network_requester_address {
+                        results.mixnet = Some(
+                            do_mixnet_benchmark(
+                                network_requester,
+                                self.entry_node.identity,
+                                self.network.clone(),
+                                endpoint,
+                                &self.config.benchmark_args,
+                            )
+                            .await,
+                        );
+                    } else {
+                        warn!("No NR available, skipping the mixnet exit benchmark");
+                        results.errors.push(
+                            "mixnet exit not benchmarked: no network requester available".into(),
+                        );
+                    }
+                    probe_result.outcome.benchmark = Some(results);
+                    Some(endpoint)
+                }
+                None => {
+                    error!("Cannot run the benchmark: no benchmark endpoint configured");
+                    probe_result.outcome.benchmark = Some(BenchmarkProbeResults::with_error(
+                        "no benchmark endpoint configured",
+                    ));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if benchmark_endpoint.is_some()
+            && mixnet_client.is_none()
+            && let Some(benchmark) = probe_result.outcome.benchmark.as_mut()
+        {
+            benchmark
+                .errors
+                .push("WireGuard not benchmarked: no mixnet client available".into());
+        }
+
         // Wireguard with Authenticator test
         if let Some(mixnet_client) = mixnet_client {
             // We have a mixnet_client to disconnect at the end here
@@ -378,22 +460,43 @@ impl Probe {
                         .await?
                         .data;
 
-                    let outcome = wg_probe(
+                    let (outcome, wg_benchmark) = match wg_probe(
                         auth_client,
                         ip_address,
                         exit_node.authenticator_version,
                         self.config.amnezia_args.clone(),
                         self.config.netstack_args.clone(),
                         credential,
+                        benchmark_endpoint
+                            .map(|endpoint| (endpoint, self.config.benchmark_args.clone())),
                     )
                     .await
-                    .unwrap_or_default();
+                    {
+                        Ok(results) => results,
+                        Err(err) => {
+                            error!("WireGuard probe failed: {err}");
+                            let wg_benchmark = benchmark_endpoint.map(|_| {
+                                PathBenchmark::with_error(format!("WireGuard probe failed: {err}"))
+                            });
+                            (Default::default(), wg_benchmark)
+                        }
+                    };
 
                     // Add wg results to probe result
                     probe_result.outcome.wg = Some(outcome);
+                    if let Some(benchmark) = probe_result.outcome.benchmark.as_mut() {
+                        benchmark.wireguard = wg_benchmark;
+                    }
                     mixnet_listener_task.stop().await;
                 } else {
                     warn!("Not enough information to run WireGuard via mixnet registration tests");
+                    if benchmark_endpoint.is_some()
+                        && let Some(benchmark) = probe_result.outcome.benchmark.as_mut()
+                    {
+                        benchmark
+                            .errors
+                            .push("WireGuard not benchmarked: no authenticator available".into());
+                    }
                     mixnet_client.disconnect().await;
                 }
             } else {
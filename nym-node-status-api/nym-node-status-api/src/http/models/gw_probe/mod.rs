@@ -140,6 +140,8 @@ pub struct ProbeOutcome {
     pub wg: Option<WgProbeResults>,
     pub socks5: Option<Socks5ProbeResults>,
     pub lp: Option<LpProbeResults>,
+    pub exit_policy: Option<ExitPolicyProbeResults>,
+    pub benchmark: Option<BenchmarkProbeResults>,
 }
 
 use nym_gateway_probe::types::ProbeOutcome as ProbeOutcomeLatest;
@@ -152,6 +154,8 @@ impl From<ProbeOutcomeLatest> for ProbeOutcome {
             wg: value.wg.map(From::from),
             socks5: value.socks5.map(From::from),
             lp: value.lp.map(From::from),
+            exit_policy: value.exit_policy.map(From::from),
+            benchmark: value.benchmark.map(From::from),
         }
     }
 }
@@ -474,3 +478,164 @@ impl From<LpProbeResultsLatest> for LpProbeResults {
         }
     }
 }
+
+use nym_gateway_probe::types::ExitPolicyProbeResults as ExitPolicyProbeResultsLatest;
+use nym_gateway_probe::types::ExitPolicySample as ExitPolicySampleLatest;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename = "exit_policy")]
+pub struct ExitPolicyProbeResults {
+    pub policy_retrieved: bool,
+    pub network_requester: Vec<ExitPolicySample>,
+    pub ip_packet_router: Vec<ExitPolicySample>,
+    pub mismatches: usize,
+    pub inconclusive: usize,
+    pub conformant: bool,
+    pub errors: Vec<String>,
+}
+
+impl From<ExitPolicyProbeResultsLatest> for ExitPolicyProbeResults {
+    fn from(value: ExitPolicyProbeResultsLatest) -> Self {
+        let conformant = value.is_conformant();
+        Self {
+            policy_retrieved: value.policy_retrieved,
+            network_requester: value
+                .network_requester
+                .into_iter()
+                .map(From::from)
+                .collect(),
+            ip_packet_router: value.ip_packet_router.into_iter().map(From::from).collect(),
+            mismatches: value.mismatches,
+            inconclusive: value.inconclusive,
+            conformant,
+            errors: value.errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExitPolicySample {
+    pub destination: String,
+    pub expected_allowed: bool,
+    pub directly_reachable: bool,
+    pub observed: ExitPolicyObservation,
+    pub conclusive: bool,
+    pub mismatch: bool,
+}
+
+impl From<ExitPolicySampleLatest> for ExitPolicySample {
+    fn from(value: ExitPolicySampleLatest) -> Self {
+        Self {
+            destination: value.destination,
+            expected_allowed: value.expected_allowed,
+            directly_reachable: value.directly_reachable,
+            observed: value.observed.into(),
+            conclusive: value.conclusive,
+            mismatch: value.mismatch,
+        }
+    }
+}
+
+use nym_gateway_probe::types::ExitPolicyObservation as ExitPolicyObservationLatest;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitPolicyObservation {
+    Reachable,
+    Rejected,
+    Closed,
+    NoResponse,
+    NotTested,
+}
+
+impl From<ExitPolicyObservationLatest> for ExitPolicyObservation {
+    fn from(value: ExitPolicyObservationLatest) -> Self {
+        match value {
+            ExitPolicyObservationLatest::Reachable => Self::Reachable,
+            ExitPolicyObservationLatest::Rejected => Self::Rejected,
+            ExitPolicyObservationLatest::Closed => Self::Closed,
+            ExitPolicyObservationLatest::NoResponse => Self::NoResponse,
+            ExitPolicyObservationLatest::NotTested => Self::NotTested,
+        }
+    }
+}
+
+use nym_gateway_probe::types::BenchmarkProbeResults as BenchmarkProbeResultsLatest;
+use nym_gateway_probe::types::PathBenchmark as PathBenchmarkLatest;
+use nym_gateway_probe::types::RttStatistics as RttStatisticsLatest;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename = "benchmark")]
+pub struct BenchmarkProbeResults {
+    pub endpoint: String,
+    pub mixnet: Option<PathBenchmark>,
+    pub wireguard: Option<PathBenchmark>,
+    pub errors: Vec<String>,
+}
+
+impl From<BenchmarkProbeResultsLatest> for BenchmarkProbeResults {
+    fn from(value: BenchmarkProbeResultsLatest) -> Self {
+        Self {
+            endpoint: value.endpoint,
+            mixnet: value.mixnet.map(From::from),
+            wireguard: value.wireguard.map(From::from),
+            errors: value.errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+pub struct PathBenchmark {
+    pub rtt_probes_sent: u32,
+    pub rtt_probes_received: u32,
+    pub packet_loss: f32,
+    pub rtt_ms: Option<RttStatistics>,
+    pub bytes_sent: u64,
+    pub upload_duration_ms: u64,
+    pub upload_bps: u64,
+    pub bytes_received: u64,
+    pub download_duration_ms: u64,
+    pub download_bps: u64,
+    pub error: Option<String>,
+}
+
+impl From<PathBenchmarkLatest> for PathBenchmark {
+    fn from(value: PathBenchmarkLatest) -> Self {
+        Self {
+            rtt_probes_sent: value.rtt_probes_sent,
+            rtt_probes_received: value.rtt_probes_received,
+            packet_loss: value.packet_loss,
+            rtt_ms: value.rtt_ms.map(From::from),
+            bytes_sent: value.bytes_sent,
+            upload_duration_ms: value.upload_duration_ms,
+            upload_bps: value.upload_bps,
+            bytes_received: value.bytes_received,
+            download_duration_ms: value.download_duration_ms,
+            download_bps: value.download_bps,
+            error: value.error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct RttStatistics {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl From<RttStatisticsLatest> for RttStatistics {
+    fn from(value: RttStatisticsLatest) -> Self {
+        Self {
+            min: value.min,
+            mean: value.mean,
+            p50: value.p50,
+            p90: value.p90,
+            p99: value.p99,
+            max: value.max,
+        }
+    }
+}
@@ -75,6 +75,8 @@ fn test_weighted_score_calculation() {
                 // so these fields are irrelevant
                 socks5: None,
                 lp: None,
+                exit_policy: None,
+                benchmark: None,
             },
         }
     }
@@ -134,10 +136,15 @@ fn test_weighted_score_calculation() {
 #[test]
 fn conversion_from_gw_probe_latest() {
     use nym_gateway_probe::types::{
-        Entry as EntryLatest, EntryTestResult as EntryTestResultLatest, Exit as ExitLatest,
+        BenchmarkProbeResults as BenchmarkProbeResultsLatest, Entry as EntryLatest,
+        EntryTestResult as EntryTestResultLatest, Exit as ExitLatest,
+        ExitPolicyObservation as ExitPolicyObservationLatest,
+        ExitPolicyProbeResults as ExitPolicyProbeResultsLatest,
+        ExitPolicySample as ExitPolicySampleLatest,
         HttpsConnectivityResult as HttpsConnectivityResultLatest,
-        LpProbeResults as LpProbeResultsLatest, ProbeOutcome as ProbeOutcomeLatest,
-        ProbeResult as ProbeResultLatest, Socks5ProbeResults as Socks5ProbeResultsLatest,
+        LpProbeResults as LpProbeResultsLatest, PathBenchmark as PathBenchmarkLatest,
+        ProbeOutcome as ProbeOutcomeLatest, ProbeResult as ProbeResultLatest,
+        RttStatistics as RttStatisticsLatest, Socks5ProbeResults as Socks5ProbeResultsLatest,
         WgProbeResults as WgProbeResultsLatest,
     };
 
@@ -196,6 +203,42 @@ fn conversion_from_gw_probe_latest() {
                 can_register: true,
                 error: Some(String::from("error1")),
             }),
+            exit_policy: Some(ExitPolicyProbeResultsLatest {
+                policy_retrieved: true,
+                network_requester: vec![ExitPolicySampleLatest::new(
+                    "1.1.1.1:25",
+                    false,
+                    true,
+                    ExitPolicyObservationLatest::Reachable,
+                )],
+                ip_packet_router: vec![ExitPolicySampleLatest::new(
+                    "1.1.1.1:443",
+                    true,
+                    true,
+                    ExitPolicyObservationLatest::NoResponse,
+                )],
+                mismatches: 1,
+                inconclusive: 1,
+                errors: vec![String::from("error1")],
+            }),
+            benchmark: Some(BenchmarkProbeResultsLatest {
+                endpoint: String::from("1.1.1.1:7"),
+                mixnet: Some(PathBenchmarkLatest {
+                    rtt_probes_sent: 4,
+                    rtt_probes_received: 3,
+                    packet_loss: 0.25,
+                    rtt_ms: RttStatisticsLatest::from_samples(&[100.0, 200.0, 300.0]),
+                    bytes_sent: 1000,
+                    upload_duration_ms: 1000,
+                    upload_bps: 8000,
+                    bytes_received: 500,
+                    download_duration_ms: 1000,
+                    download_bps: 4000,
+                    error: None,
+                }),
+                wireguard: Some(PathBenchmarkLatest::with_error("error1")),
+                errors: vec![String::from("error1")],
+            }),
         },
     };
 
@@ -208,6 +251,53 @@ fn conversion_from_gw_probe_latest() {
     assert!(lp.can_register);
     assert_eq!(lp.error, Some(String::from("error1")));
 
+    let exit_policy = result
+        .outcome
+        .exit_policy
+        .as_ref()
+        .expect("exit_policy should be Some");
+    assert!(exit_policy.policy_retrieved);
+    assert_eq!(exit_policy.mismatches, 1);
+    assert_eq!(exit_policy.inconclusive, 1);
+    assert!(!exit_policy.conformant);
+    assert_eq!(exit_policy.errors, vec![String::from("error1")]);
+    let nr_sample = &exit_policy.network_requester[0];
+    assert_eq!(nr_sample.destination, "1.1.1.1:25");
+    assert!(!nr_sample.expected_allowed);
+    assert!(nr_sample.directly_reachable);
+    assert_eq!(nr_sample.observed, ExitPolicyObservation::Reachable);
+    assert!(nr_sample.conclusive);
+    assert!(nr_sample.mismatch);
+    let ipr_sample = &exit_policy.ip_packet_router[0];
+    assert_eq!(ipr_sample.destination, "1.1.1.1:443");
+    assert!(ipr_sample.expected_allowed);
+    assert_eq!(ipr_sample.observed, ExitPolicyObservation::NoResponse);
+    // silence is never held against the exit
+    assert!(!ipr_sample.conclusive);
+    assert!(!ipr_sample.mismatch);
+
+    let benchmark = result
+        .outcome
+        .benchmark
+        .as_ref()
+        .expect("benchmark should be Some");
+    assert_eq!(benchmark.endpoint, "1.1.1.1:7");
+    let wireguard = benchmark
+        .wireguard
+        .as_ref()
+        .expect("wireguard should be Some");
+    assert_eq!(wireguard.error, Some(String::from("error1")));
+    assert_eq!(benchmark.errors, vec![String::from("error1")]);
+    let mixnet = benchmark.mixnet.as_ref().expect("mixnet should be Some");
+    assert_eq!(mixnet.rtt_probes_sent, 4);
+    assert_eq!(mixnet.rtt_probes_received, 3);
+    assert_eq!(mixnet.upload_bps, 8000);
+    assert_eq!(mixnet.download_bps, 4000);
+    let rtt = mixnet.rtt_ms.expect("rtt_ms should be Some");
+    assert_eq!(rtt.min, 100.0);
+    assert_eq!(rtt.p50, 200.0);
+    assert_eq!(rtt.max, 300.0);
+
     let socks5 = result.outcome.socks5.as_ref().expect("lp should be Some");
     assert!(socks5.can_connect_socks5);
     assert!(socks5.https_connectivity.https_success);
@@ -514,6 +604,8 @@ fn round_trip_serialization() {
                 can_register: true,
                 error: Some(String::from("example")),
             }),
+            exit_policy: None,
+            benchmark: None,
         },
     };
 
@@ -31,15 +31,24 @@ pub struct LewesProtocol {
     /// for their computation.
     /// note: digests are hex encoded
     pub kem_keys: BTreeMap<LPKEM, BTreeMap<LPHashFunction, String>>,
+
+    /// Versions of the Lewes Protocol supported by this node.
+    /// Nodes that predate protocol negotiation do not advertise it and only support version 1.
+    // note: the field is omitted when empty so that signatures of legacy nodes remain valid
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supported_versions: Vec<u8>,
 }
 
 impl LewesProtocol {
+    pub const LEGACY_VERSION: u8 = 1;
+
     pub fn new(
         enabled: bool,
         control_port: u16,
         data_port: u16,
         x25519: x25519::DHPublicKey,
         kem_keys: BTreeMap<LPKEM, BTreeMap<LPHashFunction, String>>,
+        supported_versions: Vec<u8>,
     ) -> Self {
         LewesProtocol {
             enabled,
@@ -47,6 +56,16 @@ impl LewesProtocol {
             data_port,
             x25519,
             kem_keys,
+            supported_versions,
+        }
+    }
+
+    /// Protocol versions supported by the node, accounting for nodes that predate negotiation
+    pub fn supported_versions(&self) -> Vec<u8> {
+        if self.supported_versions.is_empty() {
+            vec![Self::LEGACY_VERSION]
+        } else {
+            self.supported_versions.clone()
         }
     }
 }
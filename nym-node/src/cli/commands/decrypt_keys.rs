@@ -0,0 +1,29 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::commands::encrypt_keys::{node_private_key_files, required_key_passphrase};
+use crate::cli::helpers::ConfigArgs;
+use crate::config::upgrade_helpers::try_load_current_config;
+use tracing::info;
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct Args {
+    #[clap(flatten)]
+    pub(crate) config: ConfigArgs,
+}
+
+pub async fn execute(args: Args) -> anyhow::Result<()> {
+    let config = try_load_current_config(args.config.config_path()).await?;
+    let passphrase = required_key_passphrase()?;
+
+    let mut decrypted = 0;
+    for path in node_private_key_files(&config) {
+        if nym_pemstore::decrypt_key_file(path, passphrase)? {
+            info!("decrypted {}", path.display());
+            decrypted += 1;
+        }
+    }
+
+    info!("decrypted {decrypted} key file(s) of node {}", config.id);
+    Ok(())
+}
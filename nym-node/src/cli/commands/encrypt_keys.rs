@@ -0,0 +1,56 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::helpers::ConfigArgs;
+use crate::config::Config;
+use crate::config::upgrade_helpers::try_load_current_config;
+use crate::node::helpers::key_passphrase;
+use anyhow::bail;
+use nym_pemstore::KeyPassphrase;
+use std::path::Path;
+use tracing::info;
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct Args {
+    #[clap(flatten)]
+    pub(crate) config: ConfigArgs,
+}
+
+/// Private keys of this node that are managed through the key passphrase.
+/// Note: keys of the embedded service providers are not included as they're loaded by the client-core.
+pub(crate) fn node_private_key_files(config: &Config) -> Vec<&Path> {
+    let mut files = config.storage_paths.keys.private_key_files().to_vec();
+    files.push(
+        &config
+            .wireguard
+            .storage_paths
+            .private_diffie_hellman_key_file,
+    );
+    files.retain(|path| path.exists());
+    files
+}
+
+pub(crate) fn required_key_passphrase() -> anyhow::Result<&'static KeyPassphrase> {
+    match key_passphrase() {
+        Some(passphrase) => Ok(passphrase),
+        None => bail!(
+            "no key passphrase provided. use `--key-passphrase-file`, `--prompt-key-passphrase` or set `NYMNODE_KEY_PASSPHRASE`"
+        ),
+    }
+}
+
+pub async fn execute(args: Args) -> anyhow::Result<()> {
+    let config = try_load_current_config(args.config.config_path()).await?;
+    let passphrase = required_key_passphrase()?;
+
+    let mut encrypted = 0;
+    for path in node_private_key_files(&config) {
+        if nym_pemstore::encrypt_key_file(path, passphrase)? {
+            info!("encrypted {}", path.display());
+            encrypted += 1;
+        }
+    }
+
+    info!("encrypted {encrypted} key file(s) of node {}", config.id);
+    Ok(())
+}
@@ -10,6 +10,8 @@ use celes::Country;
 use clap::Args;
 use clap::builder::ArgPredicate;
 use nym_crypto::asymmetric::ed25519;
+use nym_pemstore::KeyPassphrase;
+use std::io;
 use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use url::Url;
@@ -52,6 +54,53 @@ impl ConfigArgs {
     }
 }
 
+#[derive(Args, Debug)]
+pub(crate) struct KeyPassphraseArgs {
+    /// Passphrase used for encrypting the private keys of this node at rest.
+    /// Prefer providing it via the environment or a file rather than on the command line.
+    #[clap(
+        long,
+        global = true,
+        hide = true,
+        hide_env_values = true,
+        env = NYMNODE_KEY_PASSPHRASE_ARG
+    )]
+    key_passphrase: Option<String>,
+
+    /// Path to a file containing the passphrase used for encrypting the private keys of this node at rest.
+    #[clap(
+        long,
+        global = true,
+        env = NYMNODE_KEY_PASSPHRASE_FILE_ARG
+    )]
+    key_passphrase_file: Option<PathBuf>,
+
+    /// Interactively prompt for the passphrase used for encrypting the private keys of this node at rest.
+    #[clap(long, global = true)]
+    prompt_key_passphrase: bool,
+}
+
+impl KeyPassphraseArgs {
+    /// Resolve the passphrase from the prompt, the file or the raw value (in that order).
+    /// `new_passphrase` makes the prompt ask for a confirmation.
+    pub(crate) fn resolve(&self, new_passphrase: bool) -> io::Result<Option<KeyPassphrase>> {
+        if self.prompt_key_passphrase {
+            return KeyPassphrase::prompt("key passphrase:", new_passphrase).map(Some);
+        }
+        if let Some(file) = &self.key_passphrase_file {
+            return KeyPassphrase::from_file(file).map(Some);
+        }
+        match &self.key_passphrase {
+            Some(passphrase) if passphrase.is_empty() => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the key passphrase must not be empty",
+            )),
+            Some(passphrase) => Ok(Some(KeyPassphrase::new(passphrase.as_bytes()))),
+            None => Ok(None),
+        }
+    }
+}
+
 #[derive(clap::Args, Debug)]
 pub(crate) struct HostArgs {
     /// Comma separated list of public ip addresses that will be announced to the nym-api and subsequently to the clients.
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::cli::commands::{
-    bonding_information, build_info, debug, migrate, node_details, reset_sphinx_keys, run, sign,
-    test_throughput,
+    bonding_information, build_info, debug, decrypt_keys, encrypt_keys, migrate, node_details,
+    reset_sphinx_keys, run, sign, test_throughput,
 };
+use crate::cli::helpers::KeyPassphraseArgs;
 use crate::env::vars::{NYMNODE_CONFIG_ENV_FILE_ARG, NYMNODE_NO_BANNER_ARG};
 use clap::{Args, Parser, Subcommand};
 use nym_bin_common::bin_info;
@@ -76,6 +77,9 @@ pub(crate) struct Cli {
     )]
     pub(crate) no_banner: bool,
 
+    #[clap(flatten)]
+    pub(crate) key_passphrase: KeyPassphraseArgs,
+
     #[cfg(feature = "otel")]
     #[clap(flatten)]
     pub(crate) otel: OtelArgs,
@@ -95,6 +99,13 @@ impl Cli {
         let use_otel = matches!(self.command, Commands::Run(..));
         let _otel_guard = runtime.block_on(async { self.setup_logging(use_otel) })?;
 
+        // resolve the key passphrase (if any) before doing anything else so that
+        // all subsequent key operations could use it
+        let new_passphrase = matches!(self.command, Commands::EncryptKeys(..));
+        if let Some(passphrase) = self.key_passphrase.resolve(new_passphrase)? {
+            crate::node::helpers::set_key_passphrase(passphrase);
+        }
+
         // `_otel_guard` is dropped at function exit, flushing pending spans via its Drop impl
         runtime.block_on(async {
             match self.command {
@@ -106,6 +117,8 @@ impl Cli {
                 Commands::Sign(args) => sign::execute(args).await?,
                 Commands::TestThroughput(args) => test_throughput::execute(args)?,
                 Commands::UnsafeResetSphinxKeys(args) => reset_sphinx_keys::execute(args).await?,
+                Commands::EncryptKeys(args) => encrypt_keys::execute(args).await?,
+                Commands::DecryptKeys(args) => decrypt_keys::execute(args).await?,
                 Commands::Debug(debug) => match debug.command {
                     DebugCommands::ResetProvidersGatewayDbs(args) => {
                         debug::reset_providers_dbs::execute(args).await?
@@ -178,6 +191,12 @@ pub(crate) enum Commands {
     /// UNSAFE: reset existing sphinx keys and attempt to generate fresh one for the current network state
     UnsafeResetSphinxKeys(reset_sphinx_keys::Args),
 
+    /// Encrypt the existing private keys of this node with the provided key passphrase.
+    EncryptKeys(encrypt_keys::Args),
+
+    /// Decrypt the private keys of this node, storing them in plaintext.
+    DecryptKeys(decrypt_keys::Args),
+
     /// Commands exposed for debug purposes, usually not meant to be used by operators
     #[clap(hide = true)]
     Debug(Debug),
@@ -29,11 +29,61 @@ pub struct LpConfig {
     #[serde(deserialize_with = "de_maybe_port")]
     pub announce_data_port: Option<u16>,
 
+    /// Per-source limits applied to incoming client control connections
+    #[serde(default)]
+    pub rate_limiting: LpRateLimiting,
+
     /// Auxiliary configuration
     #[serde(default)]
     pub debug: LpDebug,
 }
 
+/// Per-source limits applied to incoming client control connections.
+///
+/// Every accepted client connection immediately performs the expensive KKT/PSQ handshake,
+/// so limiting the rate of new connections of a single source also limits its handshakes.
+/// Connections from known LP nodes are never limited.
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct LpRateLimiting {
+    /// Specifies whether the limits are enforced (default: true)
+    pub enabled: bool,
+
+    /// Maximum number of concurrent control connections from a single IP address (default: 32)
+    pub max_connections_per_ip: usize,
+
+    /// Maximum number of concurrent control connections from a single subnet (default: 256)
+    pub max_connections_per_subnet: usize,
+
+    /// Maximum number of handshakes a single IP address can start per minute.
+    /// Short bursts up to this value are allowed (default: 60)
+    pub handshakes_per_ip_per_minute: u32,
+
+    /// Maximum number of handshakes a single subnet can start per minute.
+    /// Short bursts up to this value are allowed (default: 600)
+    pub handshakes_per_subnet_per_minute: u32,
+
+    /// Prefix length used for grouping IPv4 addresses into subnets (default: 24)
+    pub ipv4_subnet_prefix: u8,
+
+    /// Prefix length used for grouping IPv6 addresses into subnets (default: 48)
+    pub ipv6_subnet_prefix: u8,
+
+    /// Number of failed credential verifications tolerated from a single IP address
+    /// before it gets temporarily banned (default: 3)
+    pub allowed_credential_failures: u32,
+
+    /// Duration of the first ban after too many failed credential verifications.
+    /// It doubles with every subsequent failure (default: 30s)
+    #[serde(with = "humantime_serde")]
+    pub credential_failure_penalty: Duration,
+
+    /// Upper bound on the duration of a ban. It is also the time after which
+    /// previous credential failures are forgotten (default: 1h)
+    #[serde(with = "humantime_serde")]
+    pub max_credential_failure_penalty: Duration,
+}
+
 #[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct LpDebug {
@@ -72,6 +122,15 @@ pub struct LpDebug {
     #[serde(with = "humantime_serde")]
     pub session_ttl: Duration,
 
+    /// Validity of the resumption handles issued to clients after establishing a session (default: 24h)
+    ///
+    /// A client presenting a valid handle can establish a new session without repeating
+    /// the full KKT/PSQ handshake. Every handle can only be used once
+    /// and a fresh one is issued for the resumed session.
+    /// Set to zero to disable session resumption.
+    #[serde(with = "humantime_serde")]
+    pub resumption_handle_ttl: Duration,
+
     /// How often to run the state cleanup task (default: 5 minutes)
     ///
     /// The cleanup task scans for and removes stale handshakes and sessions.
@@ -118,11 +177,41 @@ impl Default for LpConfig {
             ),
             announce_control_port: None,
             announce_data_port: None,
+            rate_limiting: Default::default(),
             debug: Default::default(),
         }
     }
 }
 
+impl LpRateLimiting {
+    pub const DEFAULT_MAX_CONNECTIONS_PER_IP: usize = 32;
+    pub const DEFAULT_MAX_CONNECTIONS_PER_SUBNET: usize = 256;
+    pub const DEFAULT_HANDSHAKES_PER_IP_PER_MINUTE: u32 = 60;
+    pub const DEFAULT_HANDSHAKES_PER_SUBNET_PER_MINUTE: u32 = 600;
+    pub const DEFAULT_IPV4_SUBNET_PREFIX: u8 = 24;
+    pub const DEFAULT_IPV6_SUBNET_PREFIX: u8 = 48;
+    pub const DEFAULT_ALLOWED_CREDENTIAL_FAILURES: u32 = 3;
+    pub const DEFAULT_CREDENTIAL_FAILURE_PENALTY: Duration = Duration::from_secs(30);
+    pub const DEFAULT_MAX_CREDENTIAL_FAILURE_PENALTY: Duration = Duration::from_secs(3600);
+}
+
+impl Default for LpRateLimiting {
+    fn default() -> Self {
+        LpRateLimiting {
+            enabled: true,
+            max_connections_per_ip: Self::DEFAULT_MAX_CONNECTIONS_PER_IP,
+            max_connections_per_subnet: Self::DEFAULT_MAX_CONNECTIONS_PER_SUBNET,
+            handshakes_per_ip_per_minute: Self::DEFAULT_HANDSHAKES_PER_IP_PER_MINUTE,
+            handshakes_per_subnet_per_minute: Self::DEFAULT_HANDSHAKES_PER_SUBNET_PER_MINUTE,
+            ipv4_subnet_prefix: Self::DEFAULT_IPV4_SUBNET_PREFIX,
+            ipv6_subnet_prefix: Self::DEFAULT_IPV6_SUBNET_PREFIX,
+            allowed_credential_failures: Self::DEFAULT_ALLOWED_CREDENTIAL_FAILURES,
+            credential_failure_penalty: Self::DEFAULT_CREDENTIAL_FAILURE_PENALTY,
+            max_credential_failure_penalty: Self::DEFAULT_MAX_CREDENTIAL_FAILURE_PENALTY,
+        }
+    }
+}
+
 impl LpDebug {
     pub const DEFAULT_MAX_CONNECTIONS: usize = 10000;
 
@@ -132,6 +221,9 @@ impl LpDebug {
     // 24 hours - for long-lived dVPN sessions
     pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(86400);
 
+    // 24 hours - matches the default session TTL
+    pub const DEFAULT_RESUMPTION_HANDLE_TTL: Duration = Duration::from_secs(86400);
+
     // 5 minutes - balances memory reclamation with task overhead
     pub const DEFAULT_STATE_CLEANUP_INTERVAL: Duration = Duration::from_secs(300);
 
@@ -146,6 +238,7 @@ impl Default for LpDebug {
             use_mock_ecash: false,
             handshake_ttl: Self::DEFAULT_HANDSHAKE_TTL,
             session_ttl: Self::DEFAULT_SESSION_TTL,
+            resumption_handle_ttl: Self::DEFAULT_RESUMPTION_HANDLE_TTL,
             state_cleanup_interval: Self::DEFAULT_STATE_CLEANUP_INTERVAL,
             max_concurrent_forwards: Self::DEFAULT_MAX_CONCURRENT_FORWARDS,
         }
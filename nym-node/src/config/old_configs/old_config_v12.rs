@@ -724,11 +724,13 @@ pub async fn try_upgrade_config_v12<P: AsRef<Path>>(
             data_bind_address: old_cfg.gateway_tasks.lp.data_bind_address,
             announce_control_port: old_cfg.gateway_tasks.lp.announce_control_port,
             announce_data_port: old_cfg.gateway_tasks.lp.announce_data_port,
+            rate_limiting: Default::default(),
             debug: LpDebug {
                 max_connections: old_cfg.gateway_tasks.lp.debug.max_connections,
                 use_mock_ecash: old_cfg.gateway_tasks.lp.debug.use_mock_ecash,
                 handshake_ttl: old_cfg.gateway_tasks.lp.debug.handshake_ttl,
                 session_ttl: old_cfg.gateway_tasks.lp.debug.session_ttl,
+                resumption_handle_ttl: LpDebug::DEFAULT_RESUMPTION_HANDLE_TTL,
                 state_cleanup_interval: old_cfg.gateway_tasks.lp.debug.state_cleanup_interval,
                 max_concurrent_forwards: old_cfg.gateway_tasks.lp.debug.max_concurrent_forwards,
             },
@@ -187,6 +187,20 @@ impl KeysPaths {
             &self.public_mceliece_lp_key_file,
         )
     }
+
+    /// Paths to all private keys of this node, i.e. the ones that get encrypted at rest
+    /// when a key passphrase is used.
+    pub fn private_key_files(&self) -> [&Path; 7] {
+        [
+            &self.private_ed25519_identity_key_file,
+            &self.primary_x25519_sphinx_key_file,
+            &self.secondary_x25519_sphinx_key_file,
+            &self.private_x25519_noise_key_file,
+            &self.private_x25519_lp_key_file,
+            &self.private_mlkem768_lp_key_file,
+            &self.private_mceliece_lp_key_file,
+        ]
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
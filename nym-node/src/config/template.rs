@@ -206,6 +206,37 @@ announce_control_port ={{#if lp.announce_control_port }} {{ lp.announce_control_
 # (default: 0 - disabled)
 announce_data_port ={{#if lp.announce_data_port }} {{ lp.announce_data_port }} {{else}} 0 {{/if}}
 
+[lp.rate_limiting]
+# Specifies whether per-source limits are enforced on incoming client control connections.
+# Connections from known LP nodes are never limited.
+enabled = {{ lp.rate_limiting.enabled }}
+
+# Maximum number of concurrent control connections from a single IP address.
+max_connections_per_ip = {{ lp.rate_limiting.max_connections_per_ip }}
+
+# Maximum number of concurrent control connections from a single subnet.
+max_connections_per_subnet = {{ lp.rate_limiting.max_connections_per_subnet }}
+
+# Maximum number of handshakes a single IP address can start per minute.
+handshakes_per_ip_per_minute = {{ lp.rate_limiting.handshakes_per_ip_per_minute }}
+
+# Maximum number of handshakes a single subnet can start per minute.
+handshakes_per_subnet_per_minute = {{ lp.rate_limiting.handshakes_per_subnet_per_minute }}
+
+# Prefix lengths used for grouping addresses into subnets.
+ipv4_subnet_prefix = {{ lp.rate_limiting.ipv4_subnet_prefix }}
+ipv6_subnet_prefix = {{ lp.rate_limiting.ipv6_subnet_prefix }}
+
+# Number of failed credential verifications tolerated from a single IP address
+# before it gets temporarily banned.
+allowed_credential_failures = {{ lp.rate_limiting.allowed_credential_failures }}
+
+# Duration of the first ban. It doubles with every subsequent failure.
+credential_failure_penalty = '{{ lp.rate_limiting.credential_failure_penalty }}'
+
+# Upper bound on the duration of a ban.
+max_credential_failure_penalty = '{{ lp.rate_limiting.max_credential_failure_penalty }}'
+
 
 ##### verloc config options #####
 
@@ -4,6 +4,8 @@
 pub mod vars {
     pub const NYMNODE_NO_BANNER_ARG: &str = "NYMNODE_NO_BANNER";
     pub const NYMNODE_CONFIG_ENV_FILE_ARG: &str = "NYMNODE_CONFIG_ENV_FILE_ARG";
+    pub const NYMNODE_KEY_PASSPHRASE_ARG: &str = "NYMNODE_KEY_PASSPHRASE";
+    pub const NYMNODE_KEY_PASSPHRASE_FILE_ARG: &str = "NYMNODE_KEY_PASSPHRASE_FILE";
     pub const NYMNODE_ID_ARG: &str = "NYMNODE_ID";
     pub const NYMNODE_OUTPUT_ARG: &str = "NYMNODE_OUTPUT";
     pub const NYMNODE_CONFIG_PATH_ARG: &str = "NYMNODE_CONFIG";
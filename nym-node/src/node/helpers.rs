@@ -11,14 +11,15 @@ use nym_kkt::keys::{
     DHKeyPair, DHPrivateKey, MlKem768KeyPair, MlKem768PrivateKey, MlKem768PublicKey, mceliece,
 };
 use nym_node_requests::api::v1::node::models::NodeDescription;
-use nym_pemstore::KeyPairPath;
 use nym_pemstore::traits::{PemStorableKey, PemStorableKeyPair};
+use nym_pemstore::{KeyPairPath, KeyPassphrase};
 use nym_task::ShutdownToken;
 use nym_validator_client::QueryHttpRpcNyxdClient;
 use nym_validator_client::nyxd::contract_traits::MixnetQueryClient;
 use serde::Serialize;
 use std::fmt::{Display, Formatter};
 use std::path::Path;
+use std::sync::OnceLock;
 use tracing::warn;
 use url::Url;
 
@@ -101,14 +102,30 @@ impl Display for DisplayDetails {
     }
 }
 
+static KEY_PASSPHRASE: OnceLock<KeyPassphrase> = OnceLock::new();
+
+/// Set the passphrase used for encrypting the private keys of this node at rest.
+/// It's resolved once on startup and used by all subsequent key load and store operations.
+pub(crate) fn set_key_passphrase(passphrase: KeyPassphrase) {
+    if KEY_PASSPHRASE.set(passphrase).is_err() {
+        warn!("attempted to set the key passphrase more than once");
+    }
+}
+
+pub(crate) fn key_passphrase() -> Option<&'static KeyPassphrase> {
+    KEY_PASSPHRASE.get()
+}
+
 pub(crate) fn load_keypair<T: PemStorableKeyPair>(
     paths: &KeyPairPath,
     name: impl Into<String>,
 ) -> Result<T, KeyIOFailure> {
-    nym_pemstore::load_keypair(paths).map_err(|err| KeyIOFailure::KeyPairLoadFailure {
-        keys: name.into(),
-        paths: paths.clone(),
-        err,
+    nym_pemstore::load_keypair_with_passphrase(paths, key_passphrase()).map_err(|err| {
+        KeyIOFailure::KeyPairLoadFailure {
+            keys: name.into(),
+            paths: paths.clone(),
+            err,
+        }
     })
 }
 
@@ -117,10 +134,12 @@ pub(crate) fn store_keypair<T: PemStorableKeyPair>(
     paths: &KeyPairPath,
     name: impl Into<String>,
 ) -> Result<(), KeyIOFailure> {
-    nym_pemstore::store_keypair(keys, paths).map_err(|err| KeyIOFailure::KeyPairStoreFailure {
-        keys: name.into(),
-        paths: paths.clone(),
-        err,
+    nym_pemstore::store_keypair_with_passphrase(keys, paths, key_passphrase()).map_err(|err| {
+        KeyIOFailure::KeyPairStoreFailure {
+            keys: name.into(),
+            paths: paths.clone(),
+            err,
+        }
     })
 }
 
@@ -129,10 +148,12 @@ where
     T: PemStorableKey,
     P: AsRef<Path>,
 {
-    nym_pemstore::load_key(path.as_ref()).map_err(|err| KeyIOFailure::KeyLoadFailure {
-        key: name.into(),
-        path: path.as_ref().to_path_buf(),
-        err,
+    nym_pemstore::load_key_with_passphrase(path.as_ref(), key_passphrase()).map_err(|err| {
+        KeyIOFailure::KeyLoadFailure {
+            key: name.into(),
+            path: path.as_ref().to_path_buf(),
+            err,
+        }
     })
 }
 
@@ -141,10 +162,12 @@ where
     T: PemStorableKey,
     P: AsRef<Path>,
 {
-    nym_pemstore::store_key(key, path.as_ref()).map_err(|err| KeyIOFailure::KeyStoreFailure {
-        key: name.into(),
-        path: path.as_ref().to_path_buf(),
-        err,
+    nym_pemstore::store_key_with_passphrase(key, path.as_ref(), key_passphrase()).map_err(|err| {
+        KeyIOFailure::KeyStoreFailure {
+            key: name.into(),
+            path: path.as_ref().to_path_buf(),
+            err,
+        }
     })
 }
 
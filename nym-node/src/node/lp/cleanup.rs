@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::config::LpDebug;
-use crate::node::lp::state::ActiveLpSessions;
+use crate::node::lp::rate_limit::LpRateLimiter;
+use crate::node::lp::state::{ActiveLpSessions, ResumableLpSessions};
 use nym_metrics::inc_by;
 use std::time::Duration;
 use tracing::{debug, info};
@@ -72,6 +73,8 @@ impl<T> TimestampedState<T> {
 
 pub(crate) struct CleanupTask {
     session_states: ActiveLpSessions,
+    rate_limiter: LpRateLimiter,
+    resumptions: ResumableLpSessions,
     cfg: LpDebug,
     shutdown: nym_task::ShutdownToken,
 }
@@ -79,11 +82,15 @@ pub(crate) struct CleanupTask {
 impl CleanupTask {
     pub fn new(
         session_states: ActiveLpSessions,
+        rate_limiter: LpRateLimiter,
+        resumptions: ResumableLpSessions,
         cfg: LpDebug,
         shutdown: nym_task::ShutdownToken,
     ) -> Self {
         CleanupTask {
             session_states,
+            rate_limiter,
+            resumptions,
             cfg,
             shutdown,
         }
@@ -118,6 +125,20 @@ impl CleanupTask {
                 inc_by!("lp_states_cleanup_session_removed", ss_removed as i64);
             }
         }
+
+        // Remove resumption handles that can no longer be used
+        let rs_removed = self.resumptions.remove_expired();
+        if rs_removed > 0 {
+            debug!("LP state cleanup: {rs_removed} expired resumption handles");
+            inc_by!("lp_states_cleanup_resumption_removed", rs_removed as i64);
+        }
+
+        // Forget about sources that are no longer limited in any way
+        let rl_removed = self.rate_limiter.prune();
+        if rl_removed > 0 {
+            debug!("LP state cleanup: {rl_removed} rate limiting entries");
+            inc_by!("lp_rate_limit_entries_pruned", rl_removed as i64);
+        }
     }
 
     /// Background loop for cleaning up stale state entries
@@ -9,10 +9,11 @@ use dashmap::mapref::one::RefMut;
 use nym_lp::packet::frame::LpFrameKind;
 use nym_lp::packet::{EncryptedLpPacket, ForwardPacketData, LpFrame};
 use nym_lp::peer_config::LpReceiverIndex;
+use nym_lp::psq::SessionOrigin;
 use nym_lp::session::{LpAction, LpInput};
 use nym_lp::transport::LpHandshakeChannel;
 use nym_lp::transport::traits::LpTransportChannel;
-use nym_lp::{LpTransportSession, packet::frame::ExpectedResponseSize};
+use nym_lp::{LpTransportSession, ResumptionHandle, packet::frame::ExpectedResponseSize};
 use nym_metrics::{add_histogram_obs, inc};
 use nym_node_metrics::NymNodeMetrics;
 use nym_registration_common::{LpRegistrationRequest, RegistrationStatus};
@@ -39,6 +40,9 @@ pub struct LpClientConnectionHandler<S = TcpStream> {
     /// Set from ClientHello's proposed receiver_index, or from header for non-bootstrap packets.
     bound_receiver_idx: Option<LpReceiverIndex>,
 
+    /// Resumption handle issued for the session established on this connection, if any.
+    issued_resumption: Option<ResumptionHandle>,
+
     /// Persistent connection to exit gateway for forwarding.
     /// Opened on first forward, reused for subsequent forwards, closed when client disconnects.
     /// Tuple contains (stream, target_address) to verify subsequent forwards go to same exit.
@@ -56,6 +60,7 @@ where
             state,
             stats: LpConnectionStats::new(),
             bound_receiver_idx: None,
+            issued_resumption: None,
             exit_stream: None,
         }
     }
@@ -95,15 +100,16 @@ where
         // State persists in LpHandlerState maps across packets
         // ============================================================
 
-        // 1. complete KKT/PSQ handshake before doing anything else.
+        // 1. complete KKT/PSQ handshake (or resume an earlier session) before doing anything else.
         // bail if it takes too long
         let timeout = self.state.shared.lp_config.debug.handshake_ttl;
         let local_peer = self.state.local_lp_peer.clone();
+        let resumptions = self.state.resumptions.clone();
         let stream = &mut self.stream;
 
-        let session = match tokio::time::timeout(timeout, async move {
+        let (session, origin) = match tokio::time::timeout(timeout, async move {
             LpTransportSession::psq_handshake_responder(stream, local_peer)
-                .complete_handshake()
+                .complete_handshake_or_resume(|handle| resumptions.take(handle))
                 .await
         })
         .await
@@ -118,10 +124,28 @@ where
                 self.emit_lifecycle_metrics(false);
                 return Ok(());
             }
-            Ok(Ok(session)) => session,
+            Ok(Ok(established)) => established,
         };
         let receiver_idx = session.receiver_index();
 
+        if origin == SessionOrigin::Resumption {
+            debug!("resumed LP session with {remote} (receiver_idx={receiver_idx})");
+            inc!("lp_sessions_resumed");
+        }
+
+        // allow the client to skip the handshake on its next connection
+        let resumption_ttl = self.state.shared.lp_config.debug.resumption_handle_ttl;
+        if !resumption_ttl.is_zero() {
+            match session.resumable(resumption_ttl) {
+                Ok(resumable) => {
+                    self.issued_resumption = Some(resumable.handle());
+                    self.state.resumptions.insert(resumable);
+                    inc!("lp_resumption_handles_issued");
+                }
+                Err(err) => warn!("failed to export resumption data for {remote}: {err}"),
+            }
+        }
+
         // 2. insert the state machine into the shared state
         self.state.shared.session_states.insert_new_session(session);
         self.bound_receiver_idx = Some(receiver_idx);
@@ -320,7 +344,11 @@ where
         request: LpRegistrationRequest,
     ) -> Result<(), LpHandlerError> {
         // Process registration (might modify state)
-        let response = self.state.process_registration(receiver_idx, request).await;
+        let remote_ip = self.remote_addr.ip();
+        let response = self
+            .state
+            .process_registration(remote_ip, receiver_idx, request)
+            .await;
         let response_bytes = response
             .serialise()
             .map_err(|source| LpHandlerError::MalformedRegistrationRequest { source })?;
@@ -338,6 +366,13 @@ where
                     self.remote_addr,
                     response.error_message()
                 );
+                // don't let banned sources keep retrying over an already established connection
+                if self.state.rate_limiter.is_banned(remote_ip) {
+                    if let Some(handle) = self.issued_resumption.take() {
+                        self.state.resumptions.revoke(&handle);
+                    }
+                    return Err(LpHandlerError::BannedSource { ip_addr: remote_ip });
+                }
             }
             RegistrationStatus::PendingMoreData => {
                 info!(
@@ -587,7 +622,8 @@ mod tests {
     use super::*;
     use crate::config::LpConfig;
     use crate::config::lp::LpDebug;
-    use crate::node::lp::state::{ActiveLpSessions, SharedLpState};
+    use crate::node::lp::rate_limit::LpRateLimiter;
+    use crate::node::lp::state::{ActiveLpSessions, ResumableLpSessions, SharedLpState};
     use nym_lp::peer::{KEMKeys, LpLocalPeer, generate_keypair_mceliece, generate_keypair_mlkem};
     use nym_lp::{Ciphersuite, SessionManager, sessions_for_tests};
     use nym_test_utils::helpers::{deterministic_rng, deterministic_rng_09};
@@ -610,6 +646,7 @@ mod tests {
         let forward_semaphore = Arc::new(tokio::sync::Semaphore::new(
             lp_config.debug.max_concurrent_forwards,
         ));
+        let rate_limiter = LpRateLimiter::new(lp_config.rate_limiting);
 
         let id_keys = Arc::new(ed25519::KeyPair::new(&mut rng));
         let x_keys = Arc::new(id_keys.to_x25519().try_into().unwrap());
@@ -624,6 +661,8 @@ mod tests {
             local_lp_peer: lp_peer,
             peer_registrator: None,
             forward_semaphore,
+            rate_limiter,
+            resumptions: ResumableLpSessions::new(),
             shared: SharedLpState {
                 lp_config,
                 metrics: nym_node_metrics::NymNodeMetrics::default(),
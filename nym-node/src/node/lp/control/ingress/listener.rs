@@ -6,7 +6,9 @@ use crate::error::NymNodeError;
 use crate::node::lp::control::ingress::client_handler::LpClientConnectionHandler;
 use crate::node::lp::control::ingress::node_handler::InitialLpIngressNodeConnectionHandler;
 use crate::node::lp::directory::LpNodeDetails;
+use crate::node::lp::rate_limit::RateLimitRejection;
 use crate::node::lp::state::{SharedLpClientControlState, SharedLpNodeControlState};
+use nym_metrics::inc;
 use nym_task::ShutdownTracker;
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
@@ -137,6 +139,36 @@ impl LpControlListener {
             return;
         }
 
+        // Check per-source limits before committing to the expensive handshake
+        let permit = match self
+            .clients_handler_state
+            .rate_limiter
+            .try_admit(remote_addr.ip())
+        {
+            Ok(permit) => permit,
+            Err(rejection) => {
+                match rejection {
+                    RateLimitRejection::Penalised { .. } => {
+                        inc!("lp_rate_limit_rejected_penalised")
+                    }
+                    RateLimitRejection::IpConnectionLimit => {
+                        inc!("lp_rate_limit_rejected_ip_connections")
+                    }
+                    RateLimitRejection::SubnetConnectionLimit => {
+                        inc!("lp_rate_limit_rejected_subnet_connections")
+                    }
+                    RateLimitRejection::IpHandshakeRate => {
+                        inc!("lp_rate_limit_rejected_ip_handshakes")
+                    }
+                    RateLimitRejection::SubnetHandshakeRate => {
+                        inc!("lp_rate_limit_rejected_subnet_handshakes")
+                    }
+                }
+                debug!("rejecting LP connection from {remote_addr}: {rejection}");
+                return;
+            }
+        };
+
         debug!(
             "Accepting LP client connection from {remote_addr} ({active_connections} active connections)"
         );
@@ -147,6 +179,9 @@ impl LpControlListener {
 
         self.shutdown.try_spawn_named_with_shutdown(
             async move {
+                // Released once the connection is done
+                let _permit = permit;
+
                 // Increment connection counter
                 handler.metrics().network.new_ingress_lp_client_connection();
 
@@ -54,6 +54,11 @@ pub enum LpHandlerError {
     #[error("data channel is not yet implemented")]
     UnimplementedDataChannel,
 
+    #[error(
+        "{ip_addr} has been temporarily banned after repeated credential verification failures"
+    )]
+    BannedSource { ip_addr: IpAddr },
+
     #[error("{ip_addr} does not correspond to any known LP node")]
     NotLpNode { ip_addr: IpAddr },
 
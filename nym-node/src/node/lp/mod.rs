@@ -13,6 +13,8 @@
 // - lp_connections_total: Counter for total LP connections handled
 // - lp_client_hello_failed: Counter for ClientHello failures (timestamp validation, protocol errors)
 // - lp_handshakes_success: Counter for successful handshake completions
+// - lp_sessions_resumed: Counter for sessions resumed via a resumption handle instead of a full handshake
+// - lp_resumption_handles_issued: Counter for resumption handles issued to clients
 // - lp_handshakes_failed: Counter for failed handshakes
 // - lp_handshake_duration_seconds: Histogram of handshake durations (buckets: 10ms to 10s)
 // - lp_timestamp_validation_accepted: Counter for timestamp validations that passed
@@ -56,8 +58,19 @@
 // ## State Cleanup Metrics (in cleanup task)
 // - lp_states_cleanup_handshake_removed: Counter for stale handshakes removed by cleanup task
 // - lp_states_cleanup_session_removed: Counter for stale sessions removed by cleanup task
+// - lp_states_cleanup_resumption_removed: Counter for expired resumption handles removed by cleanup task
 // - lp_states_cleanup_demoted_removed: Counter for demoted (read-only) sessions removed by cleanup task
 //
+// ## Rate Limiting Metrics (in listener and registration.rs)
+// - lp_rate_limit_rejected_penalised: Counter for connections rejected because the source IP is banned
+// - lp_rate_limit_rejected_ip_connections: Counter for connections rejected due to the per-IP connection limit
+// - lp_rate_limit_rejected_subnet_connections: Counter for connections rejected due to the per-subnet connection limit
+// - lp_rate_limit_rejected_ip_handshakes: Counter for connections rejected due to the per-IP handshake rate
+// - lp_rate_limit_rejected_subnet_handshakes: Counter for connections rejected due to the per-subnet handshake rate
+// - lp_rate_limit_credential_failures: Counter for failed credential verifications attributed to a source IP
+// - lp_rate_limit_penalties_applied: Counter for source IPs banned after repeated credential failures
+// - lp_rate_limit_entries_pruned: Counter for rate limiting entries removed by cleanup task
+//
 // ## Subsession/Rekeying Metrics (in client_handler)
 // - lp_subsession_kk2_sent: Counter for SubsessionKK2 responses sent (indicates client initiated rekeying)
 // - lp_subsession_complete: Counter for successful subsession promotions
@@ -82,7 +95,8 @@ use tokio::sync::Semaphore;
 use tracing::error;
 
 use crate::node::lp::directory::LpNodes;
-use crate::node::lp::state::{ActiveLpSessions, SharedLpNodeControlState};
+use crate::node::lp::rate_limit::LpRateLimiter;
+use crate::node::lp::state::{ActiveLpSessions, ResumableLpSessions, SharedLpNodeControlState};
 pub use nym_mixnet_client::forwarder::{MixForwardingReceiver, mix_forwarding_channels};
 pub use state::{SharedLpClientControlState, SharedLpDataState, SharedLpState};
 
@@ -91,6 +105,7 @@ pub mod control;
 mod data;
 pub mod directory;
 pub mod error;
+pub mod rate_limit;
 mod registration;
 pub mod state;
 
@@ -115,6 +130,8 @@ impl LpSetup {
     ) -> Result<Self, NymNodeError> {
         // TODO: this will require loading old states from disk in the future
         let session_states = ActiveLpSessions::new();
+        let rate_limiter = LpRateLimiter::new(lp_config.rate_limiting);
+        let resumptions = ResumableLpSessions::new();
 
         let shared_lp_state = SharedLpState {
             metrics,
@@ -126,6 +143,8 @@ impl LpSetup {
             local_lp_peer: local_lp_peer.clone(),
             peer_registrator,
             forward_semaphore: Arc::new(Semaphore::new(lp_config.debug.max_concurrent_forwards)),
+            rate_limiter: rate_limiter.clone(),
+            resumptions: resumptions.clone(),
             shared: shared_lp_state.clone(),
         };
 
@@ -153,6 +172,8 @@ impl LpSetup {
         );
         let cleanup_task = CleanupTask::new(
             session_states,
+            rate_limiter,
+            resumptions,
             lp_config.debug,
             shutdown.clone_shutdown_token(),
         );
@@ -0,0 +1,521 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::lp::LpRateLimiting;
+use dashmap::DashMap;
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const REFILL_PERIOD: Duration = Duration::from_secs(60);
+
+/// Reason for refusing an incoming client connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateLimitRejection {
+    /// The source IP is temporarily banned after repeated credential verification failures
+    Penalised {
+        remaining: Duration,
+    },
+    IpConnectionLimit,
+    SubnetConnectionLimit,
+    IpHandshakeRate,
+    SubnetHandshakeRate,
+}
+
+impl Display for RateLimitRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateLimitRejection::Penalised { remaining } => write!(
+                f,
+                "banned for another {}s due to failed credential verifications",
+                remaining.as_secs()
+            ),
+            RateLimitRejection::IpConnectionLimit => {
+                write!(f, "too many concurrent connections from the IP address")
+            }
+            RateLimitRejection::SubnetConnectionLimit => {
+                write!(f, "too many concurrent connections from the subnet")
+            }
+            RateLimitRejection::IpHandshakeRate => {
+                write!(f, "too many handshakes started by the IP address")
+            }
+            RateLimitRejection::SubnetHandshakeRate => {
+                write!(f, "too many handshakes started by the subnet")
+            }
+        }
+    }
+}
+
+/// Token bucket holding up to `capacity` tokens, refilled at `capacity` per minute.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: u32, now: Instant) -> Self {
+        TokenBucket {
+            tokens: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, capacity: u32, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refilled = elapsed.as_secs_f64() / REFILL_PERIOD.as_secs_f64() * capacity as f64;
+        self.tokens = (self.tokens + refilled).min(capacity as f64);
+        self.last_refill = now;
+    }
+
+    fn has_token(&mut self, capacity: u32, now: Instant) -> bool {
+        self.refill(capacity, now);
+        self.tokens >= 1.0
+    }
+
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+
+    fn is_full(&mut self, capacity: u32, now: Instant) -> bool {
+        self.refill(capacity, now);
+        self.tokens >= capacity as f64
+    }
+}
+
+struct SourceState {
+    active_connections: usize,
+    handshakes: TokenBucket,
+    credential_failures: u32,
+    last_credential_failure: Option<Instant>,
+    banned_until: Option<Instant>,
+}
+
+impl SourceState {
+    fn new(cfg: &LpRateLimiting, now: Instant) -> Self {
+        SourceState {
+            active_connections: 0,
+            handshakes: TokenBucket::full(cfg.handshakes_per_ip_per_minute, now),
+            credential_failures: 0,
+            last_credential_failure: None,
+            banned_until: None,
+        }
+    }
+}
+
+struct SubnetState {
+    active_connections: usize,
+    handshakes: TokenBucket,
+}
+
+struct LpRateLimiterInner {
+    cfg: LpRateLimiting,
+    sources: DashMap<IpAddr, SourceState>,
+    subnets: DashMap<IpAddr, SubnetState>,
+}
+
+/// Per-IP and per-subnet limits on incoming LP client connections.
+///
+/// To avoid deadlocks, entries of `sources` are always locked before entries of `subnets`.
+#[derive(Clone)]
+pub struct LpRateLimiter {
+    inner: Arc<LpRateLimiterInner>,
+}
+
+/// Accounts for an admitted connection until it's dropped.
+pub(crate) struct LpConnectionPermit {
+    // `None` if rate limiting is disabled
+    limiter: Option<LpRateLimiter>,
+    ip: IpAddr,
+}
+
+impl Drop for LpConnectionPermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = &self.limiter {
+            limiter.release(self.ip);
+        }
+    }
+}
+
+impl LpRateLimiter {
+    pub fn new(cfg: LpRateLimiting) -> Self {
+        LpRateLimiter {
+            inner: Arc::new(LpRateLimiterInner {
+                cfg,
+                sources: DashMap::new(),
+                subnets: DashMap::new(),
+            }),
+        }
+    }
+
+    fn cfg(&self) -> &LpRateLimiting {
+        &self.inner.cfg
+    }
+
+    fn subnet(&self, ip: IpAddr) -> IpAddr {
+        match ip {
+            IpAddr::V4(ip) => {
+                let prefix = self.cfg().ipv4_subnet_prefix.min(32) as u32;
+                let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from_bits(ip.to_bits() & mask))
+            }
+            IpAddr::V6(ip) => {
+                let prefix = self.cfg().ipv6_subnet_prefix.min(128) as u32;
+                let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from_bits(ip.to_bits() & mask))
+            }
+        }
+    }
+
+    /// Attempt to admit a new connection (and thus a new handshake) from the provided address.
+    pub(crate) fn try_admit(&self, ip: IpAddr) -> Result<LpConnectionPermit, RateLimitRejection> {
+        // connections accepted on a dual-stack socket report IPv4 peers as IPv4-mapped IPv6 addresses
+        self.try_admit_at(ip.to_canonical(), Instant::now())
+    }
+
+    fn try_admit_at(
+        &self,
+        ip: IpAddr,
+        now: Instant,
+    ) -> Result<LpConnectionPermit, RateLimitRejection> {
+        let cfg = *self.cfg();
+        if !cfg.enabled {
+            return Ok(LpConnectionPermit { limiter: None, ip });
+        }
+
+        let mut source = self
+            .inner
+            .sources
+            .entry(ip)
+            .or_insert_with(|| SourceState::new(&cfg, now));
+
+        if let Some(banned_until) = source.banned_until {
+            if banned_until > now {
+                return Err(RateLimitRejection::Penalised {
+                    remaining: banned_until - now,
+                });
+            }
+            source.banned_until = None;
+        }
+        if source.active_connections >= cfg.max_connections_per_ip {
+            return Err(RateLimitRejection::IpConnectionLimit);
+        }
+        if !source
+            .handshakes
+            .has_token(cfg.handshakes_per_ip_per_minute, now)
+        {
+            return Err(RateLimitRejection::IpHandshakeRate);
+        }
+
+        let mut subnet = self
+            .inner
+            .subnets
+            .entry(self.subnet(ip))
+            .or_insert_with(|| SubnetState {
+                active_connections: 0,
+                handshakes: TokenBucket::full(cfg.handshakes_per_subnet_per_minute, now),
+            });
+        if subnet.active_connections >= cfg.max_connections_per_subnet {
+            return Err(RateLimitRejection::SubnetConnectionLimit);
+        }
+        if !subnet
+            .handshakes
+            .has_token(cfg.handshakes_per_subnet_per_minute, now)
+        {
+            return Err(RateLimitRejection::SubnetHandshakeRate);
+        }
+
+        source.handshakes.take();
+        source.active_connections += 1;
+        subnet.handshakes.take();
+        subnet.active_connections += 1;
+
+        Ok(LpConnectionPermit {
+            limiter: Some(self.clone()),
+            ip,
+        })
+    }
+
+    fn release(&self, ip: IpAddr) {
+        if let Some(mut source) = self.inner.sources.get_mut(&ip) {
+            source.active_connections = source.active_connections.saturating_sub(1);
+        }
+        if let Some(mut subnet) = self.inner.subnets.get_mut(&self.subnet(ip)) {
+            subnet.active_connections = subnet.active_connections.saturating_sub(1);
+        }
+    }
+
+    /// Record a failed credential verification of a client connected from the provided address.
+    /// Returns the duration of the ban imposed on the address, if any.
+    pub(crate) fn record_credential_failure(&self, ip: IpAddr) -> Option<Duration> {
+        self.record_credential_failure_at(ip.to_canonical(), Instant::now())
+    }
+
+    /// Check whether the provided address is currently banned.
+    pub(crate) fn is_banned(&self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+        self.inner
+            .sources
+            .get(&ip.to_canonical())
+            .and_then(|source| source.banned_until)
+            .is_some_and(|until| until > now)
+    }
+
+    fn record_credential_failure_at(&self, ip: IpAddr, now: Instant) -> Option<Duration> {
+        let cfg = *self.cfg();
+        if !cfg.enabled {
+            return None;
+        }
+
+        let mut source = self
+            .inner
+            .sources
+            .entry(ip)
+            .or_insert_with(|| SourceState::new(&cfg, now));
+
+        // forget about failures that happened long ago
+        if let Some(last_failure) = source.last_credential_failure
+            && now.saturating_duration_since(last_failure) > cfg.max_credential_failure_penalty
+        {
+            source.credential_failures = 0;
+        }
+        source.credential_failures += 1;
+        source.last_credential_failure = Some(now);
+
+        let excess = source
+            .credential_failures
+            .checked_sub(cfg.allowed_credential_failures)?;
+        if excess == 0 {
+            return None;
+        }
+
+        // double the penalty with every failure past the allowance
+        let penalty = cfg
+            .credential_failure_penalty
+            .checked_mul(1u32.checked_shl(excess - 1).unwrap_or(u32::MAX))
+            .unwrap_or(cfg.max_credential_failure_penalty)
+            .min(cfg.max_credential_failure_penalty);
+        source.banned_until = Some(now + penalty);
+        Some(penalty)
+    }
+
+    /// Remove entries that no longer carry any information, returning the number of removed entries.
+    pub(crate) fn prune(&self) -> usize {
+        self.prune_at(Instant::now())
+    }
+
+    fn prune_at(&self, now: Instant) -> usize {
+        let cfg = *self.cfg();
+        let mut removed = 0;
+
+        self.inner.sources.retain(|_, source| {
+            let banned = source.banned_until.is_some_and(|until| until > now);
+            let remembers_failures = source.last_credential_failure.is_some_and(|last| {
+                now.saturating_duration_since(last) <= cfg.max_credential_failure_penalty
+            });
+            let keep = source.active_connections > 0
+                || banned
+                || remembers_failures
+                || !source
+                    .handshakes
+                    .is_full(cfg.handshakes_per_ip_per_minute, now);
+            if !keep {
+                removed += 1;
+            }
+            keep
+        });
+
+        self.inner.subnets.retain(|_, subnet| {
+            let keep = subnet.active_connections > 0
+                || !subnet
+                    .handshakes
+                    .is_full(cfg.handshakes_per_subnet_per_minute, now);
+            if !keep {
+                removed += 1;
+            }
+            keep
+        });
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(cfg: LpRateLimiting) -> LpRateLimiter {
+        LpRateLimiter::new(cfg)
+    }
+
+    fn ip(raw: &str) -> IpAddr {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn limits_concurrent_connections_per_ip() {
+        let limiter = limiter(LpRateLimiting {
+            max_connections_per_ip: 2,
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        let first = limiter.try_admit_at(ip("10.0.0.1"), now).unwrap();
+        let _second = limiter.try_admit_at(ip("10.0.0.1"), now).unwrap();
+        assert_eq!(
+            limiter.try_admit_at(ip("10.0.0.1"), now).err(),
+            Some(RateLimitRejection::IpConnectionLimit)
+        );
+
+        // other addresses are unaffected
+        assert!(limiter.try_admit_at(ip("10.0.0.2"), now).is_ok());
+
+        // closing a connection frees up the slot
+        drop(first);
+        assert!(limiter.try_admit_at(ip("10.0.0.1"), now).is_ok());
+    }
+
+    #[test]
+    fn limits_concurrent_connections_per_subnet() {
+        let limiter = limiter(LpRateLimiting {
+            max_connections_per_subnet: 2,
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        let _a = limiter.try_admit_at(ip("10.0.0.1"), now).unwrap();
+        let _b = limiter.try_admit_at(ip("10.0.0.2"), now).unwrap();
+        assert_eq!(
+            limiter.try_admit_at(ip("10.0.0.3"), now).err(),
+            Some(RateLimitRejection::SubnetConnectionLimit)
+        );
+        assert!(limiter.try_admit_at(ip("10.0.1.1"), now).is_ok());
+
+        // the same applies to ipv6 subnets
+        let _c = limiter.try_admit_at(ip("2001:db8:1::1"), now).unwrap();
+        let _d = limiter.try_admit_at(ip("2001:db8:1:ffff::1"), now).unwrap();
+        assert_eq!(
+            limiter.try_admit_at(ip("2001:db8:1::2"), now).err(),
+            Some(RateLimitRejection::SubnetConnectionLimit)
+        );
+        assert!(limiter.try_admit_at(ip("2001:db8:2::1"), now).is_ok());
+    }
+
+    #[test]
+    fn ipv4_mapped_addresses_share_limits_with_ipv4() {
+        let limiter = limiter(LpRateLimiting {
+            max_connections_per_ip: 1,
+            ..Default::default()
+        });
+
+        let _permit = limiter.try_admit(ip("10.0.0.1")).unwrap();
+        assert_eq!(
+            limiter.try_admit(ip("::ffff:10.0.0.1")).err(),
+            Some(RateLimitRejection::IpConnectionLimit)
+        );
+    }
+
+    #[test]
+    fn limits_handshake_rate() {
+        let limiter = limiter(LpRateLimiting {
+            handshakes_per_ip_per_minute: 3,
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            drop(limiter.try_admit_at(ip("10.0.0.1"), now).unwrap());
+        }
+        assert_eq!(
+            limiter.try_admit_at(ip("10.0.0.1"), now).err(),
+            Some(RateLimitRejection::IpHandshakeRate)
+        );
+
+        // a token is refilled every 20s
+        let later = now + Duration::from_secs(20);
+        assert!(limiter.try_admit_at(ip("10.0.0.1"), later).is_ok());
+        assert!(limiter.try_admit_at(ip("10.0.0.1"), later).is_err());
+    }
+
+    #[test]
+    fn repeated_credential_failures_are_penalised_exponentially() {
+        let cfg = LpRateLimiting {
+            allowed_credential_failures: 2,
+            credential_failure_penalty: Duration::from_secs(10),
+            max_credential_failure_penalty: Duration::from_secs(35),
+            ..Default::default()
+        };
+        let limiter = limiter(cfg);
+        let source = ip("10.0.0.1");
+        let now = Instant::now();
+
+        assert_eq!(limiter.record_credential_failure_at(source, now), None);
+        assert_eq!(limiter.record_credential_failure_at(source, now), None);
+        assert_eq!(
+            limiter.record_credential_failure_at(source, now),
+            Some(Duration::from_secs(10))
+        );
+        assert!(matches!(
+            limiter.try_admit_at(source, now),
+            Err(RateLimitRejection::Penalised { .. })
+        ));
+        assert_eq!(
+            limiter.record_credential_failure_at(source, now),
+            Some(Duration::from_secs(20))
+        );
+        // capped at the maximum
+        assert_eq!(
+            limiter.record_credential_failure_at(source, now),
+            Some(Duration::from_secs(35))
+        );
+
+        // the ban expires
+        let later = now + Duration::from_secs(36);
+        assert!(limiter.try_admit_at(source, later).is_ok());
+
+        // and so does the memory of the failures
+        let much_later = later + Duration::from_secs(36);
+        assert_eq!(
+            limiter.record_credential_failure_at(source, much_later),
+            None
+        );
+    }
+
+    #[test]
+    fn disabled_limiter_admits_everything() {
+        let limiter = limiter(LpRateLimiting {
+            enabled: false,
+            max_connections_per_ip: 0,
+            ..Default::default()
+        });
+        let now = Instant::now();
+        assert!(limiter.try_admit_at(ip("10.0.0.1"), now).is_ok());
+        assert_eq!(
+            limiter.record_credential_failure_at(ip("10.0.0.1"), now),
+            None
+        );
+    }
+
+    #[test]
+    fn pruning_keeps_relevant_entries() {
+        let limiter = limiter(LpRateLimiting::default());
+        let now = Instant::now();
+
+        let permit = limiter.try_admit_at(ip("10.0.0.1"), now).unwrap();
+        limiter.record_credential_failure_at(ip("10.0.0.2"), now);
+
+        // active connection and recent failure are kept
+        assert_eq!(limiter.prune_at(now + Duration::from_secs(120)), 0);
+
+        drop(permit);
+        // the handshake bucket of the first address has been refilled by now
+        let removed = limiter.prune_at(now + Duration::from_secs(120));
+        assert_eq!(removed, 2, "source and subnet entries should be removed");
+
+        // and the failure is eventually forgotten
+        let removed = limiter.prune_at(now + Duration::from_secs(7200));
+        assert_eq!(removed, 1);
+        assert!(limiter.inner.sources.is_empty());
+        assert!(limiter.inner.subnets.is_empty());
+    }
+}
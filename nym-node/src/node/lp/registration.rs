@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::node::lp::state::SharedLpClientControlState;
+use nym_gateway::node::wireguard::GatewayWireguardError;
 use nym_lp::peer_config::LpReceiverIndex;
 use nym_metrics::{add_histogram_obs, inc};
 use nym_registration_common::dvpn::{
@@ -13,6 +14,7 @@ use nym_registration_common::{
     LpRegistrationRequest, LpRegistrationRequestData, LpRegistrationResponse, RegistrationMode,
     RegistrationStatus,
 };
+use std::net::IpAddr;
 use tracing::*;
 
 // Histogram buckets for LP registration duration tracking
@@ -55,6 +57,7 @@ impl SharedLpClientControlState {
 
     async fn process_dvpn_registration_finalisation(
         &self,
+        remote: IpAddr,
         sender: LpReceiverIndex,
         request: LpDvpnRegistrationFinalisation,
     ) -> LpRegistrationResponse {
@@ -69,6 +72,9 @@ impl SharedLpClientControlState {
             .on_final_lp_request(request, sender)
             .await
             .unwrap_or_else(|err| {
+                if matches!(err, GatewayWireguardError::CredentialVerificationError(_)) {
+                    self.on_credential_failure(remote);
+                }
                 LpRegistrationResponse::error(
                     format!("LP registration has failed: {err}"),
                     RegistrationMode::Dvpn,
@@ -76,8 +82,21 @@ impl SharedLpClientControlState {
             })
     }
 
+    /// Account for a failed credential verification, banning the source if it keeps failing
+    fn on_credential_failure(&self, remote: IpAddr) {
+        inc!("lp_rate_limit_credential_failures");
+        if let Some(penalty) = self.rate_limiter.record_credential_failure(remote) {
+            inc!("lp_rate_limit_penalties_applied");
+            warn!(
+                "banning {remote} for {}s after repeated credential verification failures",
+                penalty.as_secs()
+            );
+        }
+    }
+
     async fn process_dvpn_registration(
         &self,
+        remote: IpAddr,
         sender: LpReceiverIndex,
         request: Box<LpDvpnRegistrationRequestMessage>,
     ) -> LpRegistrationResponse {
@@ -89,7 +108,7 @@ impl SharedLpClientControlState {
                 self.process_dvpn_initial_registration(sender, req).await
             }
             LpDvpnRegistrationRequestMessageContent::Finalisation(req) => {
-                self.process_dvpn_registration_finalisation(sender, req)
+                self.process_dvpn_registration_finalisation(remote, sender, req)
                     .await
             }
         }
@@ -109,6 +128,7 @@ impl SharedLpClientControlState {
     /// Process an LP registration request
     pub async fn process_registration(
         &self,
+        remote: IpAddr,
         sender: LpReceiverIndex,
         request: LpRegistrationRequest,
     ) -> LpRegistrationResponse {
@@ -127,7 +147,7 @@ impl SharedLpClientControlState {
         // 2. Process based on mode
         let result = match request.registration_data {
             LpRegistrationRequestData::Dvpn { data } => {
-                self.process_dvpn_registration(sender, data).await
+                self.process_dvpn_registration(remote, sender, data).await
             }
             LpRegistrationRequestData::Mixnet { data } => {
                 self.process_mixnet_registration(data).await
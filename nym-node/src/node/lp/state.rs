@@ -5,15 +5,16 @@ use crate::config::LpConfig;
 use crate::node::lp::cleanup::TimestampedState;
 use crate::node::lp::directory::LpNodes;
 use crate::node::lp::error::LpHandlerError;
+use crate::node::lp::rate_limit::LpRateLimiter;
 use dashmap::DashMap;
 use dashmap::mapref::one::RefMut;
 use nym_gateway::node::wireguard::PeerRegistrator;
-use nym_lp::LpTransportSession;
 use nym_lp::peer::LpLocalPeer;
 use nym_lp::peer_config::LpReceiverIndex;
+use nym_lp::{LpTransportSession, ResumableSession, ResumptionHandle, ResumptionStore};
 use nym_mixnet_client::forwarder::MixForwardingSender;
 use nym_node_metrics::NymNodeMetrics;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::Semaphore;
 
 /// Shared state for LP control connections
@@ -33,6 +34,12 @@ pub struct SharedLpClientControlState {
     // this is temporary until there is persistent KKT/PSQ session between nodes
     pub forward_semaphore: Arc<Semaphore>,
 
+    /// Per-IP and per-subnet limits on incoming client connections
+    pub rate_limiter: LpRateLimiter,
+
+    /// Sessions that clients are allowed to resume without a full KKT/PSQ handshake
+    pub resumptions: ResumableLpSessions,
+
     /// Common shared data
     pub shared: SharedLpState,
 }
@@ -95,6 +102,44 @@ impl ActiveLpSessions {
     }
 }
 
+/// Single-use resumption handles issued to clients, keyed by the handle itself
+///
+/// Handles are removed as soon as a client attempts to use them (successfully or not)
+/// and expired ones are periodically purged by the cleanup task.
+#[derive(Clone, Default)]
+pub struct ResumableLpSessions {
+    store: Arc<Mutex<ResumptionStore>>,
+}
+
+impl ResumableLpSessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn store(&self) -> std::sync::MutexGuard<'_, ResumptionStore> {
+        // the store does not hold any invariants that could be broken by a panicking holder
+        self.store
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn insert(&self, resumable: ResumableSession) {
+        self.store().insert(resumable)
+    }
+
+    pub(crate) fn take(&self, handle: &ResumptionHandle) -> Option<ResumableSession> {
+        self.store().take(handle)
+    }
+
+    pub(crate) fn revoke(&self, handle: &ResumptionHandle) -> bool {
+        self.store().revoke(handle)
+    }
+
+    pub(crate) fn remove_expired(&self) -> usize {
+        self.store().remove_expired()
+    }
+}
+
 /// Shared state for LP connection handlers
 #[derive(Clone)]
 pub struct SharedLpState {
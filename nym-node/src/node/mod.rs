@@ -951,6 +951,7 @@ impl NymNode {
             data_port: self.config.lp.announced_data_port(),
             x25519: self.x25519_lp_keys.pk,
             kem_keys: self.compute_kem_key_hashes(),
+            supported_versions: nym_lp::packet::version::SUPPORTED.to_vec(),
         };
 
         // SAFETY: the only way for this call to fail is if serialisation of LewesProtocol fails.
@@ -13,23 +13,24 @@ use crate::lp_client::session_helpers::{extract_forwarded_response, prepare_send
 use nym_bandwidth_controller::{BandwidthTicketProvider, DEFAULT_TICKETS_TO_SPEND};
 use nym_credentials_interface::TicketType;
 use nym_crypto::asymmetric::{ed25519, x25519};
-use nym_lp::LpTransportSession;
 use nym_lp::peer::{DHKeyPair, LpLocalPeer, LpRemotePeer};
 use nym_lp::peer_config::LpReceiverIndex;
 use nym_lp::psq::initiator::HandshakeMode;
+use nym_lp::resumption::resume_as_initiator;
 use nym_lp::transport::traits::LpTransportChannel;
 use nym_lp::transport::{LpHandshakeChannel, LpTransportError};
 use nym_lp::{Ciphersuite, packet::EncryptedLpPacket, packet::version};
+use nym_lp::{LpTransportSession, ResumableSession};
 use nym_registration_common::dvpn::LpDvpnRegistrationResponseMessageContent;
 use nym_registration_common::{
     LpRegistrationRequest, LpRegistrationResponse, WireguardConfiguration,
     WireguardRegistrationData,
 };
 use nym_wireguard_types::PeerPublicKey;
-use rand09::{CryptoRng, RngCore};
+use rand09::{CryptoRng, RngCore, SeedableRng};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use tracing::{debug, warn};
 
@@ -64,6 +65,10 @@ pub struct LpRegistrationClient<S = TcpStream> {
     /// Created during handshake initiation.
     transport_session: Option<LpTransportSession>,
 
+    /// Data for resuming the most recently established session on a new connection.
+    /// Refreshed after every successful handshake or resumption.
+    resumable: Option<ResumableSession>,
+
     /// Configuration for timeouts and TCP parameters.
     pub(crate) config: LpRegistrationConfig,
 
@@ -113,11 +118,26 @@ where
             gateway_lp_address,
             gateway_supported_lp_protocol_version: lp_protocol,
             transport_session: None,
+            resumable: None,
             config,
             stream: None,
         }
     }
 
+    /// Attempt to resume the provided session on the next handshake instead of performing
+    /// the full KKT/PSQ exchange.
+    #[must_use]
+    pub fn with_resumable_session(mut self, resumable: ResumableSession) -> Self {
+        self.resumable = Some(resumable);
+        self
+    }
+
+    /// Take the data required for resuming the current session, e.g. so that
+    /// it could be used by a future instance of the client.
+    pub fn take_resumable_session(&mut self) -> Option<ResumableSession> {
+        self.resumable.take()
+    }
+
     /// Attempt to use this `LpRegistrationClient` as transport for `NestedSession`
     pub fn as_nested_connection(&mut self, exit_address: SocketAddr) -> NestedConnection<'_, S> {
         NestedConnection {
@@ -384,11 +404,51 @@ where
     /// received on the same connection. The connection remains open for
     /// registration/forwarding after handshake completes.
     async fn perform_handshake_inner(&mut self) -> Result<()> {
-        tracing::debug!("Starting LP handshake as initiator (persistent connection)");
-
         // Ensure we have a TCP connection
         self.ensure_connected().await?;
 
+        let session = match self.resumable.take() {
+            Some(resumable) if !resumable.is_expired(Instant::now()) => {
+                match self.try_resume_session(resumable).await {
+                    Ok(session) => session,
+                    Err(err) => {
+                        // the gateway closes the connection on a failed resumption
+                        debug!("failed to resume LP session: {err}. performing full handshake");
+                        self.close();
+                        self.ensure_connected().await?;
+                        self.perform_full_handshake().await?
+                    }
+                }
+            }
+            _ => self.perform_full_handshake().await?,
+        };
+
+        if !self.config.resumption_ttl.is_zero() {
+            match session.resumable(self.config.resumption_ttl) {
+                Ok(resumable) => self.resumable = Some(resumable),
+                Err(err) => warn!("failed to export LP resumption data: {err}"),
+            }
+        }
+
+        // Store the state machine (with established session) for later use
+        self.transport_session = Some(session);
+        Ok(())
+    }
+
+    async fn try_resume_session(
+        &mut self,
+        resumable: ResumableSession,
+    ) -> Result<LpTransportSession> {
+        tracing::debug!("Attempting to resume LP session {:?}", resumable.handle());
+
+        let mut rng = rand09::rngs::StdRng::from_os_rng();
+        let connection = self.stream_mut()?;
+        Ok(resume_as_initiator(connection, resumable, &mut rng).await?)
+    }
+
+    async fn perform_full_handshake(&mut self) -> Result<LpTransportSession> {
+        tracing::debug!("Starting LP handshake as initiator (persistent connection)");
+
         let local_peer = self.lp_local_peer.clone();
         let remote_peer = self.gateway_lp_peer.clone();
         let protocol_version = self.gateway_supported_lp_protocol_version;
@@ -404,9 +464,7 @@ where
         .complete_handshake()
         .await?;
 
-        // Store the state machine (with established session) for later use
-        self.transport_session = Some(session);
-        Ok(())
+        Ok(session)
     }
 
     /// This is an internal method only meant to be called by `Self::register_dvpn` if the gateway
@@ -67,6 +67,14 @@ pub struct LpRegistrationConfig {
     /// Since LP is registration-only with short-lived connections, keepalive is not needed.
     /// Default: None.
     pub tcp_keepalive: Option<Duration>,
+
+    /// Validity of the resumption handle retained after establishing a session.
+    ///
+    /// While the handle is valid, subsequent handshakes with the same gateway first attempt
+    /// to resume the session instead of performing the full KKT/PSQ exchange.
+    /// Zero disables resumption.
+    /// Default: 24 hours.
+    pub resumption_ttl: Duration,
 }
 
 impl Default for LpRegistrationConfig {
@@ -81,6 +89,9 @@ impl Default for LpRegistrationConfig {
             // nym-104: Optimized for registration-only protocol
             tcp_nodelay: true,   // Lower latency for small messages
             tcp_keepalive: None, // Not needed for ephemeral connections
+
+            // matches the default handle validity on the gateway side
+            resumption_ttl: Duration::from_secs(24 * 60 * 60),
         }
     }
 }
@@ -99,5 +110,6 @@ mod tests {
         assert_eq!(config.forward_timeout, Duration::from_secs(30));
         assert!(config.tcp_nodelay);
         assert_eq!(config.tcp_keepalive, None);
+        assert_eq!(config.resumption_ttl, Duration::from_secs(86400));
     }
 }
@@ -2,13 +2,62 @@
 // SPDX-License-Identifier: Apache-2.0
 // TODO REMOVE when you're working on new CPP branch
 #![allow(clippy::all)]
-// use nym_ffi_shared;
-use std::ffi::{c_char, c_int, CStr, CString};
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
 
-use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
-use std::mem::forget;
+use nym_ffi_shared::{FfiError, StatusCode};
+use nym_sdk::mixnet::ReconstructedMessage;
+use std::path::PathBuf;
+use std::ptr;
 mod types;
-use crate::types::types::{CMessageCallback, CStringCallback, ReceivedMessage, StatusCode};
+use crate::types::types::{CMessageCallback, CStringCallback, ReceivedMessage, UserData};
+
+fn to_status_code(result: Result<(), FfiError>) -> c_int {
+    match result {
+        Ok(_) => StatusCode::NoError as c_int,
+        Err(err) => err.status_code() as c_int,
+    }
+}
+
+fn store_handle(result: Result<u64, FfiError>, handle_out: *mut u64) -> c_int {
+    to_status_code(result.map(|handle| unsafe { *handle_out = handle }))
+}
+
+// SAFETY: the caller guarantees the pointer is either null or points to a valid C string
+unsafe fn read_str<'a>(ptr: *const c_char) -> Result<&'a str, c_int> {
+    if ptr.is_null() {
+        return Err(StatusCode::NullArgument as c_int);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| StatusCode::InvalidArgument as c_int)
+}
+
+// SAFETY: the caller guarantees the pointer is either null or points to `size` readable bytes
+unsafe fn read_bytes(ptr: *const u8, size: usize) -> Result<Vec<u8>, c_int> {
+    if ptr.is_null() {
+        return Err(StatusCode::NullArgument as c_int);
+    }
+    Ok(std::slice::from_raw_parts(ptr, size).to_vec())
+}
+
+// the message is borrowed for the duration of the callback and dropped afterwards
+fn trigger_message_callback(
+    callback: &CMessageCallback,
+    client: u64,
+    received: &ReconstructedMessage,
+    user_data: *mut c_void,
+) {
+    let sender_tag = received.sender_tag.map(|tag| tag.to_bytes());
+    let rec_for_c = ReceivedMessage {
+        message: received.message.as_ptr(),
+        size: received.message.len(),
+        sender_tag: sender_tag
+            .as_ref()
+            .map(|tag| tag.as_ptr())
+            .unwrap_or(ptr::null()),
+    };
+    callback.trigger(client, rec_for_c, user_data);
+}
 
 #[no_mangle]
 pub extern "C" fn init_logging() {
@@ -16,98 +65,153 @@ pub extern "C" fn init_logging() {
 }
 
 #[no_mangle]
-pub extern "C" fn init_ephemeral() -> c_int {
-    match nym_ffi_shared::init_ephemeral_internal() {
-        Ok(_) => StatusCode::NoError as c_int,
-        Err(_) => StatusCode::ClientInitError as c_int,
+pub extern "C" fn init_ephemeral(client_out: *mut u64) -> c_int {
+    if client_out.is_null() {
+        return StatusCode::NullArgument as c_int;
     }
+    store_handle(nym_ffi_shared::init_ephemeral_internal(), client_out)
 }
 
 #[no_mangle]
-pub extern "C" fn get_self_address(callback: CStringCallback) -> c_int {
-    match nym_ffi_shared::get_self_address_internal(/*callback*/) {
+pub extern "C" fn init_with_storage(config_dir: *const c_char, client_out: *mut u64) -> c_int {
+    if client_out.is_null() {
+        return StatusCode::NullArgument as c_int;
+    }
+    let config_dir = match unsafe { read_str(config_dir) } {
+        Ok(config_dir) => PathBuf::from(config_dir),
+        Err(code) => return code,
+    };
+    store_handle(
+        nym_ffi_shared::init_default_storage_internal(config_dir),
+        client_out,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn get_self_address(client: u64, callback: CStringCallback) -> c_int {
+    match nym_ffi_shared::get_self_address_internal(client) {
         Ok(addr) => {
-            let c_ptr = CString::new(addr).expect("could not convert Nym address to CString");
-            let call = CStringCallback::new(callback.callback);
+            let c_ptr =
+                CString::new(addr.to_string()).expect("could not convert Nym address to CString");
             // as_ptr() keeps ownership in rust unlike into_raw() so no need to free it
-            call.trigger(c_ptr.as_ptr());
+            callback.trigger(c_ptr.as_ptr());
             StatusCode::NoError as c_int
         }
-        Err(_) => StatusCode::SelfAddrError as c_int,
+        Err(err) => err.status_code() as c_int,
     }
 }
 
+/// Send `size` bytes of `message` to the recipient, attaching the default amount of reply SURBs.
 #[no_mangle]
-pub extern "C" fn send_message(recipient: *const c_char, message: *const c_char) -> c_int {
-    let c_str = unsafe {
-        if recipient.is_null() {
-            return StatusCode::RecipientNullError as c_int;
-        }
-        let c_str = CStr::from_ptr(recipient);
-        c_str
+pub extern "C" fn send_message(
+    client: u64,
+    recipient: *const c_char,
+    message: *const u8,
+    size: usize,
+) -> c_int {
+    send(client, recipient, message, size, None)
+}
+
+/// Send `size` bytes of `message` to the recipient, attaching `reply_surbs` reply SURBs.
+#[no_mangle]
+pub extern "C" fn send_message_with_surbs(
+    client: u64,
+    recipient: *const c_char,
+    message: *const u8,
+    size: usize,
+    reply_surbs: u32,
+) -> c_int {
+    send(client, recipient, message, size, Some(reply_surbs))
+}
+
+fn send(
+    client: u64,
+    recipient: *const c_char,
+    message: *const u8,
+    size: usize,
+    reply_surbs: Option<u32>,
+) -> c_int {
+    let recipient = match unsafe { read_str(recipient) } {
+        Ok(recipient) => match nym_ffi_shared::parse_recipient(recipient) {
+            Ok(recipient) => recipient,
+            Err(err) => return err.status_code() as c_int,
+        },
+        Err(code) => return code,
     };
-    let r_str = c_str.to_str().unwrap();
-    let recipient = r_str.parse().unwrap();
-    let c_str = unsafe {
-        if message.is_null() {
-            return StatusCode::MessageNullError as c_int;
-        }
-        let c_str = CStr::from_ptr(message);
-        c_str
+    let message = match unsafe { read_bytes(message, size) } {
+        Ok(message) => message,
+        Err(code) => return code,
     };
-    let message = c_str.to_str().unwrap();
 
-    match nym_ffi_shared::send_message_internal(recipient, message) {
-        Ok(_) => StatusCode::NoError as c_int,
-        Err(_) => StatusCode::SendMsgError as c_int,
-    }
+    to_status_code(nym_ffi_shared::send_message_internal(
+        client,
+        recipient,
+        message,
+        reply_surbs,
+    ))
 }
 
+/// Reply with `size` bytes of `message` using the 16 bytes long `sender_tag` of a received message.
 #[no_mangle]
-pub extern "C" fn reply(recipient: *const c_char, message: *const c_char) -> c_int {
-    let recipient = unsafe {
-        if recipient.is_null() {
-            return StatusCode::RecipientNullError as c_int;
-        }
-        let r_str = CStr::from_ptr(recipient).to_string_lossy().into_owned();
-        AnonymousSenderTag::try_from_base58_string(r_str)
-            .expect("could not construct AnonymousSenderTag from supplied value")
+pub extern "C" fn reply(
+    client: u64,
+    sender_tag: *const u8,
+    message: *const u8,
+    size: usize,
+) -> c_int {
+    let sender_tag = match unsafe { read_bytes(sender_tag, nym_ffi_shared::SENDER_TAG_SIZE) } {
+        Ok(sender_tag) => match nym_ffi_shared::parse_sender_tag(&sender_tag) {
+            Ok(sender_tag) => sender_tag,
+            Err(err) => return err.status_code() as c_int,
+        },
+        Err(code) => return code,
     };
-    let message = unsafe {
-        if message.is_null() {
-            return StatusCode::MessageNullError as c_int;
-        }
-        let c_str = CStr::from_ptr(message);
-        let r_str = c_str.to_str().unwrap();
-        r_str
+    let message = match unsafe { read_bytes(message, size) } {
+        Ok(message) => message,
+        Err(code) => return code,
     };
 
-    match nym_ffi_shared::reply_internal(recipient, message) {
-        Ok(_) => StatusCode::NoError as c_int,
-        Err(_) => StatusCode::ReplyError as c_int,
-    }
+    to_status_code(nym_ffi_shared::reply_internal(client, sender_tag, message))
 }
 
+/// Block until the client receives a message and pass it to the callback.
 #[no_mangle]
-pub extern "C" fn listen_for_incoming(callback: CMessageCallback) -> c_int {
-    match nym_ffi_shared::listen_for_incoming_internal() {
+pub extern "C" fn listen_for_incoming(
+    client: u64,
+    callback: CMessageCallback,
+    user_data: *mut c_void,
+) -> c_int {
+    match nym_ffi_shared::listen_for_incoming_internal(client) {
         Ok(received) => {
-            let message_ptr = received.message.as_ptr();
-            let message_length = received.message.len();
-            let c_string = CString::new(received.sender_tag.unwrap().to_string()).unwrap();
-            let sender_ptr = c_string.as_ptr();
-            // stop deallocation when out of scope as passing raw ptr to it elsewhere
-            forget(received);
-            let rec_for_c = ReceivedMessage {
-                message: message_ptr,
-                size: message_length,
-                sender_tag: sender_ptr,
-            };
-            let call = CMessageCallback::new(callback.callback);
-            call.trigger(rec_for_c);
-
+            trigger_message_callback(&callback, client, &received, user_data);
             StatusCode::NoError as c_int
         }
-        Err(_) => StatusCode::ListenError as c_int,
+        Err(err) => err.status_code() as c_int,
     }
 }
+
+/// Pass every message received by the client to the callback (invoked from a background thread)
+/// until `stop_listening` or `destroy_client` is called.
+#[no_mangle]
+pub extern "C" fn start_listening(
+    client: u64,
+    callback: CMessageCallback,
+    user_data: *mut c_void,
+) -> c_int {
+    let user_data = UserData(user_data);
+    to_status_code(nym_ffi_shared::start_listening_internal(
+        client,
+        move |received| trigger_message_callback(&callback, client, &received, user_data.get()),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn stop_listening(client: u64) -> c_int {
+    to_status_code(nym_ffi_shared::stop_listening_internal(client))
+}
+
+/// Disconnect the client from the mixnet. Its handle can no longer be used afterwards.
+#[no_mangle]
+pub extern "C" fn destroy_client(client: u64) -> c_int {
+    to_status_code(nym_ffi_shared::destroy_client_internal(client))
+}
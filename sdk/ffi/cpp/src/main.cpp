@@ -1,8 +1,14 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+#include <cstdint>
+#include <cstring>
+#include <future>
 #include <iostream>
+#include <mutex>
+#include <string>
 #include <thread>
+#include <vector>
 #include <boost/chrono.hpp>
 #include <boost/thread.hpp>
 
@@ -11,48 +17,59 @@ extern "C" {
     struct ReceivedMessage {
        const uint8_t* message;
        size_t size;
-       const char* sender_tag;
+       // either null or pointing to the 16 bytes of the sender tag
+       const uint8_t* sender_tag;
     };
 
-    void* init_logging();
-    char init_ephemeral();
-    char get_self_address(void (*callback)(const char*));
-    char send_message(const char*, const char*);
-    char listen_for_incoming(void (*callback)(ReceivedMessage));
-    char reply(const char*, const char*);
+    void init_logging();
+    int init_ephemeral(uint64_t* client_out);
+    int init_with_storage(const char* config_dir, uint64_t* client_out);
+    int get_self_address(uint64_t client, void (*callback)(const char*));
+    int send_message(uint64_t client, const char* recipient, const uint8_t* message, size_t size);
+    int send_message_with_surbs(uint64_t client, const char* recipient, const uint8_t* message, size_t size, uint32_t reply_surbs);
+    int reply(uint64_t client, const uint8_t* sender_tag, const uint8_t* message, size_t size);
+    int listen_for_incoming(uint64_t client, void (*callback)(uint64_t, ReceivedMessage, void*), void* user_data);
+    int start_listening(uint64_t client, void (*callback)(uint64_t, ReceivedMessage, void*), void* user_data);
+    int stop_listening(uint64_t client);
+    int destroy_client(uint64_t client);
 }
 
-//return code for error handling
-char return_code = 0;
-// bytes for sender tag
-char sender_tag[22];
-// nym address
-char addr[134];
-// test messages
-char message[14] = "Hello World";
-char reply_message[14] = "Reply World";
+// nym address of the last client we queried
+std::string addr;
+// test messages: binary payloads are passed as-is, including any null bytes
+const uint8_t message[] = {'H', 'e', 'l', 'l', 'o', 0x00, 0xff, 'W', 'o', 'r', 'l', 'd'};
+const uint8_t reply_message[] = {'R', 'e', 'p', 'l', 'y', 0x00, 0xff, 'W', 'o', 'r', 'l', 'd'};
 
 void string_callback_function(const char* c_string) {
     std::cout << "(c++)  callback received: " << c_string << std::endl;
-    strcpy(addr, c_string);
+    addr = c_string;
 }
 
-void incoming_message_callback(ReceivedMessage received) {
-    // this is where you deal with the incoming message -
-    // in this case we'll just log it and save sender_tag to a pre-allocated
-    // buffer to reply to the message further down in main()
-    std::cout << "(c++) sender tag: " << received.sender_tag << std::endl;
-    std::cout << "(c++) message: " << received.message << std::endl;
-    std::cout << "(c++) message length : " << received.size << std::endl;
-    const char* incoming_sender_tag = received.sender_tag;
-    strcpy(sender_tag, incoming_sender_tag);
+// invoked from a background thread for every message received by the listening client.
+// It's safe to call back into the library from here, e.g. to reply using the sender tag
+void listener_callback(uint64_t client, ReceivedMessage received, void* user_data) {
+    std::cout << "(c++) client " << client << " received " << received.size << " bytes" << std::endl;
+    if (received.sender_tag != nullptr) {
+        int return_code = reply(client, received.sender_tag, reply_message, sizeof(reply_message));
+        std::cout << "(c++) client " << client << " replied with code " << return_code << std::endl;
+    }
+    static std::once_flag notified;
+    std::call_once(notified, [user_data] { static_cast<std::promise<void>*>(user_data)->set_value(); });
+}
+
+// the pointers of the received message are only valid within the callback, so copy what we need
+void incoming_message_callback(uint64_t client, ReceivedMessage received, void* user_data) {
+    auto* buffer = static_cast<std::vector<uint8_t>*>(user_data);
+    buffer->assign(received.message, received.message + received.size);
+    std::cout << "(c++) client " << client << " received reply of " << received.size << " bytes" << std::endl;
 }
 
 // an overly simplified example - handle the error however you wish
-int handle(char return_code) {
+int handle(int return_code) {
     if (return_code == 0) {
         return 0;
     } else {
+        std::cout << "(c++) error code: " << return_code << std::endl;
         return -1;
     }
 }
@@ -67,47 +84,45 @@ int main() {
     // - execute
     // - get() returned val
     // - handle val
-    // initialise an ephemeral client - aka one without specified keystore
-    boost::packaged_task<char> init(boost::bind(init_ephemeral));
-    boost::unique_future<char> init_future = init.get_future();
-    init();
-    return_code = init_future.get();
-    handle(return_code);
-
-    // get_self_addr is sync so no thread required: this is the only exposed rust fn that isn't async
-    return_code = get_self_address(string_callback_function);
-    handle(return_code);
-
-    // send a message through the mixnet - in this case to ourselves using the value from get_self_address
-    std::cout << "(c++)  message to send through mixnet: " << message << std::endl;
-    boost::packaged_task<char> send(boost::bind(send_message, addr, message));
-    boost::unique_future<char> send_future = send.get_future();
-    send();
-    return_code = send_future.get();
-    handle(return_code);
-
-    // listen out for incoming messages: in the future the client can be split into a listening and a sending client,
-    // allowing for this to run as a persistent process in its own thread and not have to block but instead be running
-    // concurrently
-    boost::packaged_task<char> listen(boost::bind(listen_for_incoming, incoming_message_callback));
-    boost::unique_future<char> listen_future = listen.get_future();
+    // initialise two ephemeral clients - aka ones without specified keystore - identified by their handles
+    uint64_t alice = 0;
+    uint64_t bob = 0;
+    boost::packaged_task<int> init_alice(boost::bind(init_ephemeral, &alice));
+    boost::unique_future<int> init_alice_future = init_alice.get_future();
+    init_alice();
+    if (handle(init_alice_future.get()) != 0) return -1;
+
+    boost::packaged_task<int> init_bob(boost::bind(init_ephemeral, &bob));
+    boost::unique_future<int> init_bob_future = init_bob.get_future();
+    init_bob();
+    if (handle(init_bob_future.get()) != 0) return -1;
+
+    // get_self_addr is sync so no thread required
+    if (handle(get_self_address(bob, string_callback_function)) != 0) return -1;
+
+    // let bob process his incoming messages in the background: the callback replies to each of them
+    std::promise<void> bob_received;
+    if (handle(start_listening(bob, listener_callback, &bob_received)) != 0) return -1;
+
+    // alice sends a message to bob, attaching 10 reply SURBs so that he could reply without knowing her address
+    std::cout << "(c++) alice sending " << sizeof(message) << " bytes to bob" << std::endl;
+    if (handle(send_message_with_surbs(alice, addr.c_str(), message, sizeof(message), 10)) != 0) return -1;
+    bob_received.get_future().wait();
+
+    // alice blocks until she gets the reply
+    std::vector<uint8_t> received_reply;
+    boost::packaged_task<int> listen(boost::bind(listen_for_incoming, alice, incoming_message_callback, &received_reply));
+    boost::unique_future<int> listen_future = listen.get_future();
     listen();
-    return_code = listen_future.get();
-    handle(return_code);
-
-    // replying to incoming message (from ourselves) with SURBs - note that sending a message to a recipient and
-    // replying to an incoming are different functions: replying relies on parsing the incoming sender_tag on the Rust
-    // side and creating an AnonymousSenderTag type, instead of the Recipient type which relies on a nym address
-    boost::packaged_task<char> reply_fn(boost::bind(reply, sender_tag, reply_message));
-    boost::unique_future<char> reply_future = reply_fn.get_future();
-    reply_fn();
-    return_code = reply_future.get();
-    handle(return_code);
-
-    // sleep so that the client processes can catch up - in reality you'd have another process running to keep logging
-    // going, so this is only necessary for this reference
-    std::this_thread::sleep_for(std::chrono::seconds(40));
+    if (handle(listen_future.get()) != 0) return -1;
+    bool matches = received_reply.size() == sizeof(reply_message)
+        && std::memcmp(received_reply.data(), reply_message, sizeof(reply_message)) == 0;
+    std::cout << "(c++) reply matches what bob sent: " << std::boolalpha << matches << std::endl;
+
+    // disconnect both clients - their handles become invalid afterwards
+    handle(stop_listening(bob));
+    handle(destroy_client(bob));
+    handle(destroy_client(alice));
 
     return 0;
 }
-
@@ -4,21 +4,7 @@
 #![allow(clippy::all)]
 pub mod types {
 
-    use std::ffi::c_char;
-
-    // TODO change all the numbers / replace -2 with prxy?
-    #[derive(Debug)]
-    pub enum StatusCode {
-        NoError = 0,
-        ClientInitError = -1,
-        // ClientUninitialisedError = -2,
-        SelfAddrError = -3,
-        SendMsgError = -4,
-        ReplyError = -5,
-        ListenError = -6,
-        RecipientNullError = -7,
-        MessageNullError = -8,
-    }
+    use std::ffi::{c_char, c_void};
 
     #[repr(C)]
     pub struct CStringCallback {
@@ -36,22 +22,36 @@ pub mod types {
 
     #[repr(C)]
     pub struct CMessageCallback {
-        pub callback: extern "C" fn(ReceivedMessage),
+        pub callback: extern "C" fn(u64, ReceivedMessage, *mut c_void),
     }
 
     impl CMessageCallback {
-        pub fn new(callback: extern "C" fn(ReceivedMessage)) -> Self {
+        pub fn new(callback: extern "C" fn(u64, ReceivedMessage, *mut c_void)) -> Self {
             CMessageCallback { callback }
         }
-        pub fn trigger(&self, message: ReceivedMessage) {
-            (self.callback)(message)
+        pub fn trigger(&self, client: u64, message: ReceivedMessage, user_data: *mut c_void) {
+            (self.callback)(client, message, user_data)
+        }
+    }
+
+    // opaque pointer handed back to the caller's callback - it's up to them to make it thread safe
+    pub struct UserData(pub *mut c_void);
+
+    unsafe impl Send for UserData {}
+
+    impl UserData {
+        pub fn get(&self) -> *mut c_void {
+            self.0
         }
     }
 
+    /// Message passed to a `CMessageCallback`. The pointers are only valid for the duration
+    /// of the callback, so the data has to be copied if it's meant to outlive it.
     #[repr(C)]
     pub struct ReceivedMessage {
         pub message: *const u8,
         pub size: usize,
-        pub sender_tag: *const c_char,
+        /// Either null or pointing to `SENDER_TAG_SIZE` (16) bytes.
+        pub sender_tag: *const u8,
     }
 }
@@ -9,16 +9,16 @@ import (
 	"time"
 )
 
-func runProxyClient() {
-	run_err := bindings.RunProxyClient()
+func runProxyClient(proxyClient uint64) {
+	run_err := bindings.RunProxyClient(proxyClient)
 	if run_err != nil {
 		fmt.Println(run_err)
 		return
 	}
 }
 
-func runProxyServer() {
-	run_err := bindings.RunProxyServer()
+func runProxyServer(proxyServer uint64) {
+	run_err := bindings.RunProxyServer(proxyServer)
 	if run_err != nil {
 		fmt.Println(run_err)
 		return
@@ -99,14 +99,14 @@ func main() {
 	}
 
 	// init a proxy server
-	build_serv_err := bindings.NewProxyServer(upstreamAddress, configDir, &env_path, nil)
+	proxyServer, build_serv_err := bindings.NewProxyServer(upstreamAddress, configDir, &env_path, nil)
 	if build_serv_err != nil {
 		fmt.Println(build_serv_err)
 		return
 	}
 
 	// get proxy addr
-	proxyAddr, get_addr_err := bindings.ProxyServerAddress()
+	proxyAddr, get_addr_err := bindings.ProxyServerAddress(proxyServer)
 	if get_addr_err != nil {
 		fmt.Println("(Go) Error:", get_addr_err)
 		return
@@ -115,17 +115,17 @@ func main() {
 	fmt.Println(proxyAddr)
 
 	// run it in a goroutine
-	go runProxyServer()
+	go runProxyServer(proxyServer)
 
 	// initialise a proxy client
-	build_err := bindings.NewProxyClient(proxyAddr, "127.0.0.1", clientPort, clientTimeout, &env_path, 2)
+	proxyClient, build_err := bindings.NewProxyClient(proxyAddr, "127.0.0.1", clientPort, clientTimeout, &env_path, 2)
 	if build_err != nil {
 		fmt.Println(build_err)
 		return
 	}
 
 	// run it in a goroutine
-	go runProxyClient()
+	go runProxyClient(proxyClient)
 
 	// connect 'server-side' tcp socket to ProxyServer
 	go startTcpListener()
@@ -154,5 +154,13 @@ func main() {
 	// sleep so that the nym client processes can catch up - in reality you'd have another process
 	// running to keep logging going, so this is only necessary for this reference
 	time.Sleep(60 * time.Second)
+
+	// shut both proxies down - their handles become invalid afterwards
+	if err := bindings.DestroyProxyClient(proxyClient); err != nil {
+		fmt.Println("(Go) Error:", err)
+	}
+	if err := bindings.DestroyProxyServer(proxyServer); err != nil {
+		fmt.Println("(Go) Error:", err)
+	}
 	fmt.Println("(Go) end go example")
 }
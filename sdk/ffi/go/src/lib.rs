@@ -5,33 +5,85 @@
 #![allow(clippy::empty_line_after_doc_comments)]
 
 use nym_crypto::asymmetric::ed25519;
-use nym_sdk::mixnet::Recipient;
-use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
+use nym_ffi_shared::{FfiError, StatusCode};
+use nym_sdk::mixnet::ReconstructedMessage;
+use std::path::PathBuf;
 uniffi::include_scaffolding!("bindings");
 
+// each variant corresponds to a `StatusCode` of the shared FFI layer,
+// while its message carries the details of the underlying failure
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, thiserror::Error)]
 enum GoWrapError {
-    #[error("Couldn't init client")]
-    ClientInitError {},
-    #[error("Error getting self address")]
-    SelfAddrError {},
-    #[error("Error sending message")]
-    SendMsgError {},
-    #[error("Error sending reply")]
-    ReplyError {},
-    #[error("Could not start listening")]
-    ListenError {},
-    #[error("Couldn't init proxy client")]
-    ProxyInitError {},
-    #[error("Couldn't run proxy client")]
-    ProxyRunError {},
-    #[error("Couldn't init proxy server")]
-    ServerInitError {},
-    #[error("Couldn't get proxy server address")]
-    AddressGetterError {},
-    #[error("Couldn't run proxy server")]
-    ServerRunError {},
+    #[error("{0}")]
+    InvalidHandle(String),
+    #[error("{0}")]
+    InvalidArgument(String),
+    #[error("{0}")]
+    InvalidRecipient(String),
+    #[error("{0}")]
+    InvalidSenderTag(String),
+    #[error("{0}")]
+    ClientInitError(String),
+    #[error("{0}")]
+    SendMsgError(String),
+    #[error("{0}")]
+    ReplyError(String),
+    #[error("{0}")]
+    ListenError(String),
+    #[error("{0}")]
+    ListenerActive(String),
+    #[error("{0}")]
+    ProxyInitError(String),
+    #[error("{0}")]
+    ProxyRunError(String),
+    #[error("{0}")]
+    ServerInitError(String),
+    #[error("{0}")]
+    ServerRunError(String),
+}
+
+impl From<FfiError> for GoWrapError {
+    fn from(err: FfiError) -> Self {
+        let message = err.to_string();
+        match err.status_code() {
+            StatusCode::InvalidHandle => GoWrapError::InvalidHandle(message),
+            // null pointers can't be passed from Go
+            StatusCode::NullArgument | StatusCode::InvalidArgument => {
+                GoWrapError::InvalidArgument(message)
+            }
+            StatusCode::InvalidRecipient => GoWrapError::InvalidRecipient(message),
+            StatusCode::InvalidSenderTag => GoWrapError::InvalidSenderTag(message),
+            StatusCode::ClientInitError => GoWrapError::ClientInitError(message),
+            StatusCode::SendMsgError => GoWrapError::SendMsgError(message),
+            StatusCode::ReplyError => GoWrapError::ReplyError(message),
+            StatusCode::ListenError => GoWrapError::ListenError(message),
+            StatusCode::ListenerActive => GoWrapError::ListenerActive(message),
+            StatusCode::ProxyInitError => GoWrapError::ProxyInitError(message),
+            StatusCode::ProxyRunError => GoWrapError::ProxyRunError(message),
+            StatusCode::ServerInitError => GoWrapError::ServerInitError(message),
+            StatusCode::ServerRunError => GoWrapError::ServerRunError(message),
+            StatusCode::NoError => unreachable!("errors never map to the success code"),
+        }
+    }
+}
+
+pub struct IncomingMessage {
+    message: Vec<u8>,
+    sender_tag: Option<Vec<u8>>,
+}
+
+impl From<ReconstructedMessage> for IncomingMessage {
+    fn from(received: ReconstructedMessage) -> Self {
+        IncomingMessage {
+            message: received.message,
+            sender_tag: received.sender_tag.map(|tag| tag.to_bytes().to_vec()),
+        }
+    }
+}
+
+pub trait MessageHandler: Send + Sync {
+    fn on_message(&self, client: u64, message: IncomingMessage);
 }
 
 #[no_mangle]
@@ -40,58 +92,65 @@ fn init_logging() {
 }
 
 #[no_mangle]
-fn init_ephemeral() -> Result<(), GoWrapError> {
-    match nym_ffi_shared::init_ephemeral_internal() {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::ClientInitError {}),
-    }
+fn init_ephemeral() -> Result<u64, GoWrapError> {
+    Ok(nym_ffi_shared::init_ephemeral_internal()?)
 }
 
 #[no_mangle]
-fn get_self_address() -> Result<String, GoWrapError> {
-    match nym_ffi_shared::get_self_address_internal() {
-        Ok(addr) => Ok(addr),
-        Err(..) => Err(GoWrapError::SelfAddrError {}),
-    }
+fn init_with_storage(config_dir: String) -> Result<u64, GoWrapError> {
+    Ok(nym_ffi_shared::init_default_storage_internal(
+        PathBuf::from(config_dir),
+    )?)
 }
 
 #[no_mangle]
-fn send_message(recipient: String, message: String) -> Result<(), GoWrapError> {
-    let nym_recipient_type =
-        Recipient::try_from_base58_string(recipient).expect("couldn't create Recipient");
-    match nym_ffi_shared::send_message_internal(nym_recipient_type, &message) {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::SendMsgError {}),
-    }
+fn get_self_address(client: u64) -> Result<String, GoWrapError> {
+    Ok(nym_ffi_shared::get_self_address_internal(client)?.to_string())
 }
 
 #[no_mangle]
-fn reply(recipient: Vec<u8>, message: String) -> Result<(), GoWrapError> {
-    let mut sized_array: [u8; 16] = [0; 16];
-    sized_array.copy_from_slice(&recipient[..16]);
-    let anon_recipient_type: AnonymousSenderTag = AnonymousSenderTag::from_bytes(sized_array);
-    match nym_ffi_shared::reply_internal(anon_recipient_type, &message) {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::ReplyError {}),
-    }
+fn send_message(
+    client: u64,
+    recipient: String,
+    message: Vec<u8>,
+    reply_surbs: Option<u32>,
+) -> Result<(), GoWrapError> {
+    let recipient = nym_ffi_shared::parse_recipient(&recipient)?;
+    Ok(nym_ffi_shared::send_message_internal(
+        client,
+        recipient,
+        message,
+        reply_surbs,
+    )?)
 }
 
-pub struct IncomingMessage {
-    message: String,
-    sender: Vec<u8>,
+#[no_mangle]
+fn reply(client: u64, sender_tag: Vec<u8>, message: Vec<u8>) -> Result<(), GoWrapError> {
+    let sender_tag = nym_ffi_shared::parse_sender_tag(&sender_tag)?;
+    Ok(nym_ffi_shared::reply_internal(client, sender_tag, message)?)
 }
 
 #[no_mangle]
-fn listen_for_incoming() -> Result<IncomingMessage, GoWrapError> {
-    match nym_ffi_shared::listen_for_incoming_internal() {
-        Ok(received) => {
-            let message = String::from_utf8_lossy(&received.message).to_string();
-            let sender = received.sender_tag.unwrap().to_bytes().to_vec();
-            let incoming = IncomingMessage { message, sender };
-            Ok(incoming)
-        }
-        Err(_) => Err(GoWrapError::ListenError {}),
-    }
+fn listen_for_incoming(client: u64) -> Result<IncomingMessage, GoWrapError> {
+    Ok(nym_ffi_shared::listen_for_incoming_internal(client)?.into())
+}
+
+#[no_mangle]
+fn start_listening(client: u64, handler: Box<dyn MessageHandler>) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::start_listening_internal(
+        client,
+        move |received| handler.on_message(client, received.into()),
+    )?)
+}
+
+#[no_mangle]
+fn stop_listening(client: u64) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::stop_listening_internal(client)?)
+}
+
+#[no_mangle]
+fn destroy_client(client: u64) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::destroy_client_internal(client)?)
 }
 
 #[no_mangle]
@@ -102,40 +161,36 @@ fn new_proxy_client(
     close_timeout: u64,
     env: Option<String>,
     pool_size: u8,
-) -> Result<(), GoWrapError> {
-    let server_nym_addr =
-        Recipient::try_from_base58_string(server_address).expect("couldn't create Recipient");
-    match nym_ffi_shared::proxy_client_new_internal(
-        server_nym_addr,
+) -> Result<u64, GoWrapError> {
+    let server_address = nym_ffi_shared::parse_recipient(&server_address)?;
+    Ok(nym_ffi_shared::proxy_client_new_internal(
+        server_address,
         &listen_address,
         &listen_port,
         close_timeout,
         env,
         pool_size as usize,
-    ) {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::ProxyInitError {}),
-    }
+    )?)
 }
 
 #[no_mangle]
 fn new_proxy_client_default(
     server_address: String,
     env: Option<String>,
-) -> Result<(), GoWrapError> {
-    let server_nym_addr =
-        Recipient::try_from_base58_string(server_address).expect("couldn't create Recipient");
-    match nym_ffi_shared::proxy_client_new_defaults_internal(server_nym_addr, env) {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::ProxyInitError {}),
-    }
+) -> Result<u64, GoWrapError> {
+    let server_address = nym_ffi_shared::parse_recipient(&server_address)?;
+    Ok(nym_ffi_shared::proxy_client_new_defaults_internal(
+        server_address,
+        env,
+    )?)
 }
 
-fn run_proxy_client() -> Result<(), GoWrapError> {
-    match nym_ffi_shared::proxy_client_run_internal() {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::ProxyRunError {}),
-    }
+fn run_proxy_client(proxy_client: u64) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::proxy_client_run_internal(proxy_client)?)
+}
+
+fn destroy_proxy_client(proxy_client: u64) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::proxy_client_destroy_internal(proxy_client)?)
 }
 
 fn new_proxy_server(
@@ -143,43 +198,30 @@ fn new_proxy_server(
     config_dir: String,
     env: Option<String>,
     gateway: Option<String>,
-) -> Result<(), GoWrapError> {
-    if gateway.is_some() {
-        let gateway_key = match gateway {
-            Some(gateway_str) => match ed25519::PublicKey::from_base58_string(&gateway_str) {
-                Ok(key) => Ok(key),
-                Err(err) => Err(anyhow::anyhow!("Failed to parse gateway key: {}", err)),
-            },
-            None => Err(anyhow::anyhow!("Gateway string is None")),
-        };
-
-        match nym_ffi_shared::proxy_server_new_internal(
-            &upstream_address,
-            &config_dir,
-            env,
-            Some(gateway_key.expect("Couldn't unwrap gateway key")),
-        ) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(GoWrapError::ServerInitError {}),
-        }
-    } else {
-        match nym_ffi_shared::proxy_server_new_internal(&upstream_address, &config_dir, env, None) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(GoWrapError::ServerInitError {}),
-        }
-    }
+) -> Result<u64, GoWrapError> {
+    let gateway = gateway
+        .map(|gateway| {
+            ed25519::PublicKey::from_base58_string(&gateway).map_err(|err| {
+                FfiError::InvalidArgument(format!("failed to parse gateway key: {err}"))
+            })
+        })
+        .transpose()?;
+    Ok(nym_ffi_shared::proxy_server_new_internal(
+        &upstream_address,
+        &config_dir,
+        env,
+        gateway,
+    )?)
 }
 
-fn proxy_server_address() -> Result<String, GoWrapError> {
-    match nym_ffi_shared::proxy_server_address_internal() {
-        Ok(address) => Ok(address.to_string()),
-        Err(_) => Err(GoWrapError::AddressGetterError {}),
-    }
+fn proxy_server_address(proxy_server: u64) -> Result<String, GoWrapError> {
+    Ok(nym_ffi_shared::proxy_server_address_internal(proxy_server)?.to_string())
 }
 
-fn run_proxy_server() -> Result<(), GoWrapError> {
-    match nym_ffi_shared::proxy_server_run_internal() {
-        Ok(_) => Ok(()),
-        Err(_) => Err(GoWrapError::ServerRunError {}),
-    }
+fn run_proxy_server(proxy_server: u64) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::proxy_server_run_internal(proxy_server)?)
+}
+
+fn destroy_proxy_server(proxy_server: u64) -> Result<(), GoWrapError> {
+    Ok(nym_ffi_shared::proxy_server_destroy_internal(proxy_server)?)
 }
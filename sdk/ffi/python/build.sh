@@ -0,0 +1,53 @@
+#!/bin/bash
+
+PROJECT_NAME="python"
+
+set -eu
+MODE="--release"
+
+PACKAGE_DIR="./nym_python"
+TARGET_DIR="../../../target/release"
+
+case "$(uname -s)" in
+  Darwin) LIB_NAME="libnym_python_ffi.dylib" ;;
+  *) LIB_NAME="libnym_python_ffi.so" ;;
+esac
+
+build_artifacts() {
+  # build rust
+  cargo build $MODE -p nym-python-ffi
+  # build python bindings
+  printf "building python bindings \n"
+  cargo run $MODE -p nym-python-ffi --bin uniffi-bindgen -- generate \
+    --library "$TARGET_DIR/$LIB_NAME" \
+    --language python \
+    --out-dir $PACKAGE_DIR
+  cp "$TARGET_DIR/$LIB_NAME" $PACKAGE_DIR/
+  printf "bindings built \n\n"
+}
+
+clean_artifacts() {
+  # clean up existing things
+  rm -f $PACKAGE_DIR/nym_python_ffi.py $PACKAGE_DIR/libnym_python_ffi.*
+  cargo clean -p nym-python-ffi
+}
+
+if [ $(pwd | awk -F/ '{print $NF}') != ${PROJECT_NAME} ]
+then
+  printf "please run from root dir of project"
+  exit 1
+fi
+
+if [ $# -eq 0 ];
+then
+  build_artifacts;
+else
+  arg=$1
+  if [ "$arg" == "clean" ]; then
+    clean_artifacts;
+    build_artifacts;
+  else
+      printf "unknown optional argument - the only available optional argument is 'clean'"
+      exit 1
+  fi
+fi
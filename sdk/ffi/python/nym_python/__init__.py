@@ -0,0 +1,58 @@
+# Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+# SPDX-License-Identifier: Apache-2.0
+
+"""Python bindings for the Nym Rust SDK.
+
+The bindings themselves (``nym_python_ffi``) are generated by ``build.sh``.
+This module re-exports them and adds async iteration over received messages
+and accepted streams.
+"""
+
+from .nym_python_ffi import (
+    ClientOptions,
+    MixnetClient,
+    MixnetListener,
+    MixnetStream,
+    NymPythonError,
+    ReceivedMessage,
+    Socks5Client,
+    init_logging,
+    is_valid_address,
+)
+
+__all__ = [
+    "ClientOptions",
+    "MixnetClient",
+    "MixnetListener",
+    "MixnetStream",
+    "NymPythonError",
+    "ReceivedMessage",
+    "Socks5Client",
+    "init_logging",
+    "is_valid_address",
+]
+
+
+def _aiter(self):
+    return self
+
+
+async def _next_received_message(self):
+    message = await self.next_message()
+    if message is None:
+        raise StopAsyncIteration
+    return message
+
+
+async def _next_accepted_stream(self):
+    stream = await self.accept()
+    if stream is None:
+        raise StopAsyncIteration
+    return stream
+
+
+# `async for message in client` / `async for stream in listener`
+MixnetClient.__aiter__ = _aiter
+MixnetClient.__anext__ = _next_received_message
+MixnetListener.__aiter__ = _aiter
+MixnetListener.__anext__ = _next_accepted_stream
@@ -0,0 +1,280 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::stream::{MixnetListener, MixnetStream};
+use crate::{run, NymPythonError};
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt};
+use nym_sdk::mixnet::{
+    self, AnonymousSenderTag, CredentialStorage, GatewaysDetailsStore, IncludedSurbs, KeyStore,
+    MixnetClientBuilder, MixnetClientSender, MixnetClientStorage, MixnetMessageSender,
+    NymNetworkDetails, Recipient, ReconstructedMessage, ReplyStorageBackend, StoragePaths,
+};
+use nym_topology::provider_trait::HardcodedTopologyProvider;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Options used for constructing a client. All of them are optional.
+#[derive(uniffi::Record, Debug, Clone, Default)]
+pub struct ClientOptions {
+    /// Directory holding the keys and gateway registrations of the client.
+    /// If not set, the client uses ephemeral in-memory storage.
+    #[uniffi(default = None)]
+    pub storage_dir: Option<String>,
+
+    /// Path to an env file describing the network the client should connect to (mainnet if not set).
+    #[uniffi(default = None)]
+    pub env_file: Option<String>,
+
+    /// Path to a topology file (such as the `network.json` of a localnet) used instead of
+    /// retrieving the network topology from the nym-api.
+    #[uniffi(default = None)]
+    pub topology_file: Option<String>,
+
+    /// Identity key of the gateway the client should register with.
+    #[uniffi(default = None)]
+    pub gateway: Option<String>,
+}
+
+impl ClientOptions {
+    pub(crate) async fn connect<F, T>(&self, connect: F) -> Result<T, NymPythonError>
+    where
+        F: AsyncConnect<T>,
+    {
+        match &self.storage_dir {
+            Some(dir) => {
+                let paths = StoragePaths::new_from_dir(dir)?;
+                let builder = MixnetClientBuilder::new_with_default_storage(paths).await?;
+                connect.connect(self.configure(builder)?).await
+            }
+            None => {
+                connect
+                    .connect(self.configure(MixnetClientBuilder::new_ephemeral())?)
+                    .await
+            }
+        }
+    }
+
+    fn configure<S>(
+        &self,
+        mut builder: MixnetClientBuilder<S>,
+    ) -> Result<MixnetClientBuilder<S>, NymPythonError>
+    where
+        S: MixnetClientStorage + Clone + 'static,
+        S::ReplyStore: Send + Sync,
+        S::GatewaysDetailsStore: Sync,
+        <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
+        <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
+        <S::KeyStore as KeyStore>::StorageError: Send + Sync,
+        <S::GatewaysDetailsStore as GatewaysDetailsStore>::StorageError: Send + Sync,
+    {
+        if let Some(env_file) = &self.env_file {
+            nym_network_defaults::setup_env(Some(env_file));
+            builder = builder.network_details(NymNetworkDetails::new_from_env());
+        }
+        if let Some(topology_file) = &self.topology_file {
+            let provider =
+                HardcodedTopologyProvider::new_from_file(topology_file).map_err(|err| {
+                    NymPythonError::InvalidArgument(format!(
+                        "failed to load the topology from '{topology_file}': {err}"
+                    ))
+                })?;
+            builder = builder.custom_topology_provider(Box::new(provider));
+        }
+        if let Some(gateway) = &self.gateway {
+            builder = builder.request_gateway(gateway.clone());
+        }
+        Ok(builder)
+    }
+}
+
+/// Final step of connecting a configured builder, abstracted over the storage type
+/// so that both the native and the socks5 clients could share the option handling.
+pub(crate) trait AsyncConnect<T> {
+    async fn connect<S>(self, builder: MixnetClientBuilder<S>) -> Result<T, NymPythonError>
+    where
+        S: MixnetClientStorage + Clone + Send + Sync + 'static,
+        S::ReplyStore: Send + Sync,
+        S::GatewaysDetailsStore: Sync,
+        <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
+        <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
+        <S::KeyStore as KeyStore>::StorageError: Send + Sync,
+        <S::GatewaysDetailsStore as GatewaysDetailsStore>::StorageError: Send + Sync;
+}
+
+struct ConnectNative;
+
+impl AsyncConnect<mixnet::MixnetClient> for ConnectNative {
+    async fn connect<S>(
+        self,
+        builder: MixnetClientBuilder<S>,
+    ) -> Result<mixnet::MixnetClient, NymPythonError>
+    where
+        S: MixnetClientStorage + Clone + Send + Sync + 'static,
+        S::ReplyStore: Send + Sync,
+        S::GatewaysDetailsStore: Sync,
+        <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
+        <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
+        <S::KeyStore as KeyStore>::StorageError: Send + Sync,
+        <S::GatewaysDetailsStore as GatewaysDetailsStore>::StorageError: Send + Sync,
+    {
+        Ok(builder.build()?.connect_to_mixnet().await?)
+    }
+}
+
+/// A message received from the mixnet.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct ReceivedMessage {
+    pub message: Vec<u8>,
+
+    /// Tag of the sender that can be used for replying to them,
+    /// if they have attached reply SURBs to their message.
+    pub sender_tag: Option<Vec<u8>>,
+}
+
+impl From<ReconstructedMessage> for ReceivedMessage {
+    fn from(message: ReconstructedMessage) -> Self {
+        ReceivedMessage {
+            message: message.message,
+            sender_tag: message.sender_tag.map(|tag| tag.to_bytes().to_vec()),
+        }
+    }
+}
+
+pub(crate) fn parse_recipient(recipient: &str) -> Result<Recipient, NymPythonError> {
+    Recipient::try_from_base58_string(recipient).map_err(|err| {
+        NymPythonError::InvalidArgument(format!("invalid recipient '{recipient}': {err}"))
+    })
+}
+
+fn parse_sender_tag(sender_tag: &[u8]) -> Result<AnonymousSenderTag, NymPythonError> {
+    let bytes = sender_tag.try_into().map_err(|_| {
+        NymPythonError::InvalidArgument(format!(
+            "sender tag must be exactly 16 bytes long, got {}",
+            sender_tag.len()
+        ))
+    })?;
+    Ok(AnonymousSenderTag::from_bytes(bytes))
+}
+
+/// Check whether the provided string is a valid Nym address.
+#[uniffi::export]
+pub fn is_valid_address(address: String) -> bool {
+    parse_recipient(&address).is_ok()
+}
+
+/// A client connected to the mixnet, able to send and receive messages
+/// as well as open and accept streams.
+#[derive(uniffi::Object)]
+pub struct MixnetClient {
+    address: Recipient,
+    sender: MixnetClientSender,
+    inner: Arc<Mutex<Option<mixnet::MixnetClient>>>,
+}
+
+impl MixnetClient {
+    async fn with_client<F, T>(&self, f: F) -> Result<T, NymPythonError>
+    where
+        F: for<'a> FnOnce(&'a mut mixnet::MixnetClient) -> BoxFuture<'a, Result<T, NymPythonError>>
+            + Send
+            + 'static,
+        T: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        run(async move {
+            let mut guard = inner.lock().await;
+            let client = guard.as_mut().ok_or(NymPythonError::Disconnected)?;
+            f(client).await
+        })
+        .await
+    }
+}
+
+#[uniffi::export]
+impl MixnetClient {
+    /// Create a new client and connect it to the mixnet.
+    #[uniffi::constructor]
+    pub async fn connect(options: ClientOptions) -> Result<Arc<Self>, NymPythonError> {
+        let client = run(async move { options.connect(ConnectNative).await }).await?;
+        Ok(Arc::new(MixnetClient {
+            address: *client.nym_address(),
+            sender: client.split_sender(),
+            inner: Arc::new(Mutex::new(Some(client))),
+        }))
+    }
+
+    /// Nym address of this client.
+    pub fn nym_address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Send the message to the recipient, attaching the specified number of reply SURBs
+    /// so that they could reply without learning our address.
+    pub async fn send(
+        &self,
+        recipient: String,
+        message: Vec<u8>,
+        reply_surbs: u32,
+    ) -> Result<(), NymPythonError> {
+        let recipient = parse_recipient(&recipient)?;
+        let sender = self.sender.clone();
+        run(async move {
+            Ok(sender
+                .send_message(recipient, message, IncludedSurbs::new(reply_surbs))
+                .await?)
+        })
+        .await
+    }
+
+    /// Reply to a received message using its sender tag.
+    pub async fn reply(&self, sender_tag: Vec<u8>, message: Vec<u8>) -> Result<(), NymPythonError> {
+        let sender_tag = parse_sender_tag(&sender_tag)?;
+        let sender = self.sender.clone();
+        run(async move { Ok(sender.send_reply(sender_tag, message).await?) }).await
+    }
+
+    /// Wait for the next message received from the mixnet.
+    /// Returns `None` once the client got disconnected.
+    pub async fn next_message(&self) -> Result<Option<ReceivedMessage>, NymPythonError> {
+        self.with_client(|client| async move { Ok(client.next().await.map(Into::into)) }.boxed())
+            .await
+    }
+
+    /// Open a stream to the recipient. Note that once a stream is opened,
+    /// the client can no longer send or receive individual messages.
+    pub async fn open_stream(
+        &self,
+        recipient: String,
+        reply_surbs: Option<u32>,
+    ) -> Result<Arc<MixnetStream>, NymPythonError> {
+        let recipient = parse_recipient(&recipient)?;
+        self.with_client(move |client| {
+            async move {
+                let stream = client.open_stream(recipient, reply_surbs).await?;
+                Ok(Arc::new(MixnetStream::new(stream)))
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    /// Create a listener for streams opened by remote peers. It can only be created once.
+    pub async fn listener(&self) -> Result<Arc<MixnetListener>, NymPythonError> {
+        self.with_client(|client| {
+            async move { Ok(Arc::new(MixnetListener::new(client.listener()?))) }.boxed()
+        })
+        .await
+    }
+
+    /// Disconnect the client from the mixnet. Any subsequent operation is going to fail.
+    pub async fn disconnect(&self) -> Result<(), NymPythonError> {
+        let inner = self.inner.clone();
+        run(async move {
+            if let Some(client) = inner.lock().await.take() {
+                client.disconnect().await;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum NymPythonError {
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("mixnet client failure: {0}")]
+    Client(String),
+
+    #[error("the client has already been disconnected")]
+    Disconnected,
+
+    #[error("stream failure: {0}")]
+    Stream(String),
+
+    #[error("runtime failure: {0}")]
+    Runtime(String),
+}
+
+impl From<nym_sdk::Error> for NymPythonError {
+    fn from(err: nym_sdk::Error) -> Self {
+        NymPythonError::Client(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for NymPythonError {
+    fn from(err: std::io::Error) -> Self {
+        NymPythonError::Stream(err.to_string())
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Python bindings for the Nym Rust SDK.
+//!
+//! Unlike the Go and C++ bindings, which wrap a single global client, every client created here
+//! is an independent object. All async methods are driven by a runtime owned by this library,
+//! so they can be awaited from any Python event loop.
+
+use lazy_static::lazy_static;
+use std::future::Future;
+use tokio::runtime::Runtime;
+
+mod client;
+mod error;
+mod socks5;
+mod stream;
+
+pub use client::{ClientOptions, MixnetClient, ReceivedMessage};
+pub use error::NymPythonError;
+pub use socks5::Socks5Client;
+pub use stream::{MixnetListener, MixnetStream};
+
+uniffi::setup_scaffolding!();
+
+lazy_static! {
+    static ref RUNTIME: Runtime = Runtime::new().expect("failed to create tokio runtime");
+}
+
+/// Run the future on the library runtime and wait for its result.
+/// The returned future doesn't depend on tokio, so the foreign executor can poll it directly.
+async fn run<F, T>(fut: F) -> Result<T, NymPythonError>
+where
+    F: Future<Output = Result<T, NymPythonError>> + Send + 'static,
+    T: Send + 'static,
+{
+    RUNTIME
+        .spawn(fut)
+        .await
+        .map_err(|err| NymPythonError::Runtime(err.to_string()))?
+}
+
+#[uniffi::export]
+pub fn init_logging() {
+    nym_bin_common::logging::setup_tracing_logger();
+}
@@ -0,0 +1,95 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::{parse_recipient, AsyncConnect, ClientOptions};
+use crate::{run, NymPythonError};
+use nym_sdk::mixnet::{
+    CredentialStorage, GatewaysDetailsStore, KeyStore, MixnetClientBuilder, MixnetClientStorage,
+    Recipient, ReplyStorageBackend, Socks5, Socks5MixnetClient,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+struct ConnectSocks5 {
+    config: Socks5,
+}
+
+impl AsyncConnect<Socks5MixnetClient> for ConnectSocks5 {
+    async fn connect<S>(
+        self,
+        builder: MixnetClientBuilder<S>,
+    ) -> Result<Socks5MixnetClient, NymPythonError>
+    where
+        S: MixnetClientStorage + Clone + Send + Sync + 'static,
+        S::ReplyStore: Send + Sync,
+        S::GatewaysDetailsStore: Sync,
+        <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
+        <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
+        <S::KeyStore as KeyStore>::StorageError: Send + Sync,
+        <S::GatewaysDetailsStore as GatewaysDetailsStore>::StorageError: Send + Sync,
+    {
+        Ok(builder
+            .socks5_config(self.config)
+            .build()?
+            .connect_to_mixnet_via_socks5()
+            .await?)
+    }
+}
+
+/// A client exposing a local SOCKS5 proxy that tunnels all connections
+/// through the mixnet to a network requester.
+#[derive(uniffi::Object)]
+pub struct Socks5Client {
+    address: Recipient,
+    socks5_url: String,
+    inner: Arc<Mutex<Option<Socks5MixnetClient>>>,
+}
+
+#[uniffi::export]
+impl Socks5Client {
+    /// Create a new client using the specified network requester and start the SOCKS5 proxy.
+    /// `listen_port` defaults to 1080.
+    #[uniffi::constructor]
+    pub async fn connect(
+        provider: String,
+        listen_port: Option<u16>,
+        options: ClientOptions,
+    ) -> Result<Arc<Self>, NymPythonError> {
+        // validate the address before attempting to connect to anything
+        parse_recipient(&provider)?;
+
+        let mut config = Socks5::new(provider);
+        if let Some(port) = listen_port {
+            config.bind_address.set_port(port);
+        }
+
+        let client = run(async move { options.connect(ConnectSocks5 { config }).await }).await?;
+        Ok(Arc::new(Socks5Client {
+            address: *client.nym_address(),
+            socks5_url: client.socks5_url(),
+            inner: Arc::new(Mutex::new(Some(client))),
+        }))
+    }
+
+    /// Nym address of this client.
+    pub fn nym_address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Url of the local SOCKS5 proxy, e.g. `socks5h://127.0.0.1:1080`.
+    pub fn socks5_url(&self) -> String {
+        self.socks5_url.clone()
+    }
+
+    /// Stop the proxy and disconnect the client from the mixnet.
+    pub async fn disconnect(&self) -> Result<(), NymPythonError> {
+        let inner = self.inner.clone();
+        run(async move {
+            if let Some(client) = inner.lock().await.take() {
+                client.disconnect().await;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{run, NymPythonError};
+use nym_sdk::mixnet;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::sync::Mutex;
+
+/// A bidirectional byte stream to a single remote Nym client.
+///
+/// Reading and writing are independent of each other, so they can happen concurrently.
+#[derive(uniffi::Object)]
+pub struct MixnetStream {
+    id: u64,
+    reader: Arc<Mutex<ReadHalf<mixnet::MixnetStream>>>,
+    writer: Arc<Mutex<WriteHalf<mixnet::MixnetStream>>>,
+}
+
+impl MixnetStream {
+    pub(crate) fn new(stream: mixnet::MixnetStream) -> Self {
+        let id = stream.id().as_u64();
+        let (reader, writer) = tokio::io::split(stream);
+        MixnetStream {
+            id,
+            reader: Arc::new(Mutex::new(reader)),
+            writer: Arc::new(Mutex::new(writer)),
+        }
+    }
+}
+
+#[uniffi::export]
+impl MixnetStream {
+    /// Unique identifier of this stream.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Read up to `max_bytes` from the stream. An empty result indicates the end of the stream.
+    pub async fn read(&self, max_bytes: u32) -> Result<Vec<u8>, NymPythonError> {
+        let reader = self.reader.clone();
+        run(async move {
+            let mut buf = vec![0u8; max_bytes as usize];
+            let n = reader.lock().await.read(&mut buf).await?;
+            buf.truncate(n);
+            Ok(buf)
+        })
+        .await
+    }
+
+    /// Write the entire buffer into the stream.
+    pub async fn write_all(&self, data: Vec<u8>) -> Result<(), NymPythonError> {
+        let writer = self.writer.clone();
+        run(async move {
+            let mut writer = writer.lock().await;
+            writer.write_all(&data).await?;
+            Ok(writer.flush().await?)
+        })
+        .await
+    }
+
+    /// Close the stream, notifying the remote peer.
+    pub async fn close(&self) -> Result<(), NymPythonError> {
+        let writer = self.writer.clone();
+        run(async move { Ok(writer.lock().await.shutdown().await?) }).await
+    }
+}
+
+/// Accepts streams opened by remote peers.
+#[derive(uniffi::Object)]
+pub struct MixnetListener {
+    inner: Arc<Mutex<mixnet::MixnetListener>>,
+}
+
+impl MixnetListener {
+    pub(crate) fn new(listener: mixnet::MixnetListener) -> Self {
+        MixnetListener {
+            inner: Arc::new(Mutex::new(listener)),
+        }
+    }
+}
+
+#[uniffi::export]
+impl MixnetListener {
+    /// Wait for a remote peer to open a stream.
+    /// Returns `None` once the underlying client has shut down.
+    pub async fn accept(&self) -> Result<Option<Arc<MixnetStream>>, NymPythonError> {
+        let inner = self.inner.clone();
+        run(async move {
+            let stream = inner.lock().await.accept().await;
+            Ok(stream.map(|stream| Arc::new(MixnetStream::new(stream))))
+        })
+        .await
+    }
+}
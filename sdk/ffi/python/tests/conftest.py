@@ -0,0 +1,56 @@
+# Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+# SPDX-License-Identifier: Apache-2.0
+
+import asyncio
+import os
+
+import pytest
+
+import nym_python
+
+# Tests marked with `localnet` run against a local mixnet, e.g. the one from `docker/localnet`.
+# They are skipped unless the topology of that network is provided.
+TOPOLOGY_VAR = "NYM_PYTHON_TEST_TOPOLOGY"
+GATEWAY_VAR = "NYM_PYTHON_TEST_GATEWAY"
+NETWORK_REQUESTER_VAR = "NYM_PYTHON_TEST_NETWORK_REQUESTER"
+
+
+def pytest_collection_modifyitems(config, items):
+    if os.environ.get(TOPOLOGY_VAR):
+        return
+    skip = pytest.mark.skip(reason=f"{TOPOLOGY_VAR} is not set")
+    for item in items:
+        if "localnet" in item.keywords:
+            item.add_marker(skip)
+
+
+@pytest.fixture
+def run():
+    """Run the coroutine to completion, failing the test if it takes too long."""
+
+    def runner(coro, timeout=120):
+        return asyncio.run(asyncio.wait_for(coro, timeout))
+
+    return runner
+
+
+@pytest.fixture
+def localnet_options(tmp_path):
+    """Returns a factory of options for clients connecting to the local mixnet."""
+
+    def factory(name=None):
+        return nym_python.ClientOptions(
+            storage_dir=str(tmp_path / name) if name else None,
+            topology_file=os.environ[TOPOLOGY_VAR],
+            gateway=os.environ.get(GATEWAY_VAR),
+        )
+
+    return factory
+
+
+@pytest.fixture
+def network_requester():
+    address = os.environ.get(NETWORK_REQUESTER_VAR)
+    if not address:
+        pytest.skip(f"{NETWORK_REQUESTER_VAR} is not set")
+    return address
@@ -0,0 +1,37 @@
+# Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nym_python
+
+
+def test_invalid_address_is_rejected():
+    assert not nym_python.is_valid_address("")
+    assert not nym_python.is_valid_address("not-a-nym-address")
+
+
+def test_client_options_default_to_ephemeral_mainnet_client():
+    options = nym_python.ClientOptions()
+    assert options.storage_dir is None
+    assert options.env_file is None
+    assert options.topology_file is None
+    assert options.gateway is None
+
+
+def test_missing_topology_file_is_reported(run, tmp_path):
+    options = nym_python.ClientOptions(topology_file=str(tmp_path / "missing.json"))
+    with pytest.raises(nym_python.NymPythonError.InvalidArgument):
+        run(nym_python.MixnetClient.connect(options))
+
+
+def test_socks5_client_rejects_invalid_provider(run):
+    with pytest.raises(nym_python.NymPythonError.InvalidArgument):
+        run(nym_python.Socks5Client.connect("foo", None, nym_python.ClientOptions()))
+
+
+def test_clients_support_async_iteration():
+    assert hasattr(nym_python.MixnetClient, "__aiter__")
+    assert hasattr(nym_python.MixnetClient, "__anext__")
+    assert hasattr(nym_python.MixnetListener, "__aiter__")
+    assert hasattr(nym_python.MixnetListener, "__anext__")
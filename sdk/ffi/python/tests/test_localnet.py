@@ -0,0 +1,106 @@
+# Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+# SPDX-License-Identifier: Apache-2.0
+
+import socket
+from urllib.parse import urlparse
+
+import pytest
+
+import nym_python
+
+pytestmark = pytest.mark.localnet
+
+
+async def _first_message(client):
+    async for message in client:
+        return message
+    raise AssertionError("client got disconnected before receiving anything")
+
+
+def test_persistent_client_keeps_its_address(run, localnet_options):
+    async def scenario():
+        client = await nym_python.MixnetClient.connect(localnet_options("persistent"))
+        address = client.nym_address()
+        await client.disconnect()
+
+        client = await nym_python.MixnetClient.connect(localnet_options("persistent"))
+        try:
+            return address, client.nym_address()
+        finally:
+            await client.disconnect()
+
+    first, second = run(scenario())
+    assert nym_python.is_valid_address(first)
+    assert first == second
+
+
+def test_send_and_reply_with_surbs(run, localnet_options):
+    async def scenario():
+        alice = await nym_python.MixnetClient.connect(localnet_options("alice"))
+        bob = await nym_python.MixnetClient.connect(localnet_options())
+        try:
+            await alice.send(bob.nym_address(), b"\x00hello bob\xff", 5)
+            received = await _first_message(bob)
+
+            await bob.reply(received.sender_tag, b"hello alice")
+            reply = await _first_message(alice)
+            return received, reply
+        finally:
+            await alice.disconnect()
+            await bob.disconnect()
+
+    received, reply = run(scenario())
+    assert received.message == b"\x00hello bob\xff"
+    assert received.sender_tag is not None and len(received.sender_tag) == 16
+    assert reply.message == b"hello alice"
+
+
+def test_reply_requires_valid_sender_tag(run, localnet_options):
+    async def scenario():
+        client = await nym_python.MixnetClient.connect(localnet_options())
+        try:
+            await client.reply(b"too short", b"data")
+        finally:
+            await client.disconnect()
+
+    with pytest.raises(nym_python.NymPythonError.InvalidArgument):
+        run(scenario())
+
+
+def test_stream_roundtrip(run, localnet_options):
+    async def scenario():
+        dialer = await nym_python.MixnetClient.connect(localnet_options())
+        server = await nym_python.MixnetClient.connect(localnet_options())
+        try:
+            listener = await server.listener()
+            outbound = await dialer.open_stream(server.nym_address(), None)
+            await outbound.write_all(b"ping")
+
+            inbound = await listener.accept()
+            assert await inbound.read(1024) == b"ping"
+
+            await inbound.write_all(b"pong")
+            return await outbound.read(1024)
+        finally:
+            await dialer.disconnect()
+            await server.disconnect()
+
+    assert run(scenario()) == b"pong"
+
+
+def test_socks5_client_exposes_local_proxy(run, localnet_options, network_requester):
+    async def scenario():
+        client = await nym_python.Socks5Client.connect(
+            network_requester, 11080, localnet_options()
+        )
+        try:
+            url = urlparse(client.socks5_url())
+            # the proxy must be accepting connections
+            socket.create_connection((url.hostname, url.port), timeout=5).close()
+            return url
+        finally:
+            await client.disconnect()
+
+    url = run(scenario())
+    assert url.scheme == "socks5h"
+    assert url.port == 11080
@@ -0,0 +1,215 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FfiError, Handle, Registry, RUNTIME};
+use futures::StreamExt;
+use lazy_static::lazy_static;
+use nym_sdk::mixnet::{
+    IncludedSurbs, MixnetClient, MixnetClientBuilder, MixnetClientSender, MixnetMessageSender,
+    Recipient, ReconstructedMessage, StoragePaths,
+};
+use nym_sphinx_anonymous_replies::requests::{AnonymousSenderTag, SENDER_TAG_SIZE};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use tokio::task::JoinHandle;
+
+lazy_static! {
+    static ref CLIENTS: Registry<ClientEntry> = Registry::new("mixnet client");
+}
+
+struct ClientEntry {
+    address: Recipient,
+    sender: MixnetClientSender,
+    // the receiving half of the client: it's held either by a blocking read
+    // or, for as long as it's active, by the background task of the listener
+    client: tokio::sync::Mutex<Option<MixnetClient>>,
+    listener: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ClientEntry {
+    fn new(client: MixnetClient) -> Self {
+        ClientEntry {
+            address: *client.nym_address(),
+            sender: client.split_sender(),
+            client: tokio::sync::Mutex::new(Some(client)),
+            listener: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn take_listener(&self) -> Option<JoinHandle<()>> {
+        self.listener
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
+    fn has_active_listener(&self) -> bool {
+        self.listener
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+}
+
+pub fn parse_recipient(recipient: &str) -> Result<Recipient, FfiError> {
+    Recipient::try_from_base58_string(recipient).map_err(|err| FfiError::InvalidRecipient {
+        recipient: recipient.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+pub fn parse_sender_tag(sender_tag: &[u8]) -> Result<AnonymousSenderTag, FfiError> {
+    let bytes: [u8; SENDER_TAG_SIZE] = sender_tag
+        .try_into()
+        .map_err(|_| FfiError::InvalidSenderTag(sender_tag.len()))?;
+    Ok(AnonymousSenderTag::from_bytes(bytes))
+}
+
+pub fn init_ephemeral_internal() -> Result<Handle, FfiError> {
+    let client = RUNTIME
+        .block_on(MixnetClient::connect_new())
+        .map_err(|err| FfiError::ClientInit(Box::new(err)))?;
+    Ok(CLIENTS.insert(ClientEntry::new(client)))
+}
+
+pub fn init_default_storage_internal(config_dir: PathBuf) -> Result<Handle, FfiError> {
+    let client = RUNTIME
+        .block_on(async move {
+            let storage_paths = StoragePaths::new_from_dir(&config_dir)?;
+            MixnetClientBuilder::new_with_default_storage(storage_paths)
+                .await?
+                .build()?
+                .connect_to_mixnet()
+                .await
+        })
+        .map_err(|err| FfiError::ClientInit(Box::new(err)))?;
+    Ok(CLIENTS.insert(ClientEntry::new(client)))
+}
+
+pub fn get_self_address_internal(handle: Handle) -> Result<Recipient, FfiError> {
+    Ok(CLIENTS.get(handle)?.address)
+}
+
+/// Send the message to the recipient, attaching the specified number of reply SURBs
+/// (or the default amount if not specified).
+pub fn send_message_internal(
+    handle: Handle,
+    recipient: Recipient,
+    message: Vec<u8>,
+    reply_surbs: Option<u32>,
+) -> Result<(), FfiError> {
+    let sender = CLIENTS.get(handle)?.sender.clone();
+    let surbs = reply_surbs.map(IncludedSurbs::new).unwrap_or_default();
+    RUNTIME
+        .block_on(sender.send_message(recipient, message, surbs))
+        .map_err(|err| FfiError::Send(Box::new(err)))
+}
+
+pub fn reply_internal(
+    handle: Handle,
+    recipient: AnonymousSenderTag,
+    message: Vec<u8>,
+) -> Result<(), FfiError> {
+    let sender = CLIENTS.get(handle)?.sender.clone();
+    RUNTIME
+        .block_on(sender.send_reply(recipient, message))
+        .map_err(|err| FfiError::Reply(Box::new(err)))
+}
+
+/// Block until the client receives a non-empty message.
+/// Fails if a listener has been started for this client.
+pub fn listen_for_incoming_internal(handle: Handle) -> Result<ReconstructedMessage, FfiError> {
+    let entry = CLIENTS.get(handle)?;
+    if entry.has_active_listener() {
+        return Err(FfiError::ListenerActive(handle));
+    }
+
+    RUNTIME.block_on(async move {
+        let mut client = entry.client.lock().await;
+        let client = client.as_mut().ok_or(FfiError::Disconnected(handle))?;
+        wait_for_non_empty_message(client)
+            .await
+            .ok_or(FfiError::Disconnected(handle))
+    })
+}
+
+/// Start delivering every message received by the client to `on_message` until
+/// [`stop_listening_internal`] or [`destroy_client_internal`] is called.
+///
+/// The callback is invoked sequentially from a dedicated thread (rather than from within the runtime),
+/// so it's safe to call any other function of this crate, such as [`reply_internal`], from it.
+pub fn start_listening_internal<F>(handle: Handle, mut on_message: F) -> Result<(), FfiError>
+where
+    F: FnMut(ReconstructedMessage) + Send + 'static,
+{
+    let entry = CLIENTS.get(handle)?;
+    let mut listener = entry
+        .listener
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if listener.as_ref().is_some_and(|task| !task.is_finished()) {
+        return Err(FfiError::ListenerActive(handle));
+    }
+
+    let (messages_tx, messages_rx) = mpsc::channel();
+    thread::Builder::new()
+        .name(format!("nym-ffi-listener-{handle}"))
+        .spawn(move || {
+            // terminates once the receiving task is done and has dropped the sender
+            for message in messages_rx {
+                on_message(message)
+            }
+        })
+        .map_err(FfiError::Listen)?;
+
+    let receiving_entry = entry.clone();
+    *listener = Some(RUNTIME.spawn(async move {
+        let mut client = receiving_entry.client.lock().await;
+        let Some(client) = client.as_mut() else {
+            return;
+        };
+        while let Some(message) = wait_for_non_empty_message(client).await {
+            if messages_tx.send(message).is_err() {
+                return;
+            }
+        }
+    }));
+    Ok(())
+}
+
+pub fn stop_listening_internal(handle: Handle) -> Result<(), FfiError> {
+    let entry = CLIENTS.get(handle)?;
+    stop_listener(&entry);
+    Ok(())
+}
+
+fn stop_listener(entry: &ClientEntry) {
+    if let Some(task) = entry.take_listener() {
+        task.abort();
+        // wait for the task to actually terminate so that it releases the client
+        let _ = RUNTIME.block_on(task);
+    }
+}
+
+/// Disconnect the client from the mixnet and invalidate its handle.
+pub fn destroy_client_internal(handle: Handle) -> Result<(), FfiError> {
+    let entry = CLIENTS.remove(handle)?;
+    stop_listener(&entry);
+    RUNTIME.block_on(async move {
+        if let Some(client) = entry.client.lock().await.take() {
+            client.disconnect().await
+        }
+    });
+    Ok(())
+}
+
+pub async fn wait_for_non_empty_message(client: &mut MixnetClient) -> Option<ReconstructedMessage> {
+    while let Some(message) = client.next().await {
+        if !message.message.is_empty() {
+            return Some(message);
+        }
+    }
+    None
+}
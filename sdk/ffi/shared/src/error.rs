@@ -0,0 +1,91 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Handle;
+
+/// Status codes returned across the FFI boundary. They are stable so that bindings
+/// (e.g. C/C++) could rely on their numeric values for their own error handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum StatusCode {
+    NoError = 0,
+    InvalidHandle = -1,
+    NullArgument = -2,
+    InvalidArgument = -3,
+    InvalidRecipient = -4,
+    InvalidSenderTag = -5,
+    ClientInitError = -6,
+    SendMsgError = -7,
+    ReplyError = -8,
+    ListenError = -9,
+    ListenerActive = -10,
+    ProxyInitError = -11,
+    ProxyRunError = -12,
+    ServerInitError = -13,
+    ServerRunError = -14,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FfiError {
+    #[error("there is no {kind} associated with handle {handle}")]
+    InvalidHandle { kind: &'static str, handle: Handle },
+
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("invalid recipient address '{recipient}': {reason}")]
+    InvalidRecipient { recipient: String, reason: String },
+
+    #[error("sender tag must be exactly 16 bytes long, got {0}")]
+    InvalidSenderTag(usize),
+
+    #[error("failed to create the client: {0}")]
+    ClientInit(#[source] Box<nym_sdk::Error>),
+
+    #[error("failed to send the message: {0}")]
+    Send(#[source] Box<nym_sdk::Error>),
+
+    #[error("failed to send the reply: {0}")]
+    Reply(#[source] Box<nym_sdk::Error>),
+
+    #[error("client {0} got disconnected before receiving any message")]
+    Disconnected(Handle),
+
+    #[error("failed to start listening: {0}")]
+    Listen(#[source] std::io::Error),
+
+    #[error("client {0} already has an active listener")]
+    ListenerActive(Handle),
+
+    #[error("failed to create the proxy client: {0}")]
+    ProxyInit(anyhow::Error),
+
+    #[error("the proxy client failed: {0}")]
+    ProxyRun(anyhow::Error),
+
+    #[error("failed to create the proxy server: {0}")]
+    ServerInit(anyhow::Error),
+
+    #[error("the proxy server failed: {0}")]
+    ServerRun(anyhow::Error),
+}
+
+impl FfiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            FfiError::InvalidHandle { .. } => StatusCode::InvalidHandle,
+            FfiError::InvalidArgument(_) => StatusCode::InvalidArgument,
+            FfiError::InvalidRecipient { .. } => StatusCode::InvalidRecipient,
+            FfiError::InvalidSenderTag(_) => StatusCode::InvalidSenderTag,
+            FfiError::ClientInit(_) => StatusCode::ClientInitError,
+            FfiError::Send(_) => StatusCode::SendMsgError,
+            FfiError::Reply(_) => StatusCode::ReplyError,
+            FfiError::Disconnected(_) | FfiError::Listen(_) => StatusCode::ListenError,
+            FfiError::ListenerActive(_) => StatusCode::ListenerActive,
+            FfiError::ProxyInit(_) => StatusCode::ProxyInitError,
+            FfiError::ProxyRun(_) => StatusCode::ProxyRunError,
+            FfiError::ServerInit(_) => StatusCode::ServerInitError,
+            FfiError::ServerRun(_) => StatusCode::ServerRunError,
+        }
+    }
+}
@@ -1,274 +1,82 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{anyhow, bail, Error, Result};
-use lazy_static::lazy_static;
-use nym_sdk::mixnet::{
-    MixnetClient, MixnetClientBuilder, MixnetMessageSender, Recipient, ReconstructedMessage,
-    StoragePaths,
-};
+//! Internal functions shared by the C/C++ and Go FFI bindings.
+//!
+//! Every client (and proxy) created through this crate is identified by an opaque [`Handle`],
+//! so any number of them can coexist within the same process. All payloads are treated
+//! as raw bytes and every failure is reported as an [`FfiError`] carrying a stable [`StatusCode`].
 
-use nym_crypto::asymmetric::ed25519;
-use nym_sdk::tcp_proxy::{NymProxyClient, NymProxyServer};
-use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
-use std::path::PathBuf;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::runtime::Runtime;
 
-// NYM_CLIENT/PROXIES: Static thread-safe reference (init once) to Option<Client>s.
-// RUNTIME: Tokio runtime: no need to pass across FFI boundary and deal with raw pointers.
-lazy_static! {
-    static ref NYM_PROXY_CLIENT: Arc<Mutex<Option<NymProxyClient>>> = Arc::new(Mutex::new(None));
-    static ref NYM_PROXY_SERVER: Arc<Mutex<Option<NymProxyServer>>> = Arc::new(Mutex::new(None));
-    static ref NYM_CLIENT: Arc<Mutex<Option<MixnetClient>>> = Arc::new(Mutex::new(None));
-    static ref RUNTIME: Runtime = Runtime::new().unwrap();
-}
+mod client;
+mod error;
+mod proxy;
 
-pub fn init_ephemeral_internal() -> Result<(), Error> {
-    if NYM_CLIENT.lock().unwrap().as_ref().is_some() {
-        bail!("client already exists");
-    } else {
-        RUNTIME.block_on(async move {
-            let init_client = MixnetClient::connect_new().await?;
-            let mut client = NYM_CLIENT.try_lock();
-            if let Ok(ref mut client) = client {
-                **client = Some(init_client);
-            } else {
-                return Err(anyhow!("couldnt lock ephemeral NYM_CLIENT"));
-            }
-            Ok::<(), Error>(())
-        })?;
-    }
-    Ok(())
-}
+pub use client::*;
+pub use error::{FfiError, StatusCode};
+pub use nym_sphinx_anonymous_replies::requests::SENDER_TAG_SIZE;
+pub use proxy::*;
 
-pub fn init_default_storage_internal(config_dir: PathBuf) -> Result<(), Error> {
-    if NYM_CLIENT.lock().unwrap().as_ref().is_some() {
-        bail!("client already exists");
-    } else {
-        RUNTIME.block_on(async move {
-            let storage_paths = StoragePaths::new_from_dir(&config_dir).unwrap();
-            let init_client = MixnetClientBuilder::new_with_default_storage(storage_paths)
-                .await?
-                .build()?
-                .connect_to_mixnet()
-                .await?;
-            let mut client = NYM_CLIENT.try_lock();
-            if let Ok(ref mut client) = client {
-                **client = Some(init_client);
-            } else {
-                return Err(anyhow!("couldnt lock NYM_CLIENT"));
-            }
-            Ok::<(), Error>(())
-        })?;
-    }
-    Ok(())
-}
-
-pub fn get_self_address_internal() -> Result<String, Error> {
-    let client = NYM_CLIENT.lock().expect("could not lock NYM_CLIENT");
-    if client.is_none() {
-        bail!("Client is not yet initialised");
-    }
-    let nym_client = client
-        .as_ref()
-        .ok_or_else(|| anyhow!("could not get client as_ref()"))?;
-
-    Ok(nym_client.nym_address().to_string())
-}
-
-// TODO split sender
-
-pub fn send_message_internal(
-    recipient: Recipient,
-    message: &str,
-    // TODO add Option<surb_amount>, if Some(surb_amount) call send_message() instead with specified #, else send_plain_message as this uses the default
-) -> Result<(), Error> {
-    let client = NYM_CLIENT.lock().expect("could not lock NYM_CLIENT");
-    if client.is_none() {
-        bail!("Client is not yet initialised");
-    }
-    let nym_client = client
-        .as_ref()
-        .ok_or_else(|| anyhow!("could not get client as_ref()"))?;
-
-    RUNTIME.block_on(async move {
-        nym_client.send_plain_message(recipient, message).await?;
-        Ok::<(), Error>(())
-    })?;
-    Ok(())
-}
+/// Opaque identifier of an object (client, proxy client or proxy server) owned by this crate.
+/// Handles are never reused and `0` is never a valid handle.
+pub type Handle = u64;
 
-// TODO send_raw_message_internal
-
-pub fn reply_internal(recipient: AnonymousSenderTag, message: &str) -> Result<(), Error> {
-    let client = NYM_CLIENT.lock().expect("could not lock NYM_CLIENT");
-    if client.is_none() {
-        bail!("Client is not yet initialised");
-    }
-    let nym_client = client
-        .as_ref()
-        .ok_or_else(|| anyhow!("could not get client as_ref()"))?;
-
-    RUNTIME.block_on(async move {
-        nym_client.send_reply(recipient, message).await?;
-        Ok::<(), Error>(())
-    })?;
-    Ok(())
+// RUNTIME: Tokio runtime: no need to pass across FFI boundary and deal with raw pointers.
+// NEXT_HANDLE: shared between all the registries so that handles are unique process-wide.
+lazy_static! {
+    static ref RUNTIME: Runtime = Runtime::new().unwrap();
+    static ref NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
 }
 
-pub fn listen_for_incoming_internal() -> Result<ReconstructedMessage, Error> {
-    let mut binding = NYM_CLIENT.lock().expect("could not lock NYM_CLIENT");
-    if binding.is_none() {
-        bail!("recipient is null");
-    }
-    let client = binding
-        .as_mut()
-        .ok_or_else(|| anyhow!("could not get client as_ref()"))?;
-
-    let message = RUNTIME.block_on(async move {
-        let received = wait_for_non_empty_message(client).await?;
-        Ok::<ReconstructedMessage, Error>(ReconstructedMessage {
-            message: received.message,
-            sender_tag: received.sender_tag,
-        })
-    })?;
-
-    Ok(message)
+/// Thread-safe mapping between handles and the objects they refer to.
+pub(crate) struct Registry<T> {
+    kind: &'static str,
+    entries: Mutex<HashMap<Handle, Arc<T>>>,
 }
 
-pub async fn wait_for_non_empty_message(client: &mut MixnetClient) -> Result<ReconstructedMessage> {
-    while let Some(mut new_message) = client.wait_for_messages().await {
-        if !new_message.is_empty() {
-            return new_message
-                .pop()
-                .ok_or_else(|| anyhow!("could not get non empty message"));
+impl<T> Registry<T> {
+    pub(crate) fn new(kind: &'static str) -> Self {
+        Registry {
+            kind,
+            entries: Mutex::new(HashMap::new()),
         }
     }
-    bail!("(Rust) did not receive any non-empty message")
-}
 
-pub fn proxy_client_new_internal(
-    server_address: Recipient,
-    listen_address: &str,
-    listen_port: &str,
-    close_timeout: u64,
-    env: Option<String>,
-    pool_size: usize,
-) -> Result<(), Error> {
-    if NYM_PROXY_CLIENT.lock().unwrap().as_ref().is_some() {
-        bail!("proxy client already exists");
-    } else {
-        RUNTIME.block_on(async move {
-            let init_proxy_client = NymProxyClient::new(
-                server_address,
-                listen_address,
-                listen_port,
-                close_timeout,
-                env,
-                pool_size,
-            )
-            .await?;
-            let mut client = NYM_PROXY_CLIENT.try_lock();
-            if let Ok(ref mut client) = client {
-                **client = Some(init_proxy_client);
-            } else {
-                return Err(anyhow!("couldnt lock NYM_PROXY_CLIENT"));
-            }
-            Ok::<(), Error>(())
-        })?;
+    pub(crate) fn insert(&self, value: T) -> Handle {
+        let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+        self.lock().insert(handle, Arc::new(value));
+        handle
     }
-    Ok(())
-}
 
-pub fn proxy_client_new_defaults_internal(
-    server_address: Recipient,
-    env: Option<String>,
-) -> Result<(), Error> {
-    if NYM_PROXY_CLIENT.lock().unwrap().as_ref().is_some() {
-        bail!("proxy client already exists");
-    } else {
-        RUNTIME.block_on(async move {
-            let init_proxy_client = NymProxyClient::new_with_defaults(server_address, env).await?;
-            let mut client = NYM_PROXY_CLIENT.try_lock();
-            if let Ok(ref mut client) = client {
-                **client = Some(init_proxy_client);
-            } else {
-                return Err(anyhow!("couldn't lock PROXY_CLIENT"));
-            }
-            Ok::<(), Error>(())
-        })?;
+    pub(crate) fn get(&self, handle: Handle) -> Result<Arc<T>, FfiError> {
+        self.lock()
+            .get(&handle)
+            .cloned()
+            .ok_or_else(|| self.invalid(handle))
     }
-    Ok(())
-}
 
-pub fn proxy_client_run_internal() -> Result<(), Error> {
-    let proxy_client = NYM_PROXY_CLIENT
-        .lock()
-        .expect("could not lock NYM_PROXY_CLIENT");
-    if proxy_client.is_none() {
-        bail!("Client is not yet initialised");
+    pub(crate) fn remove(&self, handle: Handle) -> Result<Arc<T>, FfiError> {
+        self.lock()
+            .remove(&handle)
+            .ok_or_else(|| self.invalid(handle))
     }
-    let proxy = proxy_client
-        .as_ref()
-        .ok_or_else(|| anyhow!("could not get proxy_client as_ref()"))?;
-    RUNTIME.block_on(async move {
-        proxy.run().await?;
-        Ok::<(), Error>(())
-    })?;
-    Ok(())
-}
 
-pub fn proxy_server_new_internal(
-    upstream_address: &str,
-    config_dir: &str,
-    env: Option<String>,
-    gateway: Option<ed25519::PublicKey>,
-) -> Result<(), Error> {
-    if NYM_PROXY_SERVER.lock().unwrap().as_ref().is_some() {
-        bail!("proxy client already exists");
-    } else {
-        RUNTIME.block_on(async move {
-            let init_proxy_server =
-                NymProxyServer::new(upstream_address, config_dir, env, gateway).await?;
-            let mut client = NYM_PROXY_SERVER.try_lock();
-            if let Ok(ref mut client) = client {
-                **client = Some(init_proxy_server);
-            } else {
-                return Err(anyhow!("couldn't lock PROXY_SERVER"));
-            }
-            Ok::<(), Error>(())
-        })?;
-    }
-    Ok(())
-}
-
-pub fn proxy_server_run_internal() -> Result<(), Error> {
-    let mut proxy_server = NYM_PROXY_SERVER
-        .lock()
-        .expect("could not lock NYM_PROXY_CLIENT");
-    if proxy_server.is_none() {
-        bail!("Server is not yet initialised");
+    fn invalid(&self, handle: Handle) -> FfiError {
+        FfiError::InvalidHandle {
+            kind: self.kind,
+            handle,
+        }
     }
-    let proxy = proxy_server
-        .as_mut()
-        .ok_or_else(|| anyhow!("could not get proxy_client as_ref()"))?;
-    RUNTIME.block_on(async move {
-        proxy.run_with_shutdown().await?;
-        Ok::<(), Error>(())
-    })?;
-    Ok(())
-}
 
-pub fn proxy_server_address_internal() -> Result<Recipient, Error> {
-    let mut proxy_server = NYM_PROXY_SERVER
-        .lock()
-        .expect("could not lock NYM_PROXY_CLIENT");
-    if proxy_server.is_none() {
-        bail!("Server is not yet initialised");
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Handle, Arc<T>>> {
+        // the map is always left in a consistent state, so it's fine to carry on after a panic
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
-    let proxy = proxy_server
-        .as_mut()
-        .ok_or_else(|| anyhow!("could not get proxy_client as_ref()"))?;
-    Ok(proxy.nym_address().to_owned())
 }
@@ -0,0 +1,103 @@
+// Copyright 2026 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FfiError, Handle, Registry, RUNTIME};
+use lazy_static::lazy_static;
+use nym_crypto::asymmetric::ed25519;
+use nym_sdk::mixnet::Recipient;
+use nym_sdk::tcp_proxy::{NymProxyClient, NymProxyServer};
+
+lazy_static! {
+    static ref PROXY_CLIENTS: Registry<NymProxyClient> = Registry::new("proxy client");
+    static ref PROXY_SERVERS: Registry<ProxyServerEntry> = Registry::new("proxy server");
+}
+
+struct ProxyServerEntry {
+    // cached so that they're available while the server is running
+    address: Recipient,
+    shutdown: tokio::sync::mpsc::Sender<()>,
+    server: tokio::sync::Mutex<NymProxyServer>,
+}
+
+pub fn proxy_client_new_internal(
+    server_address: Recipient,
+    listen_address: &str,
+    listen_port: &str,
+    close_timeout: u64,
+    env: Option<String>,
+    pool_size: usize,
+) -> Result<Handle, FfiError> {
+    let proxy_client = RUNTIME
+        .block_on(NymProxyClient::new(
+            server_address,
+            listen_address,
+            listen_port,
+            close_timeout,
+            env,
+            pool_size,
+        ))
+        .map_err(FfiError::ProxyInit)?;
+    Ok(PROXY_CLIENTS.insert(proxy_client))
+}
+
+pub fn proxy_client_new_defaults_internal(
+    server_address: Recipient,
+    env: Option<String>,
+) -> Result<Handle, FfiError> {
+    let proxy_client = RUNTIME
+        .block_on(NymProxyClient::new_with_defaults(server_address, env))
+        .map_err(FfiError::ProxyInit)?;
+    Ok(PROXY_CLIENTS.insert(proxy_client))
+}
+
+/// Run the proxy client, blocking until it gets destroyed or fails.
+pub fn proxy_client_run_internal(handle: Handle) -> Result<(), FfiError> {
+    let proxy = PROXY_CLIENTS.get(handle)?;
+    RUNTIME.block_on(proxy.run()).map_err(FfiError::ProxyRun)
+}
+
+pub fn proxy_client_destroy_internal(handle: Handle) -> Result<(), FfiError> {
+    let proxy = PROXY_CLIENTS.remove(handle)?;
+    RUNTIME.block_on(proxy.disconnect());
+    Ok(())
+}
+
+pub fn proxy_server_new_internal(
+    upstream_address: &str,
+    config_dir: &str,
+    env: Option<String>,
+    gateway: Option<ed25519::PublicKey>,
+) -> Result<Handle, FfiError> {
+    let server = RUNTIME
+        .block_on(NymProxyServer::new(
+            upstream_address,
+            config_dir,
+            env,
+            gateway,
+        ))
+        .map_err(FfiError::ServerInit)?;
+    Ok(PROXY_SERVERS.insert(ProxyServerEntry {
+        address: *server.nym_address(),
+        shutdown: server.disconnect_signal(),
+        server: tokio::sync::Mutex::new(server),
+    }))
+}
+
+/// Run the proxy server, blocking until it gets destroyed or fails.
+pub fn proxy_server_run_internal(handle: Handle) -> Result<(), FfiError> {
+    let entry = PROXY_SERVERS.get(handle)?;
+    RUNTIME
+        .block_on(async move { entry.server.lock().await.run_with_shutdown().await })
+        .map_err(FfiError::ServerRun)
+}
+
+pub fn proxy_server_address_internal(handle: Handle) -> Result<Recipient, FfiError> {
+    Ok(PROXY_SERVERS.get(handle)?.address)
+}
+
+pub fn proxy_server_destroy_internal(handle: Handle) -> Result<(), FfiError> {
+    let entry = PROXY_SERVERS.remove(handle)?;
+    // if the server is not running, the signal is simply buffered and never read
+    let _ = entry.shutdown.try_send(());
+    Ok(())
+}
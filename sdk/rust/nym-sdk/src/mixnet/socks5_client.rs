@@ -1,3 +1,4 @@
+use std::net::SocketAddr;
 use std::time::Duration;
 
 use nym_client_core::client::base_client::ClientState;
@@ -103,6 +104,11 @@ impl Socks5MixnetClient {
         format!("socks5h://{}", self.socks5_config.bind_address)
     }
 
+    /// Get the local address the SOCKS5 proxy is listening on.
+    pub fn socks5_bind_address(&self) -> SocketAddr {
+        self.socks5_config.bind_address
+    }
+
     /// Get a shallow clone of [`LaneQueueLengths`]. This is useful to manually implement some form
     /// of backpressure logic.
     pub fn shared_lane_queue_lengths(&self) -> LaneQueueLengths {
@@ -8,6 +8,7 @@
 use anyhow::{bail, Context, Result};
 use bytes::Bytes;
 use nym_crypto::asymmetric::{ed25519, x25519};
+use nym_kcp::config::KcpConfig;
 use nym_kcp::driver::KcpDriver;
 use nym_kcp::session::KcpSession;
 use nym_registration_client::LpRegistrationClient;
@@ -234,7 +235,8 @@ impl SpeedtestClient {
 
         debug!("UDP socket bound to {local_addr}, conv_id={conv_id}");
 
-        let session = KcpSession::new(conv_id);
+        // the data plane goes through the lossy mixnet, so favour quick recovery over bandwidth
+        let session = KcpSession::with_config(conv_id, KcpConfig::fast());
         let driver = KcpDriver::new(session);
 
         self.socket = Some(socket);